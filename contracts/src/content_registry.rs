@@ -0,0 +1,81 @@
+//! Content Registry for Sampled Marketplace
+//!
+//! Links uploaded samples to the audio they contain:
+//! - Exact content hash -> the sample registered with it (duplicates are rejected)
+//! - Optional perceptual fingerprint -> every sample uploaded with it, for moderators
+//!   comparing near-identical audio (re-encodes, trims, ...)
+//!
+//! A hash is public once a sample or signed listing carries it, so anyone can race the
+//! producer to it. Moderators settle such disputes by releasing or reassigning the hash;
+//! delisting a sample keeps its hash reserved.
+
+use odra::prelude::*;
+
+use crate::types::constants::CONTENT_HASH_LENGTH;
+
+/// Check that a content hash is a SHA-256 as 64 lowercase hex characters
+pub fn is_valid_content_hash(content_hash: &str) -> bool {
+    content_hash.len() == CONTENT_HASH_LENGTH
+        && content_hash.bytes().all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b))
+}
+
+/// Content hash and fingerprint lookups
+#[odra::module]
+pub struct ContentRegistry {
+    /// Sample registered with a content hash: content_hash -> sample_id (0 = released)
+    hash_sample: Mapping<String, u64>,
+    /// Number of samples per fingerprint: fingerprint -> count
+    fingerprint_count: Mapping<String, u64>,
+    /// Indexed samples per fingerprint: (fingerprint, index) -> sample_id
+    fingerprint_sample_at: Mapping<(String, u64), u64>,
+}
+
+impl ContentRegistry {
+    /// Record a sample's content hash and (optional) fingerprint
+    pub fn register(&mut self, sample_id: u64, content_hash: String, fingerprint: Option<String>) {
+        self.hash_sample.set(&content_hash, sample_id);
+        if let Some(fingerprint) = fingerprint {
+            let count = self.fingerprint_count.get_or_default(&fingerprint);
+            self.fingerprint_sample_at.set(&(fingerprint.clone(), count), sample_id);
+            self.fingerprint_count.set(&fingerprint, count + 1);
+        }
+    }
+
+    /// Register a content hash to another sample (or release it with `None`)
+    pub fn assign(&mut self, content_hash: &String, sample_id: Option<u64>) {
+        self.hash_sample.set(content_hash, sample_id.unwrap_or_default());
+    }
+
+    /// Get the sample registered with a content hash (if any)
+    pub fn sample_by_hash(&self, content_hash: &String) -> Option<u64> {
+        self.hash_sample.get(content_hash).filter(|sample_id| *sample_id > 0)
+    }
+
+    /// Get all samples uploaded with a fingerprint, oldest first
+    pub fn samples_by_fingerprint(&self, fingerprint: &String) -> Vec<u64> {
+        let count = self.fingerprint_count.get_or_default(fingerprint);
+        let mut result = Vec::new();
+        for i in 0..count {
+            if let Some(sample_id) = self.fingerprint_sample_at.get(&(fingerprint.clone(), i)) {
+                result.push(sample_id);
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn content_hash_must_be_lowercase_sha256_hex() {
+        let hash = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08";
+        assert!(is_valid_content_hash(hash));
+        assert!(!is_valid_content_hash(&hash.to_uppercase()));
+        assert!(!is_valid_content_hash(&hash[1..]));
+        assert!(!is_valid_content_hash(&format!("{hash}0")));
+        assert!(!is_valid_content_hash(&hash.replace('f', "g")));
+        assert!(!is_valid_content_hash(""));
+    }
+}
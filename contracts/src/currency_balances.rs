@@ -0,0 +1,147 @@
+//! Currency Balances shared by the marketplace and the License NFT contract
+//!
+//! Keeps withdrawable balances in every supported currency.
+//! A currency is an `Option<Address>`: `None` is native CSPR (motes) and
+//! `Some(token)` is an admin-allowlisted CEP-18 token contract.
+//! Features:
+//! - Allowlist of accepted CEP-18 tokens
+//! - Withdrawable earnings and lifetime totals per user and currency
+//! - Payouts and token collection for either kind of currency
+
+use odra::prelude::*;
+use odra::casper_types::{U256, U512};
+use odra::ContractRef;
+use odra_modules::cep18_token::Cep18ContractRef;
+
+use crate::errors::Error;
+
+/// Multi-currency earnings and token allowlist
+#[odra::module]
+pub struct CurrencyBalances {
+    /// Whether a CEP-18 token is accepted for payments
+    accepted_tokens: Mapping<Address, bool>,
+    /// Number of tokens ever added to the allowlist
+    accepted_token_count: Var<u64>,
+    /// Indexed token addresses: index -> token
+    accepted_token_at: Mapping<u64, Address>,
+
+    /// Available (withdrawable) balance: (user, currency) -> amount
+    earnings: Mapping<(Address, Option<Address>), U512>,
+    /// Lifetime earnings: (user, currency) -> amount
+    total_earned: Mapping<(Address, Option<Address>), U512>,
+}
+
+impl CurrencyBalances {
+    // ============================================
+    // Token Allowlist
+    // ============================================
+
+    /// Add or remove a CEP-18 token from the allowlist
+    pub fn set_accepted(&mut self, token: Address, accepted: bool) {
+        let known = self.accepted_tokens.get(&token).is_some();
+        if !known {
+            let count = self.accepted_token_count.get_or_default();
+            self.accepted_token_at.set(&count, token);
+            self.accepted_token_count.set(count + 1);
+        }
+        self.accepted_tokens.set(&token, accepted);
+    }
+
+    /// Check if a currency can be used for payments (CSPR is always accepted)
+    pub fn is_accepted(&self, currency: &Option<Address>) -> bool {
+        match currency {
+            None => true,
+            Some(token) => self.accepted_tokens.get_or_default(token),
+        }
+    }
+
+    /// Revert unless the currency can be used for payments
+    pub fn assert_accepted(&self, currency: &Option<Address>) {
+        if !self.is_accepted(currency) {
+            self.env().revert(Error::TokenNotAccepted);
+        }
+    }
+
+    /// Get all currently accepted CEP-18 tokens
+    pub fn accepted_tokens(&self) -> Vec<Address> {
+        let count = self.accepted_token_count.get_or_default();
+        let mut result = Vec::new();
+        for i in 0..count {
+            if let Some(token) = self.accepted_token_at.get(&i) {
+                if self.accepted_tokens.get_or_default(&token) {
+                    result.push(token);
+                }
+            }
+        }
+        result
+    }
+
+    // ============================================
+    // Balances
+    // ============================================
+
+    /// Add to a user's withdrawable balance and lifetime earnings
+    pub fn credit_earnings(&mut self, user: Address, currency: Option<Address>, amount: U512) {
+        let key = (user, currency);
+        let earnings = self.earnings.get_or_default(&key) + amount;
+        self.earnings.set(&key, earnings);
+        let total = self.total_earned.get_or_default(&key) + amount;
+        self.total_earned.set(&key, total);
+    }
+
+    /// Reset a user's withdrawable balance and return the previous amount
+    pub fn take_earnings(&mut self, user: Address, currency: Option<Address>) -> U512 {
+        let key = (user, currency);
+        let earnings = self.earnings.get_or_default(&key);
+        self.earnings.set(&key, U512::zero());
+        earnings
+    }
+
+    /// Get a user's withdrawable balance
+    pub fn earnings(&self, user: &Address, currency: &Option<Address>) -> U512 {
+        self.earnings.get_or_default(&(*user, *currency))
+    }
+
+    /// Get a user's lifetime earnings
+    pub fn total_earned(&self, user: &Address, currency: &Option<Address>) -> U512 {
+        self.total_earned.get_or_default(&(*user, *currency))
+    }
+
+    // ============================================
+    // Transfers
+    // ============================================
+
+    /// Pay an amount out of the contract in the given currency
+    pub fn pay_out(&self, to: &Address, currency: &Option<Address>, amount: U512) {
+        if amount.is_zero() {
+            return;
+        }
+        match currency {
+            None => self.env().transfer_tokens(to, &amount),
+            Some(token) => {
+                let mut cep18 = Cep18ContractRef::new(self.env(), *token);
+                cep18.transfer(to, &self.to_token_amount(amount));
+            }
+        }
+    }
+
+    /// Pull CEP-18 tokens from `from` into this contract
+    /// Requires `from` to have approved this contract for at least `amount`
+    pub fn collect_token(&self, from: &Address, token: &Address, amount: U512) {
+        if amount.is_zero() {
+            return;
+        }
+        let mut cep18 = Cep18ContractRef::new(self.env(), *token);
+        cep18.transfer_from(from, &self.env().self_address(), &self.to_token_amount(amount));
+    }
+
+    /// Convert a U512 amount to the U256 used by CEP-18 tokens
+    fn to_token_amount(&self, amount: U512) -> U256 {
+        let mut bytes = [0u8; 64];
+        amount.to_little_endian(&mut bytes);
+        if bytes[32..].iter().any(|b| *b != 0) {
+            self.env().revert(Error::TokenAmountOverflow);
+        }
+        U256::from_little_endian(&bytes[..32])
+    }
+}
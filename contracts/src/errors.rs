@@ -35,6 +35,12 @@ pub enum Error {
     Unauthorized = 14,
     /// Transfer failed
     TransferFailed = 15,
+    /// Sample sale window has not opened yet
+    SaleNotStarted = 16,
+    /// Sample sale window has already closed
+    SaleEnded = 17,
+    /// Sale window is invalid (end must be after start and in the future)
+    InvalidSaleWindow = 18,
 
     // ============================================
     // License NFT Errors (20-39)
@@ -64,4 +70,235 @@ pub enum Error {
     CannotPurchaseOwnSample = 30,
     /// License NFT contract not set
     LicenseContractNotSet = 31,
+    /// Maximum supply for this license type has been reached
+    LicenseSoldOut = 32,
+    /// Max supply cannot be lower than the number of licenses already issued
+    InvalidMaxSupply = 33,
+    /// Minting is paused for this minter
+    MinterPaused = 34,
+    /// Minter has reached its minting cap
+    MinterCapReached = 35,
+    /// Minter is not registered
+    MinterNotFound = 36,
+    /// Recipient list is empty, too long, or includes the seller
+    InvalidRecipients = 37,
+    /// License has been permanently revoked
+    LicenseRevoked = 38,
+    /// License status change is not allowed from its current status
+    InvalidLicenseStatusChange = 39,
+
+    // ============================================
+    // Pricing Errors (40-59)
+    // ============================================
+
+    /// Pricing template with given name was not found
+    TemplateNotFound = 40,
+    /// Pricing template name is empty or exceeds maximum length
+    InvalidTemplateName = 41,
+    /// Pay-what-you-want samples are sold as downloads only, not with licenses
+    LicensesUnavailableForPayWhatYouWant = 42,
+    /// Absolute license prices are in motes, so they require a CSPR-priced sample
+    AbsolutePricingRequiresCspr = 43,
+
+    // ============================================
+    // Payment Errors (60-79)
+    // ============================================
+
+    /// CEP-18 token is not on the accepted token allowlist
+    TokenNotAccepted = 60,
+    /// Payment method does not match the sample's currency
+    WrongCurrency = 61,
+    /// Amount does not fit in a CEP-18 token amount (U256)
+    TokenAmountOverflow = 62,
+    /// Amount must be greater than 0
+    InvalidAmount = 63,
+    /// Buyer's prepaid credit balance is too low
+    InsufficientCredit = 64,
+    /// CSPR was attached to a purchase paid in CEP-18 tokens
+    UnexpectedAttachedValue = 65,
+
+    // ============================================
+    // Price Oracle Errors (80-89)
+    // ============================================
+
+    /// Price feed contract has not been set
+    PriceFeedNotSet = 80,
+    /// Latest price round is older than the allowed staleness
+    StalePrice = 81,
+    /// Price moved more than the allowed deviation since the previous feed round
+    PriceDeviationTooHigh = 82,
+    /// Price feed returned an invalid (zero) price
+    InvalidOraclePrice = 83,
+
+    // ============================================
+    // Subscription Errors (90-99)
+    // ============================================
+
+    /// Subscription plan with given ID was not found
+    PlanNotFound = 90,
+    /// Subscription plan no longer accepts subscribers
+    PlanInactive = 91,
+    /// Subscription period must be greater than 0
+    InvalidSubscriptionPeriod = 92,
+    /// Subscriber already has an active subscription to this producer
+    AlreadySubscribed = 93,
+    /// Subscriber has no subscription to renew on this plan
+    SubscriptionNotFound = 94,
+    /// Producers cannot subscribe to their own plans
+    CannotSubscribeToSelf = 95,
+    /// Subscription plans can only grant Personal licenses
+    InvalidPlanLicenseType = 96,
+
+    // ============================================
+    // Referral Errors (100-109)
+    // ============================================
+
+    /// Referrer cannot be the buyer or the seller
+    InvalidReferrer = 100,
+    /// Referral rate exceeds 100% (10,000 bps)
+    InvalidReferralRate = 101,
+
+    // ============================================
+    // Moderation Errors (110-119)
+    // ============================================
+
+    /// Report with given ID was not found
+    ReportNotFound = 110,
+    /// Invalid report reason code
+    InvalidReportReason = 111,
+    /// User already has an open report against this sample
+    AlreadyReported = 112,
+    /// Report has already been resolved or dismissed
+    ReportAlreadyClosed = 113,
+    /// Address is blocked from uploading or buying
+    AddressBlocked = 114,
+    /// Sample has been delisted by a moderator
+    SampleDelisted = 115,
+    /// Sample is already active
+    SampleAlreadyActive = 116,
+
+    // ============================================
+    // Usage Rights Errors (120-129)
+    // ============================================
+
+    /// Invalid usage type code
+    InvalidUsageType = 120,
+    /// Usage rights have a zero cap or zero duration
+    InvalidUsageRights = 121,
+    /// License has expired
+    LicenseExpired = 122,
+
+    // ============================================
+    // License Terms Errors (130-139)
+    // ============================================
+
+    /// Terms hash is empty or too long
+    InvalidTermsHash = 130,
+    /// Accepted terms do not match the terms in force
+    TermsMismatch = 131,
+
+    // ============================================
+    // Release Errors (140-149)
+    // ============================================
+
+    /// ISRC must be 12 letters and digits, without hyphens
+    InvalidIsrc = 140,
+    /// Release title is empty or too long
+    InvalidReleaseTitle = 141,
+    /// License type does not allow commercial release
+    ReleaseNotPermitted = 142,
+    /// A release with this ISRC is already registered
+    IsrcAlreadyRegistered = 143,
+
+    // ============================================
+    // Sublicense Errors (150-159)
+    // ============================================
+
+    /// License type cannot be sublicensed (Personal licenses and sublicenses)
+    SublicensingNotAllowed = 150,
+    /// Sublicense scope exceeds the parent license's rights
+    InvalidSublicenseScope = 151,
+    /// Sublicense expiry is in the past
+    InvalidSublicenseExpiry = 152,
+    /// Sublicenses cannot be transferred
+    CannotTransferSublicense = 153,
+
+    // ============================================
+    // License Tier Errors (160-169)
+    // ============================================
+
+    /// Tier name is empty or too long
+    InvalidTierName = 160,
+    /// All 256 tier IDs are taken
+    TierLimitReached = 161,
+    /// Licenses of this tier cannot be resold
+    LicenseNotTransferable = 162,
+    /// Exclusive tiers cannot be made transferable
+    ExclusiveTierTransferable = 163,
+
+    // ============================================
+    // Territory Errors (170-179)
+    // ============================================
+
+    /// Territory codes must be unique ISO 3166-1 alpha-2 codes, within the limit
+    InvalidTerritory = 170,
+    /// Seller does not sell licenses for this territory
+    TerritoryNotOffered = 171,
+
+    // ============================================
+    // Price Protection Errors (180-189)
+    // ============================================
+
+    /// Price rose above the buyer's `max_price` while the purchase was in flight
+    PriceAboveMaximum = 180,
+
+    // ============================================
+    // Signed Listing Errors (190-199)
+    // ============================================
+
+    /// Signature does not match the listing and seller key
+    InvalidListingSignature = 190,
+    /// Signed listing is past its expiry
+    ListingExpired = 191,
+    /// Listing nonce has already been redeemed or cancelled
+    ListingNonceUsed = 192,
+
+    // ============================================
+    // Meta-Transaction Errors (200-209)
+    // ============================================
+
+    /// Signature does not match the meta-transaction and signer key
+    InvalidMetaTxSignature = 200,
+    /// Meta-transaction is past its deadline
+    MetaTxExpired = 201,
+    /// Nonce is not the signer's next nonce
+    InvalidMetaTxNonce = 202,
+    /// Caller is not an allowlisted relayer
+    RelayerNotAllowed = 203,
+    /// Unknown action or arguments that do not match it
+    InvalidMetaTxAction = 204,
+
+    // ============================================
+    // Content Registry Errors (210-219)
+    // ============================================
+
+    /// Content hash is not a SHA-256 as 64 lowercase hex characters
+    InvalidContentHash = 210,
+    /// Audio with this content hash has already been uploaded
+    DuplicateContent = 211,
+    /// Fingerprint is empty or too long
+    InvalidFingerprint = 212,
+    /// Sample was not uploaded with this content hash
+    ContentHashMismatch = 213,
+
+    // ============================================
+    // Minter Errors (220-229)
+    // ============================================
+
+    /// Only the primary marketplace may do this
+    NotPrimaryMarketplace = 220,
+    /// Sample is not listed on the primary marketplace, or the creator is not its seller
+    CreatorMismatch = 221,
+    /// Primary marketplace has not been set
+    MarketplaceNotSet = 222,
 }
@@ -3,6 +3,9 @@
 //! Events are emitted for important state changes and can be
 //! indexed by off-chain services.
 
+// `#[odra::event]` generates a constructor taking one argument per field.
+#![allow(clippy::too_many_arguments)]
+
 use odra::prelude::*;
 use odra::casper_types::U512;
 
@@ -23,6 +26,12 @@ pub struct SampleUploaded {
     pub cover_image: String,
     /// Timestamp when the sample was uploaded
     pub timestamp: u64,
+    /// Timestamp when the sample goes on sale (None = immediately)
+    pub sale_starts_at: Option<u64>,
+    /// Timestamp when sales end (None = never)
+    pub sale_ends_at: Option<u64>,
+    /// Hash of the audio file
+    pub content_hash: String,
 }
 
 /// Emitted when a sample is purchased
@@ -40,6 +49,10 @@ pub struct SamplePurchased {
     pub platform_fee: U512,
     /// Timestamp of the purchase
     pub timestamp: u64,
+    /// Currency the price was paid in (None = CSPR)
+    pub currency: Option<Address>,
+    /// Motes per USD used to convert a USD-priced sample (None = not converted)
+    pub conversion_rate: Option<U512>,
 }
 
 /// Emitted when a seller withdraws their earnings
@@ -47,10 +60,12 @@ pub struct SamplePurchased {
 pub struct EarningsWithdrawn {
     /// Address of the user withdrawing
     pub user: Address,
-    /// Amount withdrawn in motes
+    /// Amount withdrawn in motes or token units
     pub amount: U512,
     /// Timestamp of the withdrawal
     pub timestamp: u64,
+    /// Currency withdrawn (None = CSPR)
+    pub currency: Option<Address>,
 }
 
 /// Emitted when a sample is deactivated
@@ -77,6 +92,376 @@ pub struct PriceUpdated {
     pub timestamp: u64,
 }
 
+/// Emitted when a sample's sale window is changed
+#[odra::event]
+pub struct SaleWindowUpdated {
+    /// Unique identifier of the sample
+    pub sample_id: u64,
+    /// Timestamp when the sample goes on sale (None = immediately)
+    pub sale_starts_at: Option<u64>,
+    /// Timestamp when sales end (None = never)
+    pub sale_ends_at: Option<u64>,
+    /// Timestamp of the update
+    pub timestamp: u64,
+}
+
+/// Emitted when a sample's payment currency is changed
+#[odra::event]
+pub struct SampleCurrencyUpdated {
+    /// Unique identifier of the sample
+    pub sample_id: u64,
+    /// New currency (None = CSPR, Some = CEP-18 token contract)
+    pub currency: Option<Address>,
+    /// New price in the new currency
+    pub price: U512,
+    /// Timestamp of the update
+    pub timestamp: u64,
+}
+
+/// Emitted when a sample's USD price is set or cleared
+#[odra::event]
+pub struct UsdPriceUpdated {
+    /// Unique identifier of the sample
+    pub sample_id: u64,
+    /// New USD price in cents (None = priced in motes again)
+    pub usd_price_cents: Option<u64>,
+    /// Timestamp of the update
+    pub timestamp: u64,
+}
+
+/// Emitted when the admin configures the price feed
+#[odra::event]
+pub struct PriceFeedUpdated {
+    /// Address of the price feed contract
+    pub feed: Address,
+    /// Maximum age of a price round
+    pub max_staleness: u64,
+    /// Maximum change from the last used rate in basis points
+    pub max_deviation_bps: u64,
+    /// Timestamp of the update
+    pub timestamp: u64,
+}
+
+/// Emitted when a sample's pay-what-you-want mode is changed
+#[odra::event]
+pub struct PayWhatYouWantUpdated {
+    /// Unique identifier of the sample
+    pub sample_id: u64,
+    /// Whether pay-what-you-want is enabled
+    pub enabled: bool,
+    /// Minimum price (or fixed price when disabled)
+    pub min_price: U512,
+    /// Timestamp of the update
+    pub timestamp: u64,
+}
+
+/// Emitted when a producer receives a tip
+#[odra::event]
+pub struct TipReceived {
+    /// Producer receiving the tip
+    pub seller: Address,
+    /// Address of the tipper
+    pub tipper: Address,
+    /// Sample the tip was given for
+    pub sample_id: u64,
+    /// Tip amount in motes
+    pub amount: U512,
+    /// Platform fee taken from the tip
+    pub platform_fee: U512,
+    /// Timestamp of the tip
+    pub timestamp: u64,
+}
+
+/// Emitted when a CEP-18 token is added to or removed from the allowlist
+#[odra::event]
+pub struct AcceptedTokenUpdated {
+    /// Address of the CEP-18 token contract
+    pub token: Address,
+    /// Whether the token is now accepted
+    pub accepted: bool,
+    /// Timestamp of the update
+    pub timestamp: u64,
+}
+
+/// Emitted when a buyer deposits CSPR as prepaid credit
+#[odra::event]
+pub struct CreditDeposited {
+    /// Address of the buyer
+    pub user: Address,
+    /// Amount deposited in motes
+    pub amount: U512,
+    /// Credit balance after the deposit
+    pub balance: U512,
+    /// Timestamp of the deposit
+    pub timestamp: u64,
+}
+
+/// Emitted when a buyer withdraws unused prepaid credit
+#[odra::event]
+pub struct CreditWithdrawn {
+    /// Address of the buyer
+    pub user: Address,
+    /// Amount withdrawn in motes
+    pub amount: U512,
+    /// Credit balance after the withdrawal
+    pub balance: U512,
+    /// Timestamp of the withdrawal
+    pub timestamp: u64,
+}
+
+/// Emitted when prepaid credit is spent on a purchase
+#[odra::event]
+pub struct CreditSpent {
+    /// Address of the buyer
+    pub user: Address,
+    /// Sample the credit was spent on
+    pub sample_id: u64,
+    /// Amount spent in motes
+    pub amount: U512,
+    /// Credit balance after the purchase
+    pub balance: U512,
+    /// Timestamp of the purchase
+    pub timestamp: u64,
+}
+
+// ============================================
+// Subscription Events
+// ============================================
+
+/// Emitted when a producer creates or updates a subscription plan
+#[odra::event]
+pub struct SubscriptionPlanUpdated {
+    /// Unique identifier of the plan
+    pub plan_id: u64,
+    /// Producer offering the plan
+    pub producer: Address,
+    /// Price per period in motes
+    pub price: U512,
+    /// Length of one subscription period
+    pub period: u64,
+    /// License type granted by the plan
+    pub license_type: u8,
+    /// Whether the plan accepts subscribers
+    pub is_active: bool,
+    /// Timestamp of the update
+    pub timestamp: u64,
+}
+
+/// Emitted when a user subscribes to or renews a producer plan
+#[odra::event]
+pub struct Subscribed {
+    /// Address of the subscriber
+    pub subscriber: Address,
+    /// Producer being subscribed to
+    pub producer: Address,
+    /// Plan paid for
+    pub plan_id: u64,
+    /// Amount paid in motes
+    pub price: U512,
+    /// Platform fee taken from the payment
+    pub platform_fee: U512,
+    /// Timestamp when access expires
+    pub expires_at: u64,
+    /// Whether this payment extended an existing subscription
+    pub is_renewal: bool,
+    /// Timestamp of the payment
+    pub timestamp: u64,
+}
+
+// ============================================
+// Referral Events
+// ============================================
+
+/// Emitted when a referrer earns a commission on a sale
+#[odra::event]
+pub struct ReferralPaid {
+    /// Address of the referrer
+    pub referrer: Address,
+    /// Address of the referred buyer
+    pub buyer: Address,
+    /// Seller of the sample
+    pub seller: Address,
+    /// Sample that was sold
+    pub sample_id: u64,
+    /// Commission taken from the platform fee
+    pub platform_share: U512,
+    /// Commission funded by the seller's bonus
+    pub seller_bonus: U512,
+    /// Currency the commission was paid in (None = CSPR)
+    pub currency: Option<Address>,
+    /// Timestamp of the sale
+    pub timestamp: u64,
+}
+
+/// Emitted when the admin changes the referrer share of the platform fee
+#[odra::event]
+pub struct ReferralShareUpdated {
+    /// New share in basis points
+    pub share_bps: u64,
+    /// Timestamp of the update
+    pub timestamp: u64,
+}
+
+/// Emitted when a seller changes their referral bonus
+#[odra::event]
+pub struct SellerReferralBonusUpdated {
+    /// Address of the seller
+    pub seller: Address,
+    /// New bonus in basis points of the seller's proceeds
+    pub bonus_bps: u64,
+    /// Timestamp of the update
+    pub timestamp: u64,
+}
+
+// ============================================
+// Moderation Events
+// ============================================
+
+/// Emitted when a user reports a sample
+#[odra::event]
+pub struct SampleReported {
+    /// Unique identifier of the report
+    pub report_id: u64,
+    /// Sample being reported
+    pub sample_id: u64,
+    /// Address that filed the report
+    pub reporter: Address,
+    /// Reason code
+    pub reason_code: u8,
+    /// Timestamp of the report
+    pub timestamp: u64,
+}
+
+/// Emitted when a moderator resolves or dismisses a report
+#[odra::event]
+pub struct ReportClosed {
+    /// Unique identifier of the report
+    pub report_id: u64,
+    /// Sample the report was filed against
+    pub sample_id: u64,
+    /// New status (1=Resolved, 2=Dismissed)
+    pub status: u8,
+    /// Moderator who closed the report
+    pub moderator: Address,
+    /// Timestamp of the update
+    pub timestamp: u64,
+}
+
+/// Emitted when a moderator delists or restores a sample
+#[odra::event]
+pub struct SampleListingUpdated {
+    /// Unique identifier of the sample
+    pub sample_id: u64,
+    /// Whether the sample is now delisted
+    pub is_delisted: bool,
+    /// Moderator who made the change
+    pub moderator: Address,
+    /// Timestamp of the update
+    pub timestamp: u64,
+}
+
+/// Emitted when a moderator puts a sample taken off sale by an exclusive license back on sale
+#[odra::event]
+pub struct SampleReactivated {
+    /// Unique identifier of the sample
+    pub sample_id: u64,
+    /// Moderator who reactivated it
+    pub moderator: Address,
+    /// Timestamp of the reactivation
+    pub timestamp: u64,
+}
+
+/// Emitted when a content hash is released or reassigned to another sample
+#[odra::event]
+pub struct ContentHashUpdated {
+    /// Audio content hash
+    pub content_hash: String,
+    /// Sample now registered with the hash (None = released)
+    pub sample_id: Option<u64>,
+    /// Moderator who made the change
+    pub moderator: Address,
+    /// Timestamp of the update
+    pub timestamp: u64,
+}
+
+/// Emitted when an address is added to or removed from the blocklist
+#[odra::event]
+pub struct AddressBlockUpdated {
+    /// Affected address
+    pub account: Address,
+    /// Whether the address is now blocked
+    pub blocked: bool,
+    /// Moderator who made the change
+    pub moderator: Address,
+    /// Timestamp of the update
+    pub timestamp: u64,
+}
+
+/// Emitted when a signed listing is redeemed into a sample by a buyer
+#[odra::event]
+pub struct SignedListingRedeemed {
+    /// Sample created from the listing
+    pub sample_id: u64,
+    /// Seller who signed the listing
+    pub seller: Address,
+    /// Listing nonce
+    pub nonce: u64,
+    /// Buyer who submitted the listing
+    pub buyer: Address,
+    /// Timestamp of the redemption
+    pub timestamp: u64,
+}
+
+/// Emitted when a seller cancels a signed listing before it is redeemed
+#[odra::event]
+pub struct SignedListingCancelled {
+    /// Seller who signed the listing
+    pub seller: Address,
+    /// Listing nonce
+    pub nonce: u64,
+    /// Timestamp of the cancellation
+    pub timestamp: u64,
+}
+
+/// Emitted when a relayer submits an action on a user's behalf
+#[odra::event]
+pub struct MetaTransactionExecuted {
+    /// User who signed the action (the effective caller)
+    pub signer: Address,
+    /// Account that submitted the action and paid for gas
+    pub relayer: Address,
+    /// Action run (see `MetaTxAction`)
+    pub action: u8,
+    /// Signer nonce consumed
+    pub nonce: u64,
+    /// Timestamp of the execution
+    pub timestamp: u64,
+}
+
+/// Emitted when a user skips their current meta-transaction nonce, cancelling actions signed with it
+#[odra::event]
+pub struct MetaTxNonceBumped {
+    /// User whose nonce was bumped
+    pub user: Address,
+    /// Next nonce expected from the user
+    pub nonce: u64,
+    /// Timestamp of the bump
+    pub timestamp: u64,
+}
+
+/// Emitted when the relayer allowlist is turned on or off, or a relayer is added or removed
+#[odra::event]
+pub struct RelayerUpdated {
+    /// Relayer added or removed (None when only the allowlist switch changed)
+    pub relayer: Option<Address>,
+    /// Whether the relayer is now allowlisted
+    pub allowed: bool,
+    /// Whether only allowlisted relayers may submit meta-transactions
+    pub allowlist_enabled: bool,
+    /// Timestamp of the update
+    pub timestamp: u64,
+}
+
 // ============================================
 // License NFT Events
 // ============================================
@@ -96,8 +481,190 @@ pub struct LicenseMinted {
     pub creator: Address,
     /// Price paid for the license
     pub price: U512,
+    /// Currency of the price (None = CSPR motes, Some = CEP-18 token contract)
+    pub currency: Option<Address>,
     /// Timestamp of the purchase
     pub timestamp: u64,
+    /// Whether the license was granted for free by the creator
+    pub is_promotional: bool,
+    /// When the license expires (None = perpetual)
+    pub expires_at: Option<u64>,
+    /// Hash or CID of the license agreement accepted by the buyer
+    pub terms_hash: Option<String>,
+    /// Territories covered by the license (empty = worldwide)
+    pub territories: Vec<String>,
+}
+
+/// Emitted when a seller grants free promotional licenses
+#[odra::event]
+pub struct PromotionalLicensesGranted {
+    /// ID of the sample the licenses are for
+    pub sample_id: u64,
+    /// Seller granting the licenses
+    pub seller: Address,
+    /// Type of license granted
+    pub license_type: u8,
+    /// Recipients of the licenses
+    pub recipients: Vec<Address>,
+    /// Timestamp of the grant
+    pub timestamp: u64,
+}
+
+/// Emitted when a license holder issues a sublicense
+#[odra::event]
+pub struct SublicenseIssued {
+    /// Unique identifier of the sublicense
+    pub license_id: u64,
+    /// License the sublicense was issued under
+    pub parent_license_id: u64,
+    /// ID of the sample
+    pub sample_id: u64,
+    /// Holder of the parent license
+    pub issuer: Address,
+    /// Recipient of the sublicense
+    pub recipient: Address,
+    /// Streaming on DSPs is allowed
+    pub streaming: bool,
+    /// Sync placement is allowed
+    pub sync: bool,
+    /// Radio or TV broadcast is allowed
+    pub broadcast: bool,
+    /// Selling copies is allowed
+    pub commercial_distribution: bool,
+    /// The creator must be credited
+    pub attribution_required: bool,
+    /// Maximum streams or units sold (None = unlimited)
+    pub max_units: Option<u64>,
+    /// When the sublicense expires (None = with the parent)
+    pub expires_at: Option<u64>,
+    /// Timestamp of the issue
+    pub timestamp: u64,
+}
+
+/// Emitted when a license owner registers a release that uses the sample
+#[odra::event]
+pub struct ReleaseRegistered {
+    /// Unique identifier of the release record
+    pub release_id: u64,
+    /// License the release was made under
+    pub license_id: u64,
+    /// ID of the sample used in the release
+    pub sample_id: u64,
+    /// License owner who registered the release
+    pub owner: Address,
+    /// International Standard Recording Code
+    pub isrc: String,
+    /// Title of the released work
+    pub title: String,
+    /// Release date (timestamp)
+    pub release_date: u64,
+    /// Timestamp of the registration
+    pub timestamp: u64,
+}
+
+/// Emitted when the terms document of a license tier is set or cleared
+#[odra::event]
+pub struct LicenseTermsUpdated {
+    /// ID of the sample (None for the platform default)
+    pub sample_id: Option<u64>,
+    /// License type (0=Personal, 1=Commercial, 2=Broadcast, 3=Exclusive)
+    pub license_type: u8,
+    /// Hash or CID of the terms document (None = cleared)
+    pub terms_hash: Option<String>,
+    /// Timestamp of the change
+    pub timestamp: u64,
+}
+
+/// Emitted when a license tier is registered or reconfigured
+#[odra::event]
+pub struct LicenseTierUpdated {
+    /// Tier ID
+    pub tier_id: u8,
+    /// Display name
+    pub name: String,
+    /// Default price multiplier (100 = 1x base price)
+    pub default_multiplier: u64,
+    /// Whether licenses of this tier can be resold
+    pub is_transferable: bool,
+    /// Whether a license of this tier is exclusive
+    pub is_exclusive: bool,
+    /// Whether new licenses of this tier can be sold
+    pub is_active: bool,
+    /// Streaming on DSPs is allowed
+    pub streaming: bool,
+    /// Sync placement is allowed
+    pub sync: bool,
+    /// Radio or TV broadcast is allowed
+    pub broadcast: bool,
+    /// Selling copies is allowed
+    pub commercial_distribution: bool,
+    /// The creator must be credited
+    pub attribution_required: bool,
+    /// Maximum streams or units sold (None = unlimited)
+    pub max_units: Option<u64>,
+    /// License duration in milliseconds (None = perpetual)
+    pub duration: Option<u64>,
+    /// Timestamp of the change
+    pub timestamp: u64,
+}
+
+/// Emitted when the usage rights of a license type are changed
+#[odra::event]
+pub struct UsageRightsUpdated {
+    /// License type (0=Personal, 1=Commercial, 2=Broadcast, 3=Exclusive)
+    pub license_type: u8,
+    /// Streaming on DSPs is allowed
+    pub streaming: bool,
+    /// Sync placement is allowed
+    pub sync: bool,
+    /// Radio or TV broadcast is allowed
+    pub broadcast: bool,
+    /// Selling copies is allowed
+    pub commercial_distribution: bool,
+    /// The creator must be credited
+    pub attribution_required: bool,
+    /// Maximum streams or units sold (None = unlimited)
+    pub max_units: Option<u64>,
+    /// License duration in milliseconds (None = perpetual)
+    pub duration: Option<u64>,
+    /// Timestamp of the change
+    pub timestamp: u64,
+}
+
+/// Emitted when a license is suspended, reinstated or revoked
+#[odra::event]
+pub struct LicenseStatusChanged {
+    /// Unique identifier of the license
+    pub license_id: u64,
+    /// ID of the sample this license is for
+    pub sample_id: u64,
+    /// Current owner of the license
+    pub owner: Address,
+    /// New status (0=Active, 1=Suspended, 2=Revoked)
+    pub status: u8,
+    /// Reason code for the change
+    pub reason_code: u8,
+    /// Moderator that made the change
+    pub actor: Address,
+    /// Timestamp of the change
+    pub timestamp: u64,
+}
+
+/// Emitted when a creator asks moderators to suspend a license, or a moderator dismisses the request
+#[odra::event]
+pub struct LicenseSuspensionRequested {
+    /// Unique identifier of the license
+    pub license_id: u64,
+    /// ID of the sample this license is for
+    pub sample_id: u64,
+    /// Creator that filed the request
+    pub creator: Address,
+    /// Reason code given by the creator
+    pub reason_code: u8,
+    /// Whether the request is pending (false once dismissed)
+    pub pending: bool,
+    /// Timestamp of the request or dismissal
+    pub timestamp: u64,
 }
 
 /// Emitted when a license NFT is transferred
@@ -117,6 +684,8 @@ pub struct LicenseTransferred {
     pub platform_fee: U512,
     /// Timestamp of the transfer
     pub timestamp: u64,
+    /// Currency of the resale (None = CSPR)
+    pub currency: Option<Address>,
 }
 
 /// Emitted when royalties are paid to a creator
@@ -130,6 +699,8 @@ pub struct RoyaltyPaid {
     pub amount: U512,
     /// Timestamp of the payment
     pub timestamp: u64,
+    /// Currency of the royalty (None = CSPR)
+    pub currency: Option<Address>,
 }
 
 /// Emitted when royalties are withdrawn by a creator
@@ -141,6 +712,8 @@ pub struct RoyaltiesWithdrawn {
     pub amount: U512,
     /// Timestamp of the withdrawal
     pub timestamp: u64,
+    /// Currency withdrawn (None = CSPR)
+    pub currency: Option<Address>,
 }
 
 /// Emitted when an exclusive license is activated for a sample
@@ -154,6 +727,21 @@ pub struct ExclusiveLicenseActivated {
     pub holder: Address,
     /// Timestamp when exclusivity was activated
     pub timestamp: u64,
+    /// Territories the exclusivity applies to (empty = worldwide)
+    pub territories: Vec<String>,
+}
+
+/// Emitted when a seller prices (or stops offering) a territory for a sample
+#[odra::event]
+pub struct TerritoryPricingUpdated {
+    /// ID of the sample
+    pub sample_id: u64,
+    /// ISO 3166-1 alpha-2 territory code
+    pub territory: String,
+    /// Multiplier applied to the worldwide license price (100 = 1x, 0 = not offered)
+    pub multiplier: u64,
+    /// Timestamp of the update
+    pub timestamp: u64,
 }
 
 /// Emitted when license pricing is updated for a sample
@@ -161,6 +749,65 @@ pub struct ExclusiveLicenseActivated {
 pub struct LicensePricingUpdated {
     /// ID of the sample
     pub sample_id: u64,
+    /// Personal license multiplier (100 = 1x, 0 in absolute mode)
+    pub personal_mult: u64,
+    /// Commercial license multiplier
+    pub commercial_mult: u64,
+    /// Broadcast license multiplier
+    pub broadcast_mult: u64,
+    /// Exclusive license multiplier
+    pub exclusive_mult: u64,
+    /// Timestamp of the update
+    pub timestamp: u64,
+    /// Active pricing mode (0=Multiplier, 1=Absolute)
+    pub pricing_mode: u8,
+    /// Absolute personal license price (0 in multiplier mode)
+    pub personal_price: U512,
+    /// Absolute commercial license price
+    pub commercial_price: U512,
+    /// Absolute broadcast license price
+    pub broadcast_price: U512,
+    /// Absolute exclusive license price
+    pub exclusive_price: U512,
+}
+
+/// Emitted when a minter is authorized, revoked, paused or has its cap changed
+#[odra::event]
+pub struct MinterUpdated {
+    /// Address of the minter contract
+    pub minter: Address,
+    /// Whether the minter is on the allowlist
+    pub is_authorized: bool,
+    /// Whether minting is paused for this minter
+    pub is_paused: bool,
+    /// Maximum number of licenses (None = unlimited)
+    pub cap: Option<u64>,
+    /// Number of licenses minted so far
+    pub minted: u64,
+    /// Timestamp of the update
+    pub timestamp: u64,
+}
+
+/// Emitted when the maximum supply of a license type is changed for a sample
+#[odra::event]
+pub struct LicenseSupplyUpdated {
+    /// ID of the sample
+    pub sample_id: u64,
+    /// Type of license (0=Personal, 1=Commercial, 2=Broadcast)
+    pub license_type: u8,
+    /// Maximum number of licenses of this type (0 = unlimited)
+    pub max_supply: u64,
+    /// Timestamp of the update
+    pub timestamp: u64,
+}
+
+/// Emitted when a seller sets their default license pricing or a named template
+#[odra::event]
+pub struct SellerPricingUpdated {
+    /// Address of the seller
+    pub seller: Address,
+    /// Template name (None = the seller's default pricing)
+    pub template_name: Option<String>,
     /// Personal license multiplier (100 = 1x)
     pub personal_mult: u64,
     /// Commercial license multiplier
@@ -172,3 +819,16 @@ pub struct LicensePricingUpdated {
     /// Timestamp of the update
     pub timestamp: u64,
 }
+
+/// Emitted when a seller reprices their whole catalog at once
+#[odra::event]
+pub struct CatalogRepriced {
+    /// Address of the seller
+    pub seller: Address,
+    /// Template applied (None = the seller's default pricing)
+    pub template_name: Option<String>,
+    /// Number of samples repriced in this batch
+    pub sample_count: u64,
+    /// Timestamp of the update
+    pub timestamp: u64,
+}
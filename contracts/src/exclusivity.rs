@@ -0,0 +1,112 @@
+//! Exclusivity Storage for the License NFT contract
+//!
+//! Tracks exclusive licenses per sample, either worldwide or per territory:
+//! - A worldwide exclusive license blocks every further license for the sample
+//! - A territorial exclusive license blocks further licenses covering any of its territories,
+//!   so exclusive holders in different regions can coexist
+//!
+//! Timed exclusive licenses stop blocking once they expire, and revoked ones are released,
+//! so the sample (or territory) can be licensed exclusively again.
+
+use odra::prelude::*;
+
+use crate::license_types::ExclusiveGrant;
+
+/// Worldwide and per-territory exclusive grants
+#[odra::module]
+pub struct ExclusivityRegistry {
+    /// Worldwide exclusive grant per sample (None once released)
+    worldwide: Mapping<u64, Option<ExclusiveGrant>>,
+    /// Territorial exclusive grant: (sample_id, territory) -> grant (None once released)
+    territorial: Mapping<(u64, String), Option<ExclusiveGrant>>,
+    /// Number of distinct territories ever licensed exclusively per sample
+    territory_count: Mapping<u64, u64>,
+    /// Indexed territories: (sample_id, index) -> territory
+    territory_at: Mapping<(u64, u64), String>,
+}
+
+impl ExclusivityRegistry {
+    /// Record an exclusive license for the given territories (empty = worldwide)
+    pub fn record(&mut self, sample_id: u64, territories: &[String], grant: ExclusiveGrant) {
+        if territories.is_empty() {
+            self.worldwide.set(&sample_id, Some(grant));
+            return;
+        }
+        for territory in territories {
+            let key = (sample_id, territory.clone());
+            if self.territorial.get(&key).is_none() {
+                let count = self.territory_count.get_or_default(&sample_id);
+                self.territory_at.set(&(sample_id, count), territory.clone());
+                self.territory_count.set(&sample_id, count + 1);
+            }
+            self.territorial.set(&key, Some(grant.clone()));
+        }
+    }
+
+    /// Release an exclusive license (on revocation) so it no longer blocks other licenses
+    pub fn release(&mut self, sample_id: u64, territories: &[String], license_id: u64) {
+        if territories.is_empty() {
+            if self.worldwide_grant(sample_id).is_some_and(|grant| grant.license_id == license_id) {
+                self.worldwide.set(&sample_id, None);
+            }
+            return;
+        }
+        for territory in territories {
+            if self.territory_grant(sample_id, territory).is_some_and(|grant| grant.license_id == license_id) {
+                self.territorial.set(&(sample_id, territory.clone()), None);
+            }
+        }
+    }
+
+    /// Check if a new license for the given territories (empty = worldwide) overlaps a current exclusive license
+    /// A worldwide request checks every territory ever licensed exclusively for the sample
+    /// (bounded by the number of ISO 3166-1 codes)
+    pub fn is_blocked(&self, sample_id: u64, territories: &[String], now: u64) -> bool {
+        if self.has_worldwide(sample_id, now) {
+            return true;
+        }
+        if territories.is_empty() {
+            let count = self.territory_count.get_or_default(&sample_id);
+            return (0..count)
+                .filter_map(|i| self.territory_at.get(&(sample_id, i)))
+                .any(|territory| self.territory_holder(sample_id, &territory, now).is_some());
+        }
+        territories.iter().any(|territory| self.territory_holder(sample_id, territory, now).is_some())
+    }
+
+    /// Check if the sample has a current worldwide exclusive license
+    pub fn has_worldwide(&self, sample_id: u64, now: u64) -> bool {
+        self.worldwide_holder(sample_id, now).is_some()
+    }
+
+    /// Get the current worldwide exclusive holder of a sample
+    pub fn worldwide_holder(&self, sample_id: u64, now: u64) -> Option<Address> {
+        self.worldwide_grant(sample_id)
+            .filter(|grant| grant.is_current(now))
+            .map(|grant| grant.holder)
+    }
+
+    /// Get the license behind the current worldwide exclusive grant of a sample
+    pub fn worldwide_license(&self, sample_id: u64, now: u64) -> Option<u64> {
+        self.worldwide_grant(sample_id)
+            .filter(|grant| grant.is_current(now))
+            .map(|grant| grant.license_id)
+    }
+
+    /// Get the current exclusive holder of a sample in a territory
+    pub fn territory_holder(&self, sample_id: u64, territory: &str, now: u64) -> Option<Address> {
+        self.territory_grant(sample_id, territory)
+            .filter(|grant| grant.is_current(now))
+            .map(|grant| grant.holder)
+    }
+
+    /// Get the worldwide grant of a sample, current or expired
+    fn worldwide_grant(&self, sample_id: u64) -> Option<ExclusiveGrant> {
+        self.worldwide.get(&sample_id).flatten()
+    }
+
+    /// Get the grant of a sample in a territory, current or expired
+    fn territory_grant(&self, sample_id: u64, territory: &str) -> Option<ExclusiveGrant> {
+        self.territorial.get(&(sample_id, String::from(territory))).flatten()
+    }
+}
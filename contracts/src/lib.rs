@@ -2,9 +2,27 @@
 //!
 //! This contract allows music producers to:
 //! - Upload samples with IPFS links and metadata
+//! - Register each upload's audio content hash as on-chain proof of first publication
+//! - List samples gas-free with off-chain signed listings, created on first purchase
+//! - Let relayers submit seller actions signed by users without CSPR for gas
 //! - Purchase samples from other producers
 //! - Withdraw accumulated earnings
 //! - Purchase and manage License NFTs with different usage rights
+//! - Sell custom license tiers ("Sync-only", "Non-profit", ...) registered by the admin
+//! - Verify on-chain usage rights (streaming, sync, broadcast, distribution) per license
+//! - Bind each license tier to a terms document the buyer accepts at purchase
+//! - Register released works (ISRC) against the license they were made under
+//! - Sublicense Commercial and Broadcast rights to agencies and editors
+//! - Sell territory-restricted licenses, with exclusivity per territory
+//! - Grant free promotional licenses to contest winners and collaborators
+//! - Pay in CSPR or in admin-allowlisted CEP-18 tokens
+//! - Prepay CSPR credit to buy without attaching value on every call
+//! - Price samples in USD, converted to CSPR through a price feed contract
+//! - Pay what you want (including free downloads) and tip producers
+//! - Subscribe to producers for rolling access to their whole catalog
+//! - Reward referrers with a commission on the sales they bring in
+//! - Report samples; moderators can delist samples and block addresses
+//! - Role-based administration (admin, moderator, treasurer, pauser, ...) and an emergency stop
 //!
 //! Built with Odra framework for Casper Network.
 
@@ -16,8 +34,29 @@ extern crate alloc;
 pub mod errors;
 pub mod events;
 pub mod types;
+pub mod currency_balances;
+pub mod payment_ledger;
+pub mod price_oracle;
+#[cfg(test)]
+pub mod mock_price_feed;
 pub mod license_types;
+pub mod license_pricing;
 pub mod license_nft;
+pub mod license_index;
+pub mod license_terms;
+pub mod release_registry;
+pub mod sublicenses;
+pub mod tier_registry;
+pub mod exclusivity;
+pub mod minter_registry;
+pub mod subscriptions;
+pub mod referrals;
+pub mod moderation;
+pub mod content_registry;
+pub mod signed_listings;
+pub mod meta_transactions;
+pub mod roles;
+pub mod user_activity;
 pub mod sampled_marketplace;
 
 pub use sampled_marketplace::SampledMarketplace;
@@ -0,0 +1,144 @@
+//! License Indexes for the License NFT contract
+//!
+//! Indexed mappings used to look licenses up without iterating
+//! over every license ever minted:
+//! - Licenses per sample, with a running count per license type for supply caps
+//! - Licenses per owner (gaps are left when a license moves)
+//! - The license each user holds per (sample, license type), for duplicate prevention
+//! - The license types each user holds per sample, for access checks
+
+use odra::prelude::*;
+use odra::casper_types::bytesrepr::Bytes;
+
+/// License lookups by sample, owner and (owner, sample, type)
+#[odra::module]
+pub struct LicenseIndex {
+    /// Count of licenses per sample: sample_id -> count
+    sample_license_count: Mapping<u64, u64>,
+    /// Indexed licenses: (sample_id, index) -> license_id
+    sample_license_at: Mapping<(u64, u64), u64>,
+    /// Licenses issued per type: (sample_id, license_type) -> count
+    sample_type_issued: Mapping<(u64, u8), u64>,
+
+    /// Count of licenses per owner: owner -> count
+    owner_license_count: Mapping<Address, u64>,
+    /// Indexed licenses: (owner, index) -> license_id (0 = moved away)
+    owner_license_at: Mapping<(Address, u64), u64>,
+
+    /// Track if user has specific license type for sample: (user, sample_id, license_type) -> license_id
+    user_sample_license: Mapping<(Address, u64, u8), u64>,
+    /// Check if user has specific license type: (user, sample_id, license_type) -> bool
+    user_has_license_type: Mapping<(Address, u64, u8), bool>,
+    /// License types a user holds for a sample: (user, sample_id) -> types
+    user_sample_types: Mapping<(Address, u64), Bytes>,
+}
+
+impl LicenseIndex {
+    /// Add a license to a sample's list and count it against its type
+    pub fn add_to_sample(&mut self, sample_id: u64, license_type: u8, license_id: u64) {
+        let count = self.sample_license_count.get_or_default(&sample_id);
+        self.sample_license_at.set(&(sample_id, count), license_id);
+        self.sample_license_count.set(&sample_id, count + 1);
+
+        let issued = self.sample_type_issued.get_or_default(&(sample_id, license_type));
+        self.sample_type_issued.set(&(sample_id, license_type), issued + 1);
+    }
+
+    /// Get the number of licenses of a type issued for a sample
+    pub fn issued_count(&self, sample_id: u64, license_type: u8) -> u64 {
+        self.sample_type_issued.get_or_default(&(sample_id, license_type))
+    }
+
+    /// Get all license IDs for a sample
+    pub fn sample_licenses(&self, sample_id: u64) -> Vec<u64> {
+        let count = self.sample_license_count.get_or_default(&sample_id);
+        let mut result = Vec::new();
+        for i in 0..count {
+            if let Some(id) = self.sample_license_at.get(&(sample_id, i)) {
+                result.push(id);
+            }
+        }
+        result
+    }
+
+    /// Get the number of licenses minted for a sample
+    pub fn sample_license_count(&self, sample_id: u64) -> u64 {
+        self.sample_license_count.get_or_default(&sample_id)
+    }
+
+    /// Add a license to an owner's list
+    pub fn add_to_owner(&mut self, owner: Address, license_id: u64) {
+        let count = self.owner_license_count.get_or_default(&owner);
+        self.owner_license_at.set(&(owner, count), license_id);
+        self.owner_license_count.set(&owner, count + 1);
+    }
+
+    /// Remove a license from an owner's indexed list
+    /// Note: This leaves gaps in the index, which are skipped by `owner_licenses`
+    pub fn remove_from_owner(&mut self, owner: Address, license_id: u64) {
+        let count = self.owner_license_count.get_or_default(&owner);
+        for i in 0..count {
+            if let Some(id) = self.owner_license_at.get(&(owner, i)) {
+                if id == license_id {
+                    // Mark as removed by setting to 0
+                    self.owner_license_at.set(&(owner, i), 0);
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Get the license IDs indexed under an owner (gaps skipped)
+    pub fn owner_licenses(&self, owner: &Address) -> Vec<u64> {
+        let count = self.owner_license_count.get_or_default(owner);
+        let mut result = Vec::new();
+        for i in 0..count {
+            if let Some(id) = self.owner_license_at.get(&(*owner, i)) {
+                if id > 0 {
+                    result.push(id);
+                }
+            }
+        }
+        result
+    }
+
+    /// Record that a user holds a license of a given type for a sample
+    pub fn set_holder(&mut self, owner: Address, sample_id: u64, license_type: u8, license_id: u64) {
+        self.user_sample_license.set(&(owner, sample_id, license_type), license_id);
+        self.user_has_license_type.set(&(owner, sample_id, license_type), true);
+
+        let mut types = self.held_types(&owner, sample_id);
+        if !types.contains(&license_type) {
+            types.push(license_type);
+            self.user_sample_types.set(&(owner, sample_id), Bytes::from(types));
+        }
+    }
+
+    /// Record that a user no longer holds a license of a given type for a sample
+    pub fn clear_holder(&mut self, owner: Address, sample_id: u64, license_type: u8) {
+        self.user_has_license_type.set(&(owner, sample_id, license_type), false);
+        self.user_sample_license.set(&(owner, sample_id, license_type), 0);
+
+        let mut types = self.held_types(&owner, sample_id);
+        types.retain(|held| *held != license_type);
+        self.user_sample_types.set(&(owner, sample_id), Bytes::from(types));
+    }
+
+    /// Get the license types a user holds for a sample (valid or not)
+    pub fn held_types(&self, owner: &Address, sample_id: u64) -> Vec<u8> {
+        self.user_sample_types.get_or_default(&(*owner, sample_id)).to_vec()
+    }
+
+    /// Get the license a user holds for a sample and type (if any)
+    pub fn holder_license(&self, owner: &Address, sample_id: u64, license_type: u8) -> Option<u64> {
+        if !self.user_has_license_type.get_or_default(&(*owner, sample_id, license_type)) {
+            return None;
+        }
+        let license_id = self.user_sample_license.get_or_default(&(*owner, sample_id, license_type));
+        if license_id > 0 {
+            Some(license_id)
+        } else {
+            None
+        }
+    }
+}
@@ -2,27 +2,58 @@
 //!
 //! This contract manages License NFTs that represent usage rights for samples.
 //! Features:
-//! - Mint license NFTs on sample purchase
+//! - Mint license NFTs on sample purchase, from an allowlist of minter contracts
 //! - Transfer licenses with automatic royalty distribution
 //! - Track royalty earnings for creators
-//! - Support for different license types with varying rights
+//! - Support for different license types with varying rights, plus admin-registered custom tiers
+//! - Resales and royalties in CSPR or admin-allowlisted CEP-18 tokens
+//! - Role-based administration and an emergency stop for minting and transfers
+//! - Suspension (moderator-confirmed, on request of the creator), reinstatement and revocation of licenses
+//! - On-chain usage rights per license type, verifiable by distributors and platforms
+//! - Terms document hashes per license tier, snapshotted into each license at mint
+//! - Registry of released works (ISRC) made under each license
+//! - Sublicenses issued by holders, bounded by and cascading from their parent license
+//! - Territory-restricted licenses, with exclusivity worldwide or per territory
 
 use odra::prelude::*;
 use odra::casper_types::U512;
+use odra::ContractRef;
 
 use crate::errors::Error;
 use crate::events::{
     LicenseMinted, LicenseTransferred, RoyaltyPaid, RoyaltiesWithdrawn,
-    ExclusiveLicenseActivated,
+    ExclusiveLicenseActivated, LicenseSupplyUpdated, AcceptedTokenUpdated, MinterUpdated,
+    LicenseStatusChanged, UsageRightsUpdated, LicenseTermsUpdated, ReleaseRegistered,
+    SublicenseIssued, LicenseTierUpdated, LicenseSuspensionRequested,
 };
 use crate::license_types::{
-    LicenseType, LicenseMetadata, SampleLicenseInfo,
+    LicenseType, LicenseMetadata, LicenseStatus, SampleLicenseInfo, MinterInfo,
+    UsageRights, UsageType, UsageVerification, VerificationStatus, Release,
+    LicenseTier, TierSupply, ExclusiveGrant, is_valid_territory_set,
     constants::*,
 };
+use crate::license_index::LicenseIndex;
+use crate::license_terms::LicenseTerms;
+use crate::release_registry::ReleaseRegistry;
+use crate::sublicenses::Sublicenses;
+use crate::tier_registry::TierRegistry;
+use crate::exclusivity::ExclusivityRegistry;
+use crate::minter_registry::MinterRegistry;
+use crate::currency_balances::CurrencyBalances;
+use crate::sampled_marketplace::SampledMarketplaceContractRef;
+use crate::roles::{Roles, ADMIN_ROLE, MODERATOR_ROLE, TREASURER_ROLE, LICENSE_MINTER_ROLE};
+use odra_modules::access::Role;
+use odra_modules::access::events::{RoleGranted, RoleRevoked, RoleAdminChanged};
+use odra_modules::security::events::{Paused, Unpaused};
 
 /// License NFT Contract
 #[odra::module(
-    events = [LicenseMinted, LicenseTransferred, RoyaltyPaid, RoyaltiesWithdrawn, ExclusiveLicenseActivated],
+    events = [
+        LicenseMinted, LicenseTransferred, RoyaltyPaid, RoyaltiesWithdrawn, ExclusiveLicenseActivated,
+        LicenseSupplyUpdated, AcceptedTokenUpdated, MinterUpdated, LicenseStatusChanged, RoleGranted, RoleRevoked, RoleAdminChanged,
+        Paused, Unpaused, UsageRightsUpdated, LicenseTermsUpdated, ReleaseRegistered,
+        SublicenseIssued, LicenseTierUpdated, LicenseSuspensionRequested
+    ],
     errors = Error
 )]
 pub struct LicenseNft {
@@ -30,10 +61,12 @@ pub struct LicenseNft {
     // Core Storage
     // ============================================
 
-    /// Admin address (can set marketplace)
+    /// Admin address (receives resale platform fees)
     admin: Var<Address>,
-    /// Marketplace contract address (authorized to mint)
-    marketplace: Var<Address>,
+    /// Contracts authorized to mint (marketplace, pack store, ...)
+    minters: SubModule<MinterRegistry>,
+    /// Role membership and pause state
+    roles: SubModule<Roles>,
 
     // ============================================
     // License Storage
@@ -44,45 +77,32 @@ pub struct LicenseNft {
     /// Total number of licenses minted
     license_count: Var<u64>,
 
-    // ============================================
-    // License Indexing by Sample
-    // ============================================
-
-    /// Count of licenses per sample: sample_id -> count
-    sample_license_count: Mapping<u64, u64>,
-    /// Indexed licenses: (sample_id, index) -> license_id
-    sample_license_at: Mapping<(u64, u64), u64>,
-    /// Track exclusive license holder per sample
-    sample_exclusive_holder: Mapping<u64, Address>,
-    /// Track if sample has exclusive license
-    sample_has_exclusive: Mapping<u64, bool>,
-
-    // ============================================
-    // License Indexing by Owner
-    // ============================================
-
-    /// Count of licenses per owner: owner -> count
-    owner_license_count: Mapping<Address, u64>,
-    /// Indexed licenses: (owner, index) -> license_id
-    owner_license_at: Mapping<(Address, u64), u64>,
+    /// Licenses indexed by sample, by owner and by (owner, sample, type)
+    index: SubModule<LicenseIndex>,
+    /// License tiers: the four built-in types plus admin-registered custom tiers
+    tiers: SubModule<TierRegistry>,
+    /// Terms document hashes per license tier (seller per sample, platform default per type)
+    terms: SubModule<LicenseTerms>,
+    /// Released works registered against licenses
+    releases: SubModule<ReleaseRegistry>,
+    /// Sublicense scopes, indexed by parent and by holder
+    sublicenses: SubModule<Sublicenses>,
 
     // ============================================
-    // User License Tracking (for duplicate prevention)
+    // Sample License Limits
     // ============================================
 
-    /// Track if user has specific license type for sample: (user, sample_id, license_type) -> license_id
-    user_sample_license: Mapping<(Address, u64, u8), u64>,
-    /// Check if user has specific license type: (user, sample_id, license_type) -> bool
-    user_has_license_type: Mapping<(Address, u64, u8), bool>,
+    /// Exclusive license holders per sample, worldwide or per territory
+    exclusivity: SubModule<ExclusivityRegistry>,
+    /// Max supply per license type: (sample_id, license_type) -> max (0 = unlimited)
+    sample_max_supply: Mapping<(u64, u8), u64>,
 
     // ============================================
     // Royalty Storage
     // ============================================
 
-    /// Creator's accumulated royalty earnings
-    creator_royalty_earnings: Mapping<Address, U512>,
-    /// Total royalties earned by creator (lifetime)
-    creator_total_royalties: Mapping<Address, U512>,
+    /// Creator royalty balances per currency, plus the accepted token allowlist
+    royalties: SubModule<CurrencyBalances>,
 }
 
 #[odra::module]
@@ -96,25 +116,71 @@ impl LicenseNft {
     pub fn init(&mut self, admin: Address) {
         self.admin.set(admin);
         self.license_count.set(0);
+        self.roles.init(admin);
     }
 
     // ============================================
     // Admin Functions
     // ============================================
 
-    /// Set the marketplace contract address (only admin)
+    /// Authorize a contract to mint licenses (only admin)
+    /// `cap` limits the total number of licenses it may mint (None = unlimited)
+    pub fn authorize_minter(&mut self, minter: Address, cap: Option<u64>) {
+        self.roles.assert_role(&ADMIN_ROLE);
+        let info = self.minters.authorize(minter, cap);
+        self.emit_minter_updated(info);
+    }
+
+    /// Remove a contract from the minter allowlist (only admin)
+    pub fn revoke_minter(&mut self, minter: Address) {
+        self.roles.assert_role(&ADMIN_ROLE);
+        let info = self.minters.revoke(minter);
+        self.emit_minter_updated(info);
+    }
+
+    /// Pause or resume minting for a single minter (only admin)
+    pub fn set_minter_paused(&mut self, minter: Address, paused: bool) {
+        self.roles.assert_role(&ADMIN_ROLE);
+        let info = self.minters.set_paused(minter, paused);
+        self.emit_minter_updated(info);
+    }
+
+    /// Change a minter's cap (only admin)
+    pub fn set_minter_cap(&mut self, minter: Address, cap: Option<u64>) {
+        self.roles.assert_role(&ADMIN_ROLE);
+        let info = self.minters.set_cap(minter, cap);
+        self.emit_minter_updated(info);
+    }
+
+    /// Set the primary marketplace contract (only admin)
+    /// The marketplace is authorized as a minter (keeping its cap, if already registered)
+    /// and is the only minter that may set supply caps and terms or mint exclusive licenses
     pub fn set_marketplace(&mut self, marketplace: Address) {
-        let caller = self.env().caller();
-        let admin = self.admin.get().unwrap();
-        if caller != admin {
-            self.env().revert(Error::Unauthorized);
-        }
-        self.marketplace.set(marketplace);
+        self.roles.assert_role(&ADMIN_ROLE);
+        let cap = self.minters.info(&marketplace).and_then(|info| info.cap);
+        let info = self.minters.authorize(marketplace, cap);
+        self.minters.set_primary(marketplace);
+        self.emit_minter_updated(info);
     }
 
-    /// Get the marketplace address
+    /// Get the primary marketplace address
     pub fn get_marketplace(&self) -> Option<Address> {
-        self.marketplace.get()
+        self.minters.primary()
+    }
+
+    /// Get a minter's authorization, pause state, cap and minted count
+    pub fn get_minter(&self, minter: Address) -> Option<MinterInfo> {
+        self.minters.info(&minter)
+    }
+
+    /// Get all currently authorized minters
+    pub fn get_minters(&self) -> Vec<MinterInfo> {
+        self.minters.authorized_minters()
+    }
+
+    /// Check if an address is an authorized, unpaused minter
+    pub fn is_minter(&self, minter: Address) -> bool {
+        self.minters.is_active(&minter)
     }
 
     /// Get the admin address
@@ -122,12 +188,179 @@ impl LicenseNft {
         self.admin.get()
     }
 
+    /// Add or remove a CEP-18 token from the resale allowlist (only treasurer)
+    pub fn set_accepted_token(&mut self, token: Address, accepted: bool) {
+        self.roles.assert_role(&TREASURER_ROLE);
+
+        self.royalties.set_accepted(token, accepted);
+
+        self.env().emit_event(AcceptedTokenUpdated {
+            token,
+            accepted,
+            timestamp: self.env().get_block_time(),
+        });
+    }
+
+    /// Check if a CEP-18 token is accepted for resales
+    pub fn is_accepted_token(&self, token: Address) -> bool {
+        self.royalties.is_accepted(&Some(token))
+    }
+
+    /// Set the maximum supply of a license type for a sample
+    /// Only callable by the primary marketplace, on behalf of the seller
+    pub fn set_max_supply(&mut self, sample_id: u64, license_type: u8, max_supply: u64) {
+        self.assert_primary_marketplace();
+
+        // Exclusive licenses are inherently limited to one
+        if !self.tiers.tier(license_type).is_some_and(|tier| !tier.is_exclusive) {
+            self.env().revert(Error::InvalidLicenseType);
+        }
+
+        // Cannot cap below what has already been issued
+        let issued = self.index.issued_count(sample_id, license_type);
+        if max_supply != 0 && max_supply < issued {
+            self.env().revert(Error::InvalidMaxSupply);
+        }
+
+        self.sample_max_supply.set(&(sample_id, license_type), max_supply);
+
+        self.env().emit_event(LicenseSupplyUpdated {
+            sample_id,
+            license_type,
+            max_supply,
+            timestamp: self.env().get_block_time(),
+        });
+    }
+
+    /// Register a custom license tier (only admin); returns its ID
+    pub fn register_tier(
+        &mut self,
+        name: String,
+        rights: UsageRights,
+        default_multiplier: u64,
+        is_transferable: bool,
+        is_exclusive: bool,
+    ) -> u8 {
+        self.roles.assert_role(&ADMIN_ROLE);
+
+        let tier_id = self.tiers.next_tier_id()
+            .unwrap_or_else(|| self.env().revert(Error::TierLimitReached));
+        let tier = LicenseTier {
+            tier_id,
+            name,
+            rights,
+            default_multiplier,
+            is_transferable,
+            is_exclusive,
+            is_active: true,
+        };
+        self.validate_tier(&tier);
+
+        self.tiers.register(tier.clone());
+        self.emit_tier_updated(tier);
+        tier_id
+    }
+
+    /// Reconfigure a license tier, built-in or custom (only admin)
+    /// Exclusivity is fixed when the tier is registered, and exclusive tiers stay non-transferable;
+    /// inactive tiers cannot be sold
+    pub fn update_tier(
+        &mut self,
+        tier_id: u8,
+        name: String,
+        rights: UsageRights,
+        default_multiplier: u64,
+        is_transferable: bool,
+        is_active: bool,
+    ) {
+        self.roles.assert_role(&ADMIN_ROLE);
+
+        let current = self.tiers.tier(tier_id)
+            .unwrap_or_else(|| self.env().revert(Error::InvalidLicenseType));
+        let tier = LicenseTier {
+            name,
+            rights,
+            default_multiplier,
+            is_transferable,
+            is_active,
+            ..current
+        };
+        self.validate_tier(&tier);
+
+        self.tiers.update(tier.clone());
+        self.emit_tier_updated(tier);
+    }
+
+    /// Set the usage rights granted by a license tier (only admin)
+    /// Rights apply to existing licenses; durations only to licenses minted afterwards
+    pub fn set_usage_rights(&mut self, license_type: u8, rights: UsageRights) {
+        self.roles.assert_role(&ADMIN_ROLE);
+
+        let mut tier = self.tiers.tier(license_type)
+            .unwrap_or_else(|| self.env().revert(Error::InvalidLicenseType));
+        tier.rights = rights.clone();
+        self.validate_tier(&tier);
+
+        self.tiers.update(tier);
+
+        self.env().emit_event(UsageRightsUpdated {
+            license_type,
+            streaming: rights.streaming,
+            sync: rights.sync,
+            broadcast: rights.broadcast,
+            commercial_distribution: rights.commercial_distribution,
+            attribution_required: rights.attribution_required,
+            max_units: rights.max_units,
+            duration: rights.duration,
+            timestamp: self.env().get_block_time(),
+        });
+    }
+
+    /// Set or clear the platform default terms for a license type (only admin)
+    pub fn set_default_terms(&mut self, license_type: u8, terms_hash: Option<String>) {
+        self.roles.assert_role(&ADMIN_ROLE);
+        self.validate_terms(license_type, &terms_hash);
+
+        self.terms.set_default_terms(license_type, terms_hash.clone());
+
+        self.env().emit_event(LicenseTermsUpdated {
+            sample_id: None,
+            license_type,
+            terms_hash,
+            timestamp: self.env().get_block_time(),
+        });
+    }
+
+    /// Set or clear the terms of a sample's license tier
+    /// Only callable by the primary marketplace, which owns the sample, on behalf of the seller
+    pub fn set_license_terms(&mut self, sample_id: u64, license_type: u8, terms_hash: Option<String>) {
+        self.assert_primary_marketplace();
+        self.validate_terms(license_type, &terms_hash);
+
+        self.terms.set_sample_terms(sample_id, license_type, terms_hash.clone());
+
+        self.env().emit_event(LicenseTermsUpdated {
+            sample_id: Some(sample_id),
+            license_type,
+            terms_hash,
+            timestamp: self.env().get_block_time(),
+        });
+    }
+
     // ============================================
     // Minting (Called by Marketplace)
     // ============================================
 
     /// Mint a new license NFT
-    /// Only callable by the marketplace contract
+    /// Only callable by an authorized minter that is not paused and within its cap
+    /// (license minter role holders are registered as minters when granted the role);
+    /// other minters than the primary marketplace cannot mint exclusive tiers, and mint only
+    /// for samples listed on the marketplace, with the sample's seller as `original_creator`
+    /// `purchase_price` is recorded in `currency` (None = CSPR motes, Some = CEP-18 token)
+    /// `is_promotional` marks licenses granted for free by the creator
+    /// `accepted_terms` must match the terms in force for the tier, which are snapshotted into the license
+    /// `territories` restricts the license to ISO 3166-1 alpha-2 territories (empty = worldwide)
+    #[allow(clippy::too_many_arguments)]
     pub fn mint_license(
         &mut self,
         sample_id: u64,
@@ -135,76 +368,112 @@ impl LicenseNft {
         buyer: Address,
         original_creator: Address,
         purchase_price: U512,
+        currency: Option<Address>,
+        is_promotional: bool,
+        accepted_terms: Option<String>,
+        territories: Vec<String>,
     ) -> u64 {
-        // Verify caller is marketplace
-        let caller = self.env().caller();
-        let marketplace = self.marketplace.get()
-            .unwrap_or_else(|| self.env().revert(Error::LicenseContractNotSet));
-        if caller != marketplace {
-            self.env().revert(Error::Unauthorized);
-        }
+        // Verify caller may mint
+        self.roles.require_not_paused();
+        self.authorize_mint();
 
-        // Parse license type
-        let lt = LicenseType::from_u8(license_type)
+        // Look up the tier (it must still be on sale)
+        let tier = self.tiers.tier(license_type)
+            .filter(|tier| tier.is_active)
             .unwrap_or_else(|| self.env().revert(Error::InvalidLicenseType));
 
-        // Check for exclusive license restrictions
-        if self.sample_has_exclusive.get_or_default(&sample_id) {
+        if !self.minters.is_active_primary(&self.env().caller()) {
+            if tier.is_exclusive {
+                self.env().revert(Error::NotPrimaryMarketplace);
+            }
+            self.assert_marketplace_creator(sample_id, &original_creator);
+        }
+
+        if !is_valid_territory_set(&territories) {
+            self.env().revert(Error::InvalidTerritory);
+        }
+
+        let timestamp = self.env().get_block_time();
+
+        // Check for exclusive license restrictions (worldwide, or in any of the territories)
+        if self.exclusivity.is_blocked(sample_id, &territories, timestamp) {
             self.env().revert(Error::SampleExclusivelyLicensed);
         }
 
-        // Check if user already has this license type for this sample
-        if self.user_has_license_type.get_or_default(&(buyer, sample_id, license_type)) {
+        // Check if user already has this license type for this sample (expired licenses can be renewed)
+        let held = self.index.holder_license(&buyer, sample_id, license_type)
+            .and_then(|id| self.licenses.get(&id));
+        if held.is_some_and(|license| !license.is_expired(timestamp)) {
             self.env().revert(Error::AlreadyHasLicenseType);
         }
 
+        // The buyer must have accepted the terms currently in force
+        let terms_hash = self.terms.terms_in_force(sample_id, license_type);
+        if accepted_terms != terms_hash {
+            self.env().revert(Error::TermsMismatch);
+        }
+
+        // Enforce limited-edition supply caps
+        let max_supply = self.sample_max_supply.get_or_default(&(sample_id, license_type));
+        if max_supply > 0 {
+            let issued = self.index.issued_count(sample_id, license_type);
+            if issued >= max_supply {
+                self.env().revert(Error::LicenseSoldOut);
+            }
+        }
+
         // Generate new license ID
         let license_count = self.license_count.get_or_default();
         let license_id = license_count + 1;
         self.license_count.set(license_id);
 
-        let timestamp = self.env().get_block_time();
+        let expires_at = tier.rights.duration
+            .map(|duration| timestamp.saturating_add(duration));
 
         // Create license metadata
         let license = LicenseMetadata {
             license_id,
             sample_id,
-            license_type: lt,
+            license_type,
             original_creator,
             current_owner: buyer,
             purchase_price,
+            currency,
             purchase_timestamp: timestamp,
             is_active: true,
             transfer_count: 0,
+            is_promotional,
+            status: LicenseStatus::Active,
+            status_reason: None,
+            suspension_requested: false,
+            expires_at,
+            terms_hash: terms_hash.clone(),
+            parent_license_id: None,
+            territories: territories.clone(),
         };
 
         // Store license
         self.licenses.set(&license_id, license);
 
-        // Index by sample
-        let sample_count = self.sample_license_count.get_or_default(&sample_id);
-        self.sample_license_at.set(&(sample_id, sample_count), license_id);
-        self.sample_license_count.set(&sample_id, sample_count + 1);
-
-        // Index by owner
-        let owner_count = self.owner_license_count.get_or_default(&buyer);
-        self.owner_license_at.set(&(buyer, owner_count), license_id);
-        self.owner_license_count.set(&buyer, owner_count + 1);
-
-        // Track user's license type
-        self.user_sample_license.set(&(buyer, sample_id, license_type), license_id);
-        self.user_has_license_type.set(&(buyer, sample_id, license_type), true);
+        // Index by sample and owner, and track user's license type
+        self.index.add_to_sample(sample_id, license_type, license_id);
+        self.index.add_to_owner(buyer, license_id);
+        self.index.set_holder(buyer, sample_id, license_type, license_id);
 
         // Handle exclusive license
-        if lt.to_u8() == LicenseType::Exclusive.to_u8() {
-            self.sample_has_exclusive.set(&sample_id, true);
-            self.sample_exclusive_holder.set(&sample_id, buyer);
+        if tier.is_exclusive {
+            self.exclusivity.record(sample_id, &territories, ExclusiveGrant {
+                license_id,
+                holder: buyer,
+                expires_at,
+            });
 
             self.env().emit_event(ExclusiveLicenseActivated {
                 sample_id,
                 license_id,
                 holder: buyer,
                 timestamp,
+                territories: territories.clone(),
             });
         }
 
@@ -216,7 +485,12 @@ impl LicenseNft {
             buyer,
             creator: original_creator,
             price: purchase_price,
+            currency,
             timestamp,
+            is_promotional,
+            expires_at,
+            terms_hash,
+            territories,
         });
 
         license_id
@@ -236,260 +510,1493 @@ impl LicenseNft {
         to: Address,
         sale_price: U512,
     ) {
+        self.transfer_license_internal(license_id, to, sale_price, None);
+    }
+
+    /// Transfer a license NFT with the sale settled in a CEP-18 token
+    /// The caller must first approve this contract for sale_price + royalties
+    pub fn transfer_license_with_token(
+        &mut self,
+        license_id: u64,
+        to: Address,
+        sale_price: U512,
+        token: Address,
+    ) {
+        self.transfer_license_internal(license_id, to, sale_price, Some(token));
+    }
+
+    // ============================================
+    // Royalty Withdrawal
+    // ============================================
+
+    /// Withdraw accumulated royalty earnings
+    pub fn withdraw_royalties(&mut self) {
+        let caller = self.env().caller();
+        self.withdraw_royalties_internal(caller, None);
+    }
+
+    /// Withdraw accumulated royalty earnings in a CEP-18 token
+    pub fn withdraw_token_royalties(&mut self, token: Address) {
         let caller = self.env().caller();
-        let attached_value = self.env().attached_value();
+        self.withdraw_royalties_internal(caller, Some(token));
+    }
 
-        // Get license
-        let mut license = self.licenses.get(&license_id)
+    // ============================================
+    // Sublicensing
+    // ============================================
+
+    /// Issue a sublicense under a license whose tier grants more than streaming,
+    /// such as Commercial or Broadcast (license owner only)
+    /// `scope` must not exceed the parent's rights (its duration is ignored);
+    /// the sublicense ends at `expires_at` or with the parent, whichever comes first,
+    /// and covers the parent's territories
+    pub fn issue_sublicense(
+        &mut self,
+        license_id: u64,
+        recipient: Address,
+        scope: UsageRights,
+        expires_at: Option<u64>,
+    ) -> u64 {
+        let caller = self.env().caller();
+        self.roles.require_not_paused();
+
+        let parent = self.licenses.get(&license_id)
             .unwrap_or_else(|| self.env().revert(Error::LicenseNotFound));
 
-        // Validate ownership
-        if license.current_owner != caller {
+        if parent.current_owner != caller {
             self.env().revert(Error::NotLicenseOwner);
         }
-
-        // Check license is active
-        if !license.is_active {
-            self.env().revert(Error::LicenseInactive);
+        if parent.parent_license_id.is_some()
+            || !self.license_tier(parent.license_type).is_sublicensable()
+        {
+            self.env().revert(Error::SublicensingNotAllowed);
         }
 
-        // Exclusive licenses cannot be transferred
-        if license.license_type.to_u8() == LicenseType::Exclusive.to_u8() {
-            self.env().revert(Error::CannotTransferExclusiveLicense);
+        let timestamp = self.env().get_block_time();
+        match self.usability(&parent, timestamp) {
+            VerificationStatus::Expired => self.env().revert(Error::LicenseExpired),
+            VerificationStatus::Permitted => {}
+            _ => self.env().revert(Error::LicenseInactive),
+        }
+        if recipient == caller {
+            self.env().revert(Error::InvalidRecipients);
         }
 
-        // Calculate royalties
-        let creator_royalty = sale_price * CREATOR_ROYALTY_PERCENT / 100;
-        let platform_fee = sale_price * RESALE_PLATFORM_FEE_PERCENT / 100;
-        let total_required = sale_price + creator_royalty + platform_fee;
+        // The child's rights can never exceed the parent's
+        let scope = UsageRights { duration: None, ..scope };
+        if !scope.is_valid() || !scope.is_within(&self.effective_rights(&parent)) {
+            self.env().revert(Error::InvalidSublicenseScope);
+        }
 
-        // Verify payment
-        if attached_value < total_required {
-            self.env().revert(Error::InsufficientRoyaltyPayment);
+        if expires_at.is_some_and(|expires_at| expires_at <= timestamp) {
+            self.env().revert(Error::InvalidSublicenseExpiry);
         }
+        let expires_at = match (expires_at, parent.expires_at) {
+            (Some(child), Some(parent)) => Some(child.min(parent)),
+            (child, parent) => child.or(parent),
+        };
 
-        let previous_owner = license.current_owner;
-        let license_type_u8 = license.license_type.to_u8();
-        let sample_id = license.sample_id;
+        // Generate new license ID
+        let sublicense_id = self.license_count.get_or_default() + 1;
+        self.license_count.set(sublicense_id);
 
-        // Update license ownership
-        license.current_owner = to;
-        license.transfer_count += 1;
-        self.licenses.set(&license_id, license.clone());
+        let sample_id = parent.sample_id;
+        self.licenses.set(&sublicense_id, LicenseMetadata {
+            license_id: sublicense_id,
+            sample_id,
+            license_type: parent.license_type,
+            original_creator: parent.original_creator,
+            current_owner: recipient,
+            purchase_price: U512::zero(),
+            currency: parent.currency,
+            purchase_timestamp: timestamp,
+            is_active: true,
+            transfer_count: 0,
+            is_promotional: false,
+            status: LicenseStatus::Active,
+            status_reason: None,
+            suspension_requested: false,
+            expires_at,
+            terms_hash: parent.terms_hash,
+            parent_license_id: Some(license_id),
+            territories: parent.territories,
+        });
 
-        // Update owner indexing - remove from previous owner's list
-        self.remove_from_owner_list(previous_owner, license_id);
-        // Add to new owner's list
-        let new_owner_count = self.owner_license_count.get_or_default(&to);
-        self.owner_license_at.set(&(to, new_owner_count), license_id);
-        self.owner_license_count.set(&to, new_owner_count + 1);
+        // Sublicenses are listed under the recipient, but not counted against the sample's supply
+        self.index.add_to_owner(recipient, sublicense_id);
+        self.sublicenses.add(license_id, sublicense_id, recipient, sample_id, scope.clone());
 
-        // Update user license tracking
-        self.user_has_license_type.set(&(previous_owner, sample_id, license_type_u8), false);
-        self.user_sample_license.set(&(previous_owner, sample_id, license_type_u8), 0);
-        self.user_has_license_type.set(&(to, sample_id, license_type_u8), true);
-        self.user_sample_license.set(&(to, sample_id, license_type_u8), license_id);
+        self.env().emit_event(SublicenseIssued {
+            license_id: sublicense_id,
+            parent_license_id: license_id,
+            sample_id,
+            issuer: caller,
+            recipient,
+            streaming: scope.streaming,
+            sync: scope.sync,
+            broadcast: scope.broadcast,
+            commercial_distribution: scope.commercial_distribution,
+            attribution_required: scope.attribution_required,
+            max_units: scope.max_units,
+            expires_at,
+            timestamp,
+        });
 
-        // Transfer sale price to seller
-        self.env().transfer_tokens(&previous_owner, &sale_price);
+        sublicense_id
+    }
 
-        // Add royalty to creator's earnings
-        let current_royalties = self.creator_royalty_earnings.get_or_default(&license.original_creator);
-        self.creator_royalty_earnings.set(&license.original_creator, current_royalties + creator_royalty);
-        let total_royalties = self.creator_total_royalties.get_or_default(&license.original_creator);
-        self.creator_total_royalties.set(&license.original_creator, total_royalties + creator_royalty);
+    /// Get all sublicenses issued under a license
+    pub fn get_sublicenses(&self, license_id: u64) -> Vec<u64> {
+        self.sublicenses.children(license_id)
+    }
 
-        // Transfer platform fee to admin
-        let admin = self.admin.get().unwrap();
-        self.env().transfer_tokens(&admin, &platform_fee);
+    /// Get the scope granted to a sublicense
+    pub fn get_sublicense_scope(&self, license_id: u64) -> Option<UsageRights> {
+        self.sublicenses.scope(license_id)
+    }
 
+    // ============================================
+    // Releases
+    // ============================================
+
+    /// Register a released work that uses the licensed sample (license owner only)
+    /// The license must be valid and its type must allow commercial distribution
+    pub fn register_release(&mut self, license_id: u64, isrc: String, title: String, release_date: u64) -> u64 {
+        let caller = self.env().caller();
+        self.roles.require_not_paused();
+
+        let license = self.licenses.get(&license_id)
+            .unwrap_or_else(|| self.env().revert(Error::LicenseNotFound));
+
+        if license.current_owner != caller {
+            self.env().revert(Error::NotLicenseOwner);
+        }
         let timestamp = self.env().get_block_time();
+        match self.usability(&license, timestamp) {
+            VerificationStatus::Expired => self.env().revert(Error::LicenseExpired),
+            VerificationStatus::Permitted => {}
+            _ => self.env().revert(Error::LicenseInactive),
+        }
+        if !self.effective_rights(&license).permits(&UsageType::CommercialDistribution) {
+            self.env().revert(Error::ReleaseNotPermitted);
+        }
 
-        // Emit events
-        self.env().emit_event(LicenseTransferred {
+        // Validate release details
+        if isrc.len() != ISRC_LENGTH || !isrc.bytes().all(|b| b.is_ascii_alphanumeric()) {
+            self.env().revert(Error::InvalidIsrc);
+        }
+        let isrc = isrc.to_ascii_uppercase();
+        if self.releases.release_by_isrc(&isrc).is_some() {
+            self.env().revert(Error::IsrcAlreadyRegistered);
+        }
+        if title.is_empty() || title.len() > MAX_RELEASE_TITLE_LENGTH {
+            self.env().revert(Error::InvalidReleaseTitle);
+        }
+
+        let release = self.releases.add(Release {
+            release_id: 0,
             license_id,
-            from: previous_owner,
-            to,
-            sale_price,
-            creator_royalty,
-            platform_fee,
-            timestamp,
+            sample_id: license.sample_id,
+            owner: caller,
+            isrc,
+            title,
+            release_date,
+            registered_at: timestamp,
         });
 
-        self.env().emit_event(RoyaltyPaid {
+        self.env().emit_event(ReleaseRegistered {
+            release_id: release.release_id,
             license_id,
-            creator: license.original_creator,
-            amount: creator_royalty,
+            sample_id: release.sample_id,
+            owner: caller,
+            isrc: release.isrc,
+            title: release.title,
+            release_date,
             timestamp,
         });
+
+        release.release_id
+    }
+
+    /// Get a release by ID
+    pub fn get_release(&self, release_id: u64) -> Option<Release> {
+        self.releases.release(release_id)
+    }
+
+    /// Get the release registered for an ISRC
+    pub fn get_release_by_isrc(&self, isrc: String) -> Option<Release> {
+        self.releases.release_by_isrc(&isrc.to_ascii_uppercase())
+    }
+
+    /// Get all releases registered against a license
+    pub fn get_releases_by_license(&self, license_id: u64) -> Vec<Release> {
+        self.releases.license_releases(license_id)
+    }
+
+    /// Get all releases that use a sample
+    pub fn get_releases_by_sample(&self, sample_id: u64) -> Vec<Release> {
+        self.releases.sample_releases(sample_id)
     }
 
     // ============================================
-    // Royalty Withdrawal
+    // License Enforcement
     // ============================================
 
-    /// Withdraw accumulated royalty earnings
-    pub fn withdraw_royalties(&mut self) {
+    /// Ask moderators to suspend an active license (the license's creator only)
+    /// The license stays usable until a moderator confirms with `suspend_license`
+    pub fn request_suspension(&mut self, license_id: u64, reason_code: u8) {
         let caller = self.env().caller();
+        let mut license = self.licenses.get(&license_id)
+            .unwrap_or_else(|| self.env().revert(Error::LicenseNotFound));
 
-        let earnings = self.creator_royalty_earnings.get_or_default(&caller);
-        if earnings == U512::zero() {
-            self.env().revert(Error::NoRoyaltiesToWithdraw);
+        if caller != license.original_creator {
+            self.env().revert(Error::Unauthorized);
+        }
+        if !matches!(license.status, LicenseStatus::Active) || license.suspension_requested {
+            self.env().revert(Error::InvalidLicenseStatusChange);
         }
 
-        // Reset earnings before transfer (CEI pattern)
-        self.creator_royalty_earnings.set(&caller, U512::zero());
-
-        // Transfer royalties
-        self.env().transfer_tokens(&caller, &earnings);
-
-        self.env().emit_event(RoyaltiesWithdrawn {
-            creator: caller,
-            amount: earnings,
-            timestamp: self.env().get_block_time(),
-        });
+        license.suspension_requested = true;
+        self.licenses.set(&license_id, license.clone());
+        self.emit_suspension_requested(&license, reason_code, true);
     }
 
-    // ============================================
-    // View Functions
-    // ============================================
+    /// Dismiss a creator's pending suspension request (moderator only)
+    pub fn dismiss_suspension_request(&mut self, license_id: u64, reason_code: u8) {
+        self.roles.assert_role(&MODERATOR_ROLE);
+        let mut license = self.licenses.get(&license_id)
+            .unwrap_or_else(|| self.env().revert(Error::LicenseNotFound));
 
-    /// Get license metadata by ID
-    pub fn get_license(&self, license_id: u64) -> Option<LicenseMetadata> {
-        self.licenses.get(&license_id)
-    }
+        if !license.suspension_requested {
+            self.env().revert(Error::InvalidLicenseStatusChange);
+        }
 
-    /// Get the owner of a license
-    pub fn get_owner(&self, license_id: u64) -> Option<Address> {
-        self.licenses.get(&license_id).map(|l| l.current_owner)
+        license.suspension_requested = false;
+        self.licenses.set(&license_id, license.clone());
+        self.emit_suspension_requested(&license, reason_code, false);
     }
 
-    /// Get total number of licenses minted
-    pub fn get_license_count(&self) -> u64 {
-        self.license_count.get_or_default()
-    }
+    /// Suspend a license pending review (moderator only), confirming any creator request
+    pub fn suspend_license(&mut self, license_id: u64, reason_code: u8) {
+        let caller = self.roles.assert_role(&MODERATOR_ROLE);
+        let mut license = self.licenses.get(&license_id)
+            .unwrap_or_else(|| self.env().revert(Error::LicenseNotFound));
 
-    /// Get all license IDs owned by an address
-    pub fn get_licenses_by_owner(&self, owner: Address) -> Vec<u64> {
-        let count = self.owner_license_count.get_or_default(&owner);
-        let mut result = Vec::new();
-        for i in 0..count {
-            if let Some(id) = self.owner_license_at.get(&(owner, i)) {
-                if id > 0 {
-                    // Verify ownership hasn't changed
-                    if let Some(license) = self.licenses.get(&id) {
-                        if license.current_owner == owner {
-                            result.push(id);
-                        }
-                    }
-                }
-            }
+        if !matches!(license.status, LicenseStatus::Active) {
+            self.env().revert(Error::InvalidLicenseStatusChange);
         }
-        result
+
+        license.suspension_requested = false;
+        self.set_license_status(license, LicenseStatus::Suspended, reason_code, caller);
     }
 
-    /// Get all license IDs for a sample
-    pub fn get_licenses_by_sample(&self, sample_id: u64) -> Vec<u64> {
-        let count = self.sample_license_count.get_or_default(&sample_id);
-        let mut result = Vec::new();
-        for i in 0..count {
-            if let Some(id) = self.sample_license_at.get(&(sample_id, i)) {
-                result.push(id);
-            }
+    /// Reinstate a suspended license (moderator only)
+    pub fn reinstate_license(&mut self, license_id: u64, reason_code: u8) {
+        let caller = self.roles.assert_role(&MODERATOR_ROLE);
+        let license = self.licenses.get(&license_id)
+            .unwrap_or_else(|| self.env().revert(Error::LicenseNotFound));
+
+        if !matches!(license.status, LicenseStatus::Suspended) {
+            self.env().revert(Error::InvalidLicenseStatusChange);
         }
-        result
+
+        self.set_license_status(license, LicenseStatus::Active, reason_code, caller);
     }
 
-    /// Get license count for a sample
+    /// Permanently revoke a license (moderator only)
+    /// The holder loses the license type for the sample and the license can no longer be transferred;
+    /// an exclusive license is released so the sample (or its territories) can be licensed again.
+    /// Sublicenses issued under it keep their own status but can no longer be used or
+    /// transferred, since usability follows the parent chain
+    pub fn revoke_license(&mut self, license_id: u64, reason_code: u8) {
+        let caller = self.roles.assert_role(&MODERATOR_ROLE);
+        let mut license = self.licenses.get(&license_id)
+            .unwrap_or_else(|| self.env().revert(Error::LicenseNotFound));
+
+        if matches!(license.status, LicenseStatus::Revoked) {
+            self.env().revert(Error::LicenseRevoked);
+        }
+
+        // Sublicenses are not tracked in the holder index, nor hold exclusivity
+        if license.parent_license_id.is_none() {
+            self.index.clear_holder(license.current_owner, license.sample_id, license.license_type);
+            self.exclusivity.release(license.sample_id, &license.territories, license_id);
+        }
+
+        license.suspension_requested = false;
+        self.set_license_status(license, LicenseStatus::Revoked, reason_code, caller);
+    }
+
+    /// Get a license's status (0=Active, 1=Suspended, 2=Revoked)
+    pub fn get_license_status(&self, license_id: u64) -> Option<u8> {
+        self.licenses.get(&license_id).map(|license| license.status.to_u8())
+    }
+
+    // ============================================
+    // Roles & Emergency Stop
+    // ============================================
+
+    /// Grant a role to an account (admin only)
+    /// License minters are also authorized in the minter registry (uncapped unless
+    /// a cap was set before), so their pause switch and cap apply
+    pub fn grant_role(&mut self, role: Role, account: Address) {
+        self.roles.grant_role(&role, &account);
+        if role == LICENSE_MINTER_ROLE && !self.is_registered_minter(&account) {
+            let cap = self.minters.info(&account).and_then(|info| info.cap);
+            let info = self.minters.authorize(account, cap);
+            self.emit_minter_updated(info);
+        }
+    }
+
+    /// Revoke a role from an account (admin only)
+    /// Revoking the license minter role also removes the account from the minter registry
+    pub fn revoke_role(&mut self, role: Role, account: Address) {
+        self.roles.revoke_role(&role, &account);
+        if role == LICENSE_MINTER_ROLE {
+            self.deregister_license_minter(account);
+        }
+    }
+
+    /// Give up a role held by the caller
+    pub fn renounce_role(&mut self, role: Role) {
+        self.roles.renounce_role(&role);
+        if role == LICENSE_MINTER_ROLE {
+            self.deregister_license_minter(self.env().caller());
+        }
+    }
+
+    /// Check if an account has been granted a role
+    pub fn has_role(&self, role: Role, account: Address) -> bool {
+        self.roles.has_role(&role, &account)
+    }
+
+    /// Pause minting and license transfers (pauser only)
+    /// Royalty withdrawals stay available while paused
+    pub fn pause(&mut self) {
+        self.roles.pause();
+    }
+
+    /// Resume normal operation (pauser only)
+    pub fn unpause(&mut self) {
+        self.roles.unpause();
+    }
+
+    /// Check if the contract is paused
+    pub fn is_paused(&self) -> bool {
+        self.roles.is_paused()
+    }
+
+    // ============================================
+    // View Functions
+    // ============================================
+
+    /// Get license metadata by ID
+    pub fn get_license(&self, license_id: u64) -> Option<LicenseMetadata> {
+        self.licenses.get(&license_id)
+    }
+
+    /// Get the owner of a license
+    pub fn get_owner(&self, license_id: u64) -> Option<Address> {
+        self.licenses.get(&license_id).map(|l| l.current_owner)
+    }
+
+    /// Get total number of licenses minted
+    pub fn get_license_count(&self) -> u64 {
+        self.license_count.get_or_default()
+    }
+
+    /// Get all license IDs owned by an address
+    pub fn get_licenses_by_owner(&self, owner: Address) -> Vec<u64> {
+        // Verify ownership hasn't changed
+        self.index.owner_licenses(&owner)
+            .into_iter()
+            .filter(|id| self.licenses.get(id).is_some_and(|license| license.current_owner == owner))
+            .collect()
+    }
+
+    /// Get all license IDs for a sample
+    pub fn get_licenses_by_sample(&self, sample_id: u64) -> Vec<u64> {
+        self.index.sample_licenses(sample_id)
+    }
+
+    /// Get license count for a sample
     pub fn get_sample_license_count(&self, sample_id: u64) -> u64 {
-        self.sample_license_count.get_or_default(&sample_id)
+        self.index.sample_license_count(sample_id)
     }
 
     /// Check if user has a specific license type for a sample
+    /// Suspended, revoked and expired licenses are reported as invalid
     pub fn has_license(&self, owner: Address, sample_id: u64, license_type: u8) -> bool {
-        self.user_has_license_type.get_or_default(&(owner, sample_id, license_type))
+        self.index.holder_license(&owner, sample_id, license_type)
+            .is_some_and(|license_id| self.is_license_valid(license_id))
+    }
+
+    /// Check if a user holds a valid license of any type for a sample
+    pub fn holds_any_license(&self, owner: Address, sample_id: u64) -> bool {
+        self.index.held_types(&owner, sample_id).into_iter()
+            .any(|license_type| self.has_license(owner, sample_id, license_type))
+    }
+
+    /// Check if a license exists and is currently valid (not suspended, revoked or expired)
+    /// A sublicense is only valid while its parent is
+    pub fn is_license_valid(&self, license_id: u64) -> bool {
+        let now = self.env().get_block_time();
+        self.licenses.get(&license_id)
+            .is_some_and(|license| matches!(self.usability(&license, now), VerificationStatus::Permitted))
     }
 
     /// Get user's license ID for a sample and type (if they have one)
     pub fn get_user_license(&self, owner: Address, sample_id: u64, license_type: u8) -> Option<u64> {
-        let license_id = self.user_sample_license.get_or_default(&(owner, sample_id, license_type));
-        if license_id > 0 {
-            Some(license_id)
-        } else {
-            None
+        self.index.holder_license(&owner, sample_id, license_type)
+    }
+
+    /// Get the terms in force for a sample's license tier (seller terms, else the platform default)
+    pub fn get_license_terms(&self, sample_id: u64, license_type: u8) -> Option<String> {
+        self.terms.terms_in_force(sample_id, license_type)
+    }
+
+    /// Get the platform default terms for a license type
+    pub fn get_default_terms(&self, license_type: u8) -> Option<String> {
+        self.terms.default_terms(license_type)
+    }
+
+    /// Get the usage rights granted by a license tier
+    pub fn get_usage_rights(&self, license_type: u8) -> Option<UsageRights> {
+        self.tiers.tier(license_type).map(|tier| tier.rights)
+    }
+
+    /// Get a license tier by ID
+    pub fn get_tier(&self, tier_id: u8) -> Option<LicenseTier> {
+        self.tiers.tier(tier_id)
+    }
+
+    /// Get every license tier, built-in tiers first
+    pub fn get_tiers(&self) -> Vec<LicenseTier> {
+        self.tiers.all_tiers()
+    }
+
+    /// Check whether an address may use a sample in a given way
+    /// `usage`: 0=Streaming, 1=Sync, 2=Broadcast, 3=CommercialDistribution
+    /// `territory`: ISO 3166-1 alpha-2 code of where the use happens (None = only worldwide licenses count)
+    /// Checks ownership, status, expiry, territory and the rights matrix of every license and sublicense
+    /// the owner holds for the sample; when none permits the usage, the closest miss is reported
+    pub fn verify_usage(
+        &self,
+        owner: Address,
+        sample_id: u64,
+        usage: u8,
+        territory: Option<String>,
+    ) -> UsageVerification {
+        let usage = UsageType::from_u8(usage)
+            .unwrap_or_else(|| self.env().revert(Error::InvalidUsageType));
+        let now = self.env().get_block_time();
+
+        let mut result = UsageVerification {
+            status: VerificationStatus::NoLicense,
+            ..Default::default()
+        };
+
+        let mut candidates: Vec<u64> = self.tiers.all_tiers()
+            .iter()
+            .filter_map(|tier| self.index.holder_license(&owner, sample_id, tier.tier_id))
+            .collect();
+        candidates.extend(self.sublicenses.held(&owner, sample_id));
+
+        for license_id in candidates {
+            let license = match self.licenses.get(&license_id)
+                .filter(|license| license.current_owner == owner)
+            {
+                Some(license) => license,
+                None => continue,
+            };
+
+            let rights = self.effective_rights(&license);
+            let status = if !rights.permits(&usage) {
+                VerificationStatus::NotPermitted
+            } else if !license.covers_territory(&territory) {
+                VerificationStatus::OutsideTerritory
+            } else {
+                self.usability(&license, now)
+            };
+
+            let is_permitted = matches!(status, VerificationStatus::Permitted);
+            // Keep the outcome closest to a permit: a covering but unusable license beats a non-covering one
+            if status.closeness() > result.status.closeness() {
+                result = UsageVerification {
+                    is_permitted,
+                    status,
+                    license_id: Some(license.license_id),
+                    license_type: Some(license.license_type),
+                    attribution_required: rights.attribution_required,
+                    max_units: rights.max_units,
+                    expires_at: license.expires_at,
+                };
+            }
+            if is_permitted {
+                break;
+            }
         }
+
+        result
     }
 
-    /// Check if sample has an exclusive license
+    /// Check if sample has a current (unexpired, unrevoked) worldwide exclusive license
     pub fn is_exclusively_licensed(&self, sample_id: u64) -> bool {
-        self.sample_has_exclusive.get_or_default(&sample_id)
+        self.exclusivity.has_worldwide(sample_id, self.env().get_block_time())
     }
 
-    /// Get exclusive license holder for a sample
+    /// Get the current worldwide exclusive license holder for a sample
     pub fn get_exclusive_holder(&self, sample_id: u64) -> Option<Address> {
-        if self.sample_has_exclusive.get_or_default(&sample_id) {
-            self.sample_exclusive_holder.get(&sample_id)
-        } else {
-            None
-        }
+        self.exclusivity.worldwide_holder(sample_id, self.env().get_block_time())
+    }
+
+    /// Get the current exclusive license holder for a sample in a territory (worldwide holder included)
+    pub fn get_territory_exclusive_holder(&self, sample_id: u64, territory: String) -> Option<Address> {
+        let now = self.env().get_block_time();
+        self.exclusivity.worldwide_holder(sample_id, now)
+            .or_else(|| self.exclusivity.territory_holder(sample_id, &territory, now))
+    }
+
+    /// Check if a new license for the given territories (empty = worldwide) would be blocked by an exclusive license
+    pub fn is_territory_blocked(&self, sample_id: u64, territories: Vec<String>) -> bool {
+        self.exclusivity.is_blocked(sample_id, &territories, self.env().get_block_time())
     }
 
     /// Get sample license info summary
     pub fn get_sample_license_info(&self, sample_id: u64) -> SampleLicenseInfo {
         let licenses = self.get_licenses_by_sample(sample_id);
-        let mut info = SampleLicenseInfo::default();
-        info.total_licenses = licenses.len() as u64;
+        let mut info = SampleLicenseInfo {
+            total_licenses: licenses.len() as u64,
+            ..Default::default()
+        };
+
+        let tiers = self.tiers.all_tiers();
+        let mut issued = vec![0u64; tiers.len()];
+        // Revoked and expired exclusives no longer count, as when minting
+        let live_exclusive = self.exclusivity.worldwide_license(sample_id, self.env().get_block_time());
 
         for license_id in licenses {
             if let Some(license) = self.licenses.get(&license_id) {
-                match license.license_type {
-                    LicenseType::Personal => info.personal_count += 1,
-                    LicenseType::Commercial => info.commercial_count += 1,
-                    LicenseType::Broadcast => info.broadcast_count += 1,
-                    LicenseType::Exclusive => {
-                        info.has_exclusive = true;
-                        info.exclusive_holder = Some(license.current_owner);
-                    }
+                if let Some(count) = issued.get_mut(license.license_type as usize) {
+                    *count += 1;
+                }
+                match LicenseType::from_u8(license.license_type) {
+                    Some(LicenseType::Personal) => info.personal_count += 1,
+                    Some(LicenseType::Commercial) => info.commercial_count += 1,
+                    Some(LicenseType::Broadcast) => info.broadcast_count += 1,
+                    _ => {}
+                }
+                if live_exclusive == Some(license.license_id) {
+                    info.has_exclusive = true;
+                    info.exclusive_holder = Some(license.current_owner);
                 }
             }
         }
 
+        info.personal_remaining = self.remaining_supply(sample_id, LicenseType::Personal.to_u8(), info.personal_count);
+        info.commercial_remaining = self.remaining_supply(sample_id, LicenseType::Commercial.to_u8(), info.commercial_count);
+        info.broadcast_remaining = self.remaining_supply(sample_id, LicenseType::Broadcast.to_u8(), info.broadcast_count);
+
+        info.tiers = tiers.iter()
+            .zip(issued)
+            .map(|(tier, issued)| TierSupply {
+                tier_id: tier.tier_id,
+                issued,
+                remaining: if tier.is_exclusive {
+                    // One exclusive license per sample, across all exclusive tiers
+                    Some(u64::from(!info.has_exclusive))
+                } else {
+                    self.remaining_supply(sample_id, tier.tier_id, issued)
+                },
+            })
+            .collect();
+
         info
     }
 
+    /// Get the maximum supply of a license type for a sample (0 = unlimited)
+    pub fn get_max_supply(&self, sample_id: u64, license_type: u8) -> u64 {
+        self.sample_max_supply.get_or_default(&(sample_id, license_type))
+    }
+
+    /// Get the remaining supply of a license type for a sample (None = unlimited)
+    pub fn get_remaining_supply(&self, sample_id: u64, license_type: u8) -> Option<u64> {
+        let tier = self.tiers.tier(license_type)?;
+        if tier.is_exclusive {
+            // One exclusive license per sample, across all exclusive tiers
+            return Some(u64::from(!self.is_exclusively_licensed(sample_id)));
+        }
+        self.remaining_supply(sample_id, license_type, self.index.issued_count(sample_id, license_type))
+    }
+
     /// Get creator's available royalty earnings
     pub fn get_royalty_earnings(&self, creator: Address) -> U512 {
-        self.creator_royalty_earnings.get_or_default(&creator)
+        self.royalties.earnings(&creator, &None)
     }
 
     /// Get creator's total lifetime royalties
     pub fn get_total_royalties(&self, creator: Address) -> U512 {
-        self.creator_total_royalties.get_or_default(&creator)
+        self.royalties.total_earned(&creator, &None)
+    }
+
+    /// Get creator's available royalty earnings in a CEP-18 token
+    pub fn get_token_royalty_earnings(&self, creator: Address, token: Address) -> U512 {
+        self.royalties.earnings(&creator, &Some(token))
     }
 
     // ============================================
     // Internal Functions
     // ============================================
 
-    /// Remove a license from an owner's indexed list
-    /// Note: This leaves gaps in the index, which is handled in get_licenses_by_owner
-    fn remove_from_owner_list(&mut self, owner: Address, license_id: u64) {
-        let count = self.owner_license_count.get_or_default(&owner);
-        for i in 0..count {
-            if let Some(id) = self.owner_license_at.get(&(owner, i)) {
-                if id == license_id {
-                    // Mark as removed by setting to 0
-                    self.owner_license_at.set(&(owner, i), 0);
-                    break;
+    /// Revert unless the caller is the primary marketplace (and an active minter)
+    fn assert_primary_marketplace(&self) {
+        if !self.minters.is_active_primary(&self.env().caller()) {
+            self.env().revert(Error::NotPrimaryMarketplace);
+        }
+    }
+
+    /// Revert unless the sample is listed on the primary marketplace by `creator`
+    fn assert_marketplace_creator(&self, sample_id: u64, creator: &Address) {
+        let marketplace = self.minters.primary()
+            .unwrap_or_else(|| self.env().revert(Error::MarketplaceNotSet));
+        let seller = SampledMarketplaceContractRef::new(self.env(), marketplace)
+            .get_sample(sample_id)
+            .map(|sample| sample.seller);
+        if seller != Some(*creator) {
+            self.env().revert(Error::CreatorMismatch);
+        }
+    }
+
+    /// Check that the caller may mint one more license and count it against its cap
+    /// Every minter, license minter role holders included, needs an authorized
+    /// registry entry and is bound by its pause switch and cap
+    fn authorize_mint(&mut self) {
+        let caller = self.env().caller();
+        self.minters.assert_can_mint(&caller);
+        self.minters.record_mint(&caller);
+    }
+
+    /// Check if an account is an authorized entry in the minter registry
+    fn is_registered_minter(&self, account: &Address) -> bool {
+        self.minters.info(account).is_some_and(|info| info.is_authorized)
+    }
+
+    /// Remove a former license minter role holder from the minter registry
+    /// The primary marketplace keeps its registry entry
+    fn deregister_license_minter(&mut self, account: Address) {
+        if self.is_registered_minter(&account) && self.minters.primary() != Some(account) {
+            let info = self.minters.revoke(account);
+            self.emit_minter_updated(info);
+        }
+    }
+
+    /// Revert unless the license type is valid and the terms hash, if any, is non-empty and within bounds
+    fn validate_terms(&self, license_type: u8, terms_hash: &Option<String>) {
+        if self.tiers.tier(license_type).is_none() {
+            self.env().revert(Error::InvalidLicenseType);
+        }
+        if let Some(hash) = terms_hash {
+            if hash.is_empty() || hash.len() > MAX_TERMS_HASH_LENGTH {
+                self.env().revert(Error::InvalidTermsHash);
+            }
+        }
+    }
+
+    /// Rights a license grants: its type's rights, narrowed to the scope for sublicenses
+    fn effective_rights(&self, license: &LicenseMetadata) -> UsageRights {
+        let rights = self.license_tier(license.license_type).rights;
+        match self.sublicenses.scope(license.license_id) {
+            Some(scope) => rights.narrowed_to(&scope),
+            None => rights,
+        }
+    }
+
+    /// Whether a license can currently be used (Permitted, Inactive or Expired)
+    /// Sublicenses also follow their parent's status and expiry
+    fn usability(&self, license: &LicenseMetadata, now: u64) -> VerificationStatus {
+        if !matches!(license.status, LicenseStatus::Active) {
+            return VerificationStatus::Inactive;
+        }
+        if license.is_expired(now) {
+            return VerificationStatus::Expired;
+        }
+        match license.parent_license_id.and_then(|parent_id| self.licenses.get(&parent_id)) {
+            Some(parent) => self.usability(&parent, now),
+            None => VerificationStatus::Permitted,
+        }
+    }
+
+    /// Tier of an existing license (tiers are never removed)
+    fn license_tier(&self, tier_id: u8) -> LicenseTier {
+        self.tiers.tier(tier_id)
+            .unwrap_or_else(|| self.env().revert(Error::InvalidLicenseType))
+    }
+
+    /// Revert unless the tier's name, rights and multiplier are valid
+    fn validate_tier(&self, tier: &LicenseTier) {
+        if tier.name.is_empty() || tier.name.len() > MAX_TIER_NAME_LENGTH {
+            self.env().revert(Error::InvalidTierName);
+        }
+        if !tier.rights.is_valid() {
+            self.env().revert(Error::InvalidUsageRights);
+        }
+        if tier.default_multiplier == 0 {
+            self.env().revert(Error::InvalidPricingMultiplier);
+        }
+        if tier.is_exclusive && tier.is_transferable {
+            self.env().revert(Error::ExclusiveTierTransferable);
+        }
+    }
+
+    /// Emit the tier update event, with the tier's usage rights flattened into its fields
+    fn emit_tier_updated(&self, tier: LicenseTier) {
+        self.env().emit_event(LicenseTierUpdated {
+            tier_id: tier.tier_id,
+            name: tier.name,
+            default_multiplier: tier.default_multiplier,
+            is_transferable: tier.is_transferable,
+            is_exclusive: tier.is_exclusive,
+            is_active: tier.is_active,
+            streaming: tier.rights.streaming,
+            sync: tier.rights.sync,
+            broadcast: tier.rights.broadcast,
+            commercial_distribution: tier.rights.commercial_distribution,
+            attribution_required: tier.rights.attribution_required,
+            max_units: tier.rights.max_units,
+            duration: tier.rights.duration,
+            timestamp: self.env().get_block_time(),
+        });
+    }
+
+    /// Store a license's new status and emit the status event
+    fn set_license_status(
+        &mut self,
+        mut license: LicenseMetadata,
+        status: LicenseStatus,
+        reason_code: u8,
+        actor: Address,
+    ) {
+        license.status = status;
+        license.is_active = matches!(status, LicenseStatus::Active);
+        license.status_reason = Some(reason_code);
+        let license_id = license.license_id;
+        let sample_id = license.sample_id;
+        let owner = license.current_owner;
+        self.licenses.set(&license_id, license);
+
+        self.env().emit_event(LicenseStatusChanged {
+            license_id,
+            sample_id,
+            owner,
+            status: status.to_u8(),
+            reason_code,
+            actor,
+            timestamp: self.env().get_block_time(),
+        });
+    }
+
+    /// Emit the suspension request event
+    fn emit_suspension_requested(&self, license: &LicenseMetadata, reason_code: u8, pending: bool) {
+        self.env().emit_event(LicenseSuspensionRequested {
+            license_id: license.license_id,
+            sample_id: license.sample_id,
+            creator: license.original_creator,
+            reason_code,
+            pending,
+            timestamp: self.env().get_block_time(),
+        });
+    }
+
+    /// Emit the minter update event
+    fn emit_minter_updated(&self, info: MinterInfo) {
+        self.env().emit_event(MinterUpdated {
+            minter: info.minter,
+            is_authorized: info.is_authorized,
+            is_paused: info.is_paused,
+            cap: info.cap,
+            minted: info.minted,
+            timestamp: self.env().get_block_time(),
+        });
+    }
+
+    /// Remaining supply given the number already issued (None = unlimited)
+    fn remaining_supply(&self, sample_id: u64, license_type: u8, issued: u64) -> Option<u64> {
+        let max_supply = self.sample_max_supply.get_or_default(&(sample_id, license_type));
+        if max_supply == 0 {
+            None
+        } else {
+            Some(max_supply.saturating_sub(issued))
+        }
+    }
+
+    /// Transfer a license, settling the sale and royalties in the given currency
+    fn transfer_license_internal(
+        &mut self,
+        license_id: u64,
+        to: Address,
+        sale_price: U512,
+        currency: Option<Address>,
+    ) {
+        let caller = self.env().caller();
+        self.roles.require_not_paused();
+
+        // Get license
+        let mut license = self.licenses.get(&license_id)
+            .unwrap_or_else(|| self.env().revert(Error::LicenseNotFound));
+
+        // Validate ownership
+        if license.current_owner != caller {
+            self.env().revert(Error::NotLicenseOwner);
+        }
+
+        // Check license is usable (sublicenses also need a usable parent)
+        match self.usability(&license, self.env().get_block_time()) {
+            VerificationStatus::Permitted => {}
+            VerificationStatus::Expired => self.env().revert(Error::LicenseExpired),
+            _ => self.env().revert(Error::LicenseInactive),
+        }
+
+        // Exclusive tiers, and tiers configured as non-transferable, cannot be resold
+        let tier = self.license_tier(license.license_type);
+        if !tier.is_transferable {
+            if tier.is_exclusive {
+                self.env().revert(Error::CannotTransferExclusiveLicense);
+            }
+            self.env().revert(Error::LicenseNotTransferable);
+        }
+
+        // Sublicenses are issued to a specific recipient
+        if license.parent_license_id.is_some() {
+            self.env().revert(Error::CannotTransferSublicense);
+        }
+
+        // Calculate royalties
+        let creator_royalty = sale_price * CREATOR_ROYALTY_PERCENT / 100;
+        let platform_fee = sale_price * RESALE_PLATFORM_FEE_PERCENT / 100;
+        let total_required = sale_price + creator_royalty + platform_fee;
+
+        // Verify payment
+        match currency {
+            None => {
+                if self.env().attached_value() < total_required {
+                    self.env().revert(Error::InsufficientRoyaltyPayment);
                 }
             }
+            Some(token) => {
+                self.royalties.assert_accepted(&currency);
+                self.royalties.collect_token(&caller, &token, total_required);
+            }
+        }
+
+        let previous_owner = license.current_owner;
+        let license_type_u8 = license.license_type;
+        let sample_id = license.sample_id;
+
+        // Update license ownership
+        license.current_owner = to;
+        license.transfer_count += 1;
+        self.licenses.set(&license_id, license.clone());
+
+        // Update owner indexing - move from previous owner's list to the new owner's
+        self.index.remove_from_owner(previous_owner, license_id);
+        self.index.add_to_owner(to, license_id);
+
+        // Update user license tracking
+        self.index.clear_holder(previous_owner, sample_id, license_type_u8);
+        self.index.set_holder(to, sample_id, license_type_u8, license_id);
+
+        // Transfer sale price to seller
+        self.royalties.pay_out(&previous_owner, &currency, sale_price);
+
+        // Add royalty to creator's earnings
+        self.royalties.credit_earnings(license.original_creator, currency, creator_royalty);
+
+        // Transfer platform fee to admin
+        let admin = self.admin.get().unwrap();
+        self.royalties.pay_out(&admin, &currency, platform_fee);
+
+        let timestamp = self.env().get_block_time();
+
+        // Emit events
+        self.env().emit_event(LicenseTransferred {
+            license_id,
+            from: previous_owner,
+            to,
+            sale_price,
+            creator_royalty,
+            platform_fee,
+            timestamp,
+            currency,
+        });
+
+        self.env().emit_event(RoyaltyPaid {
+            license_id,
+            creator: license.original_creator,
+            amount: creator_royalty,
+            timestamp,
+            currency,
+        });
+    }
+
+    /// Withdraw a creator's royalties in the given currency
+    fn withdraw_royalties_internal(&mut self, creator: Address, currency: Option<Address>) {
+        // Reset earnings before transfer (CEI pattern)
+        let earnings = self.royalties.take_earnings(creator, currency);
+        if earnings == U512::zero() {
+            self.env().revert(Error::NoRoyaltiesToWithdraw);
         }
+
+        // Transfer royalties
+        self.royalties.pay_out(&creator, &currency, earnings);
+
+        self.env().emit_event(RoyaltiesWithdrawn {
+            creator,
+            amount: earnings,
+            timestamp: self.env().get_block_time(),
+            currency,
+        });
     }
 }
 
 // ============================================
-// Tests - TODO: Fix test configuration for cross-contract references
+// Tests
 // ============================================
 
-// Tests are temporarily disabled due to Odra macro limitations with cross-contract references.
-// The contracts compile and build successfully. Integration tests should be run separately.
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sampled_marketplace::{SampledMarketplace, SampledMarketplaceInitArgs};
+    use odra::host::{Deployer, HostEnv};
+
+    const SAMPLE_ID: u64 = 1;
+
+    /// Deploy the contract with account 1 acting as the primary marketplace
+    fn setup() -> (HostEnv, LicenseNftHostRef) {
+        let env = odra_test::env();
+        let admin = env.get_account(0);
+        let mut licenses = LicenseNft::deploy(&env, LicenseNftInitArgs { admin });
+        licenses.set_marketplace(env.get_account(1));
+        (env, licenses)
+    }
+
+    /// Mint a license as the primary marketplace and return its ID
+    fn mint(
+        env: &HostEnv,
+        licenses: &mut LicenseNftHostRef,
+        license_type: LicenseType,
+        buyer: Address,
+        territories: &[&str],
+    ) -> u64 {
+        env.set_caller(env.get_account(1));
+        licenses.mint_license(
+            SAMPLE_ID,
+            license_type.to_u8(),
+            buyer,
+            env.get_account(2),
+            U512::from(1_000u64),
+            None,
+            false,
+            None,
+            territories.iter().map(|territory| String::from(*territory)).collect(),
+        )
+    }
+
+    fn try_mint(
+        env: &HostEnv,
+        licenses: &mut LicenseNftHostRef,
+        license_type: LicenseType,
+        buyer: Address,
+        territories: &[&str],
+    ) -> OdraResult<u64> {
+        env.set_caller(env.get_account(1));
+        licenses.try_mint_license(
+            SAMPLE_ID,
+            license_type.to_u8(),
+            buyer,
+            env.get_account(2),
+            U512::from(1_000u64),
+            None,
+            false,
+            None,
+            territories.iter().map(|territory| String::from(*territory)).collect(),
+        )
+    }
+
+    /// Deploy the contract behind a real marketplace listing sample 1 by account 2
+    fn setup_with_marketplace() -> (HostEnv, LicenseNftHostRef) {
+        let env = odra_test::env();
+        let admin = env.get_account(0);
+        let mut licenses = LicenseNft::deploy(&env, LicenseNftInitArgs { admin });
+        let mut marketplace = SampledMarketplace::deploy(&env, SampledMarketplaceInitArgs { admin });
+        licenses.set_marketplace(marketplace.address());
+        marketplace.set_license_nft_contract(licenses.address());
+
+        env.set_caller(env.get_account(2));
+        marketplace.upload_sample(
+            U512::from(1_000u64),
+            false,
+            String::from("ipfs://audio"),
+            String::from("Loop"),
+            140,
+            String::from("trap"),
+            String::new(),
+            String::new(),
+            None,
+            None,
+            "ab".repeat(32),
+            None,
+            None,
+        );
+        env.set_caller(admin);
+        (env, licenses)
+    }
+
+    /// Mint a license for sample 1 as `minter`, a secondary minter
+    fn try_mint_as(
+        env: &HostEnv,
+        licenses: &mut LicenseNftHostRef,
+        minter: Address,
+        license_type: LicenseType,
+        buyer: Address,
+    ) -> OdraResult<u64> {
+        env.set_caller(minter);
+        licenses.try_mint_license(
+            SAMPLE_ID,
+            license_type.to_u8(),
+            buyer,
+            env.get_account(2),
+            U512::zero(),
+            None,
+            true,
+            None,
+            Vec::new(),
+        )
+    }
+
+    // --- Supply caps ---
+
+    #[test]
+    fn capped_tier_sells_out() {
+        let (env, mut licenses) = setup();
+        env.set_caller(env.get_account(1));
+        licenses.set_max_supply(SAMPLE_ID, LicenseType::Broadcast.to_u8(), 2);
+        assert_eq!(licenses.get_remaining_supply(SAMPLE_ID, LicenseType::Broadcast.to_u8()), Some(2));
+        assert_eq!(licenses.get_remaining_supply(SAMPLE_ID, LicenseType::Personal.to_u8()), None);
+
+        mint(&env, &mut licenses, LicenseType::Broadcast, env.get_account(3), &[]);
+        mint(&env, &mut licenses, LicenseType::Broadcast, env.get_account(4), &[]);
+        assert_eq!(licenses.get_remaining_supply(SAMPLE_ID, LicenseType::Broadcast.to_u8()), Some(0));
+        assert_eq!(licenses.get_sample_license_info(SAMPLE_ID).broadcast_remaining, Some(0));
+        assert_eq!(
+            try_mint(&env, &mut licenses, LicenseType::Broadcast, env.get_account(5), &[]),
+            Err(Error::LicenseSoldOut.into())
+        );
+
+        // Caps cannot drop below what has been issued, and 0 lifts them
+        assert_eq!(
+            licenses.try_set_max_supply(SAMPLE_ID, LicenseType::Broadcast.to_u8(), 1),
+            Err(Error::InvalidMaxSupply.into())
+        );
+        licenses.set_max_supply(SAMPLE_ID, LicenseType::Broadcast.to_u8(), 0);
+        mint(&env, &mut licenses, LicenseType::Broadcast, env.get_account(5), &[]);
+    }
+
+    #[test]
+    fn only_the_primary_marketplace_sets_caps() {
+        let (env, mut licenses) = setup();
+        env.set_caller(env.get_account(0));
+        assert_eq!(
+            licenses.try_set_max_supply(SAMPLE_ID, LicenseType::Personal.to_u8(), 5),
+            Err(Error::NotPrimaryMarketplace.into())
+        );
+        env.set_caller(env.get_account(1));
+        assert_eq!(
+            licenses.try_set_max_supply(SAMPLE_ID, LicenseType::Exclusive.to_u8(), 5),
+            Err(Error::InvalidLicenseType.into())
+        );
+    }
+
+    // --- License minter role ---
+
+    #[test]
+    fn license_minter_role_holders_obey_cap_and_pause() {
+        let (env, mut licenses) = setup_with_marketplace();
+        let admin = env.get_account(0);
+        let minter = env.get_account(5);
+        assert_eq!(
+            try_mint_as(&env, &mut licenses, minter, LicenseType::Personal, env.get_account(6)),
+            Err(Error::Unauthorized.into())
+        );
+
+        env.set_caller(admin);
+        licenses.grant_role(LICENSE_MINTER_ROLE, minter);
+        assert!(licenses.get_minter(minter).unwrap().is_authorized);
+        licenses.set_minter_cap(minter, Some(1));
+        try_mint_as(&env, &mut licenses, minter, LicenseType::Personal, env.get_account(6)).unwrap();
+        assert_eq!(
+            try_mint_as(&env, &mut licenses, minter, LicenseType::Personal, env.get_account(7)),
+            Err(Error::MinterCapReached.into())
+        );
+
+        env.set_caller(admin);
+        licenses.set_minter_cap(minter, None);
+        licenses.set_minter_paused(minter, true);
+        assert_eq!(
+            try_mint_as(&env, &mut licenses, minter, LicenseType::Personal, env.get_account(7)),
+            Err(Error::MinterPaused.into())
+        );
+
+        // Revoking the role removes the registry entry, so the pause cannot be bypassed
+        env.set_caller(admin);
+        licenses.set_minter_paused(minter, false);
+        licenses.revoke_role(LICENSE_MINTER_ROLE, minter);
+        assert!(!licenses.get_minter(minter).unwrap().is_authorized);
+        assert_eq!(
+            try_mint_as(&env, &mut licenses, minter, LicenseType::Personal, env.get_account(7)),
+            Err(Error::Unauthorized.into())
+        );
+    }
+
+    // --- Minter allowlist ---
+
+    #[test]
+    fn secondary_minters_mint_non_exclusive_licenses_for_listed_samples() {
+        let (env, mut licenses) = setup_with_marketplace();
+        let pack_store = env.get_account(5);
+        let buyer = env.get_account(6);
+        env.set_caller(env.get_account(1));
+        assert_eq!(licenses.try_authorize_minter(pack_store, None), Err(Error::Unauthorized.into()));
+
+        env.set_caller(env.get_account(0));
+        licenses.authorize_minter(pack_store, Some(10));
+        assert!(licenses.get_minters().iter().any(|info| info.minter == pack_store));
+
+        assert_eq!(
+            try_mint_as(&env, &mut licenses, pack_store, LicenseType::Exclusive, buyer),
+            Err(Error::NotPrimaryMarketplace.into())
+        );
+        env.set_caller(pack_store);
+        assert_eq!(
+            licenses.try_mint_license(
+                SAMPLE_ID,
+                LicenseType::Personal.to_u8(),
+                buyer,
+                env.get_account(3),
+                U512::zero(),
+                None,
+                true,
+                None,
+                Vec::new(),
+            ),
+            Err(Error::CreatorMismatch.into())
+        );
+        let license_id = try_mint_as(&env, &mut licenses, pack_store, LicenseType::Personal, buyer).unwrap();
+        assert_eq!(licenses.get_license(license_id).unwrap().current_owner, buyer);
+        assert_eq!(licenses.get_minter(pack_store).unwrap().minted, 1);
+
+        env.set_caller(env.get_account(0));
+        licenses.revoke_minter(pack_store);
+        assert!(licenses.get_minters().iter().all(|info| info.minter != pack_store));
+        assert_eq!(
+            try_mint_as(&env, &mut licenses, pack_store, LicenseType::Commercial, buyer),
+            Err(Error::Unauthorized.into())
+        );
+    }
+
+    // --- Suspension and revocation ---
+
+    #[test]
+    fn suspended_and_revoked_licenses_are_invalid() {
+        let (env, mut licenses) = setup();
+        let moderator = env.get_account(0);
+        let buyer = env.get_account(3);
+        let commercial = LicenseType::Commercial.to_u8();
+        let license_id = mint(&env, &mut licenses, LicenseType::Commercial, buyer, &[]);
+
+        env.set_caller(buyer);
+        assert_eq!(licenses.try_request_suspension(license_id, 1), Err(Error::Unauthorized.into()));
+        assert_eq!(licenses.try_suspend_license(license_id, 1), Err(Error::Unauthorized.into()));
+        env.set_caller(env.get_account(2));
+        licenses.request_suspension(license_id, 1);
+        assert!(licenses.has_license(buyer, SAMPLE_ID, commercial));
+
+        env.set_caller(moderator);
+        licenses.suspend_license(license_id, 1);
+        assert_eq!(licenses.get_license_status(license_id), Some(LicenseStatus::Suspended.to_u8()));
+        assert!(!licenses.has_license(buyer, SAMPLE_ID, commercial));
+        licenses.reinstate_license(license_id, 0);
+        assert!(licenses.has_license(buyer, SAMPLE_ID, commercial));
+
+        licenses.revoke_license(license_id, 2);
+        assert!(!licenses.is_license_valid(license_id));
+        assert!(!licenses.has_license(buyer, SAMPLE_ID, commercial));
+        assert_eq!(
+            licenses.try_reinstate_license(license_id, 0),
+            Err(Error::InvalidLicenseStatusChange.into())
+        );
+        assert_eq!(licenses.try_revoke_license(license_id, 2), Err(Error::LicenseRevoked.into()));
+
+        // The holder lost the license type, so it can be licensed to them again
+        mint(&env, &mut licenses, LicenseType::Commercial, buyer, &[]);
+        assert!(licenses.has_license(buyer, SAMPLE_ID, commercial));
+    }
+
+    // --- Usage verification ---
+
+    #[test]
+    fn verify_usage_checks_the_rights_matrix() {
+        let (env, mut licenses) = setup();
+        let buyer = env.get_account(3);
+        let streaming = UsageType::Streaming as u8;
+        let broadcast = UsageType::Broadcast as u8;
+        let license_id = mint(&env, &mut licenses, LicenseType::Personal, buyer, &[]);
+
+        let result = licenses.verify_usage(buyer, SAMPLE_ID, streaming, None);
+        assert!(result.is_permitted);
+        assert_eq!(result.license_id, Some(license_id));
+        assert!(result.attribution_required);
+        assert_eq!(result.max_units, Some(DEFAULT_PERSONAL_STREAM_CAP));
+        assert!(matches!(
+            licenses.verify_usage(buyer, SAMPLE_ID, broadcast, None).status,
+            VerificationStatus::NotPermitted
+        ));
+        assert!(matches!(
+            licenses.verify_usage(env.get_account(4), SAMPLE_ID, streaming, None).status,
+            VerificationStatus::NoLicense
+        ));
+        assert_eq!(
+            licenses.try_verify_usage(buyer, SAMPLE_ID, 9, None),
+            Err(Error::InvalidUsageType.into())
+        );
+
+        env.set_caller(env.get_account(0));
+        licenses.suspend_license(license_id, 1);
+        let result = licenses.verify_usage(buyer, SAMPLE_ID, streaming, None);
+        assert!(!result.is_permitted);
+        assert!(matches!(result.status, VerificationStatus::Inactive));
+    }
+
+    #[test]
+    fn timed_licenses_expire() {
+        let (env, mut licenses) = setup();
+        let buyer = env.get_account(3);
+        env.set_caller(env.get_account(0));
+        licenses.set_usage_rights(
+            LicenseType::Broadcast.to_u8(),
+            UsageRights {
+                duration: Some(1_000),
+                ..LicenseType::Broadcast.default_usage_rights()
+            },
+        );
+        mint(&env, &mut licenses, LicenseType::Broadcast, buyer, &[]);
+        let broadcast = UsageType::Broadcast as u8;
+        assert!(licenses.verify_usage(buyer, SAMPLE_ID, broadcast, None).is_permitted);
+
+        env.advance_block_time(1_001);
+        assert!(matches!(
+            licenses.verify_usage(buyer, SAMPLE_ID, broadcast, None).status,
+            VerificationStatus::Expired
+        ));
+        assert!(!licenses.has_license(buyer, SAMPLE_ID, LicenseType::Broadcast.to_u8()));
+    }
+
+    #[test]
+    fn timed_exclusive_lapses_on_expiry() {
+        let (env, mut licenses) = setup();
+        let first_holder = env.get_account(3);
+        let second_holder = env.get_account(4);
+
+        env.set_caller(env.get_account(0));
+        licenses.set_usage_rights(
+            LicenseType::Exclusive.to_u8(),
+            UsageRights {
+                duration: Some(1_000),
+                ..LicenseType::Exclusive.default_usage_rights()
+            },
+        );
+
+        mint(&env, &mut licenses, LicenseType::Exclusive, first_holder, &[]);
+        assert!(licenses.is_exclusively_licensed(SAMPLE_ID));
+        assert_eq!(licenses.get_exclusive_holder(SAMPLE_ID), Some(first_holder));
+        assert_eq!(licenses.get_sample_license_info(SAMPLE_ID).exclusive_holder, Some(first_holder));
+        assert_eq!(
+            try_mint(&env, &mut licenses, LicenseType::Exclusive, second_holder, &[]),
+            Err(Error::SampleExclusivelyLicensed.into())
+        );
+
+        env.advance_block_time(1_001);
+        assert!(!licenses.is_exclusively_licensed(SAMPLE_ID));
+        assert_eq!(licenses.get_exclusive_holder(SAMPLE_ID), None);
+        assert!(!licenses.get_sample_license_info(SAMPLE_ID).has_exclusive);
+        mint(&env, &mut licenses, LicenseType::Exclusive, second_holder, &[]);
+        assert_eq!(licenses.get_exclusive_holder(SAMPLE_ID), Some(second_holder));
+        assert_eq!(licenses.get_sample_license_info(SAMPLE_ID).exclusive_holder, Some(second_holder));
+    }
+
+    #[test]
+    fn revoked_exclusive_is_not_reported_in_sample_info() {
+        let (env, mut licenses) = setup();
+        let holder = env.get_account(3);
+        let license_id = mint(&env, &mut licenses, LicenseType::Exclusive, holder, &[]);
+        let info = licenses.get_sample_license_info(SAMPLE_ID);
+        assert!(info.has_exclusive);
+        assert_eq!(info.exclusive_holder, Some(holder));
+
+        env.set_caller(env.get_account(0));
+        licenses.revoke_license(license_id, 2);
+        let info = licenses.get_sample_license_info(SAMPLE_ID);
+        assert!(!info.has_exclusive);
+        assert_eq!(info.exclusive_holder, None);
+        let exclusive_supply = info.tiers.iter()
+            .find(|tier| tier.tier_id == LicenseType::Exclusive.to_u8())
+            .unwrap();
+        assert_eq!(exclusive_supply.remaining, Some(1));
+    }
+
+    // --- Releases ---
+
+    #[test]
+    fn commercial_license_owners_register_releases() {
+        let (env, mut licenses) = setup();
+        let artist = env.get_account(3);
+        let license_id = mint(&env, &mut licenses, LicenseType::Commercial, artist, &[]);
+        let personal_id = mint(&env, &mut licenses, LicenseType::Personal, artist, &[]);
+        let title = String::from("Night Drive");
+
+        env.set_caller(env.get_account(4));
+        assert_eq!(
+            licenses.try_register_release(license_id, String::from("USRC17607839"), title.clone(), 0),
+            Err(Error::NotLicenseOwner.into())
+        );
+        env.set_caller(artist);
+        assert_eq!(
+            licenses.try_register_release(personal_id, String::from("USRC17607839"), title.clone(), 0),
+            Err(Error::ReleaseNotPermitted.into())
+        );
+        assert_eq!(
+            licenses.try_register_release(license_id, String::from("US-RC1-76-07"), title.clone(), 0),
+            Err(Error::InvalidIsrc.into())
+        );
+
+        let release_id = licenses.register_release(license_id, String::from("usrc17607839"), title.clone(), 0);
+        let release = licenses.get_release(release_id).unwrap();
+        assert_eq!(release.isrc, "USRC17607839");
+        assert_eq!(release.owner, artist);
+        assert_eq!(licenses.get_release_by_isrc(String::from("usrc17607839")).unwrap().release_id, release_id);
+        assert_eq!(licenses.get_releases_by_license(license_id).len(), 1);
+        assert_eq!(licenses.get_releases_by_sample(SAMPLE_ID)[0].title, title);
+        assert_eq!(
+            licenses.try_register_release(license_id, String::from("USRC17607839"), title, 0),
+            Err(Error::IsrcAlreadyRegistered.into())
+        );
+    }
+
+    #[test]
+    fn suspended_licenses_cannot_register_releases() {
+        let (env, mut licenses) = setup();
+        let artist = env.get_account(3);
+        let license_id = mint(&env, &mut licenses, LicenseType::Commercial, artist, &[]);
+        env.set_caller(env.get_account(0));
+        licenses.suspend_license(license_id, 1);
+
+        env.set_caller(artist);
+        assert_eq!(
+            licenses.try_register_release(license_id, String::from("USRC17607839"), String::from("Night Drive"), 0),
+            Err(Error::LicenseInactive.into())
+        );
+    }
+
+    // --- Sublicenses ---
+
+    #[test]
+    fn sublicense_follows_parent_status() {
+        let (env, mut licenses) = setup();
+        let admin = env.get_account(0);
+        let holder = env.get_account(3);
+        let recipient = env.get_account(4);
+        let parent_id = mint(&env, &mut licenses, LicenseType::Commercial, holder, &[]);
+
+        env.set_caller(holder);
+        let scope = UsageRights {
+            commercial_distribution: false,
+            ..LicenseType::Commercial.default_usage_rights()
+        };
+        let child_id = licenses.issue_sublicense(parent_id, recipient, scope, None);
+        assert!(licenses.is_license_valid(child_id));
+
+        // Suspending the parent suspends the sublicense's usability, reinstating restores it
+        env.set_caller(admin);
+        licenses.suspend_license(parent_id, 0);
+        assert!(!licenses.is_license_valid(child_id));
+        let usage = licenses.verify_usage(recipient, SAMPLE_ID, UsageType::Streaming as u8, None);
+        assert!(!usage.is_permitted);
+        assert_eq!(usage.license_id, Some(child_id));
+
+        licenses.reinstate_license(parent_id, 0);
+        assert!(licenses.is_license_valid(child_id));
+
+        // Revoking the parent leaves the child's own status, but it can no longer be used
+        licenses.revoke_license(parent_id, 0);
+        assert_eq!(licenses.get_license_status(child_id), Some(LicenseStatus::Active.to_u8()));
+        assert!(!licenses.is_license_valid(child_id));
+        env.set_caller(recipient);
+        assert_eq!(
+            licenses.try_transfer_license(child_id, holder, U512::zero()),
+            Err(Error::LicenseInactive.into())
+        );
+    }
+
+    #[test]
+    fn sublicense_scope_cannot_exceed_parent() {
+        let (env, mut licenses) = setup();
+        let holder = env.get_account(3);
+        let parent_id = mint(&env, &mut licenses, LicenseType::Commercial, holder, &[]);
+
+        env.set_caller(holder);
+        assert_eq!(
+            licenses.try_issue_sublicense(
+                parent_id,
+                env.get_account(4),
+                LicenseType::Broadcast.default_usage_rights(),
+                None,
+            ),
+            Err(Error::InvalidSublicenseScope.into())
+        );
+    }
+
+    // --- Territorial exclusivity ---
+
+    #[test]
+    fn territorial_exclusive_blocks_only_its_territories() {
+        let (env, mut licenses) = setup();
+        let admin = env.get_account(0);
+        let exclusive_holder = env.get_account(3);
+        let buyer = env.get_account(4);
+        let exclusive_id = mint(&env, &mut licenses, LicenseType::Exclusive, exclusive_holder, &["US"]);
+
+        assert_eq!(
+            licenses.get_territory_exclusive_holder(SAMPLE_ID, String::from("US")),
+            Some(exclusive_holder)
+        );
+        assert!(!licenses.is_exclusively_licensed(SAMPLE_ID));
+        assert!(licenses.is_territory_blocked(SAMPLE_ID, vec![String::from("US")]));
+        assert!(licenses.is_territory_blocked(SAMPLE_ID, Vec::new()));
+        assert!(!licenses.is_territory_blocked(SAMPLE_ID, vec![String::from("DE")]));
+
+        assert_eq!(
+            try_mint(&env, &mut licenses, LicenseType::Personal, buyer, &["US"]),
+            Err(Error::SampleExclusivelyLicensed.into())
+        );
+        assert_eq!(
+            try_mint(&env, &mut licenses, LicenseType::Personal, buyer, &[]),
+            Err(Error::SampleExclusivelyLicensed.into())
+        );
+        mint(&env, &mut licenses, LicenseType::Personal, buyer, &["DE"]);
+
+        // Revoking the exclusive license frees the territory
+        env.set_caller(admin);
+        licenses.revoke_license(exclusive_id, 0);
+        assert!(!licenses.is_territory_blocked(SAMPLE_ID, vec![String::from("US")]));
+        mint(&env, &mut licenses, LicenseType::Commercial, buyer, &["US"]);
+    }
+}
@@ -0,0 +1,186 @@
+//! License Pricing Storage for Sampled Marketplace
+//!
+//! Groups the license pricing configuration so the marketplace
+//! module only holds a single field for it:
+//! - Multiplier-based pricing (percentages of the sample base price)
+//! - Absolute per-tier prices set by the seller
+//! - The pricing mode currently active for each sample
+//! - Seller-level default pricing and named pricing templates
+//! - Per-territory multipliers for territory-restricted licenses
+
+use odra::prelude::*;
+use odra::casper_types::U512;
+
+use crate::license_types::{LicenseTier, LicensePricing, AllLicensePrices, PricingMode, TerritoryPrice};
+
+/// Per-sample and per-seller license pricing storage
+#[odra::module]
+pub struct LicensePricingStore {
+    /// Custom license pricing per sample: sample_id -> LicensePricing (None = seller default)
+    sample_license_pricing: Mapping<u64, Option<LicensePricing>>,
+    /// Active pricing mode per sample: sample_id -> PricingMode
+    sample_pricing_mode: Mapping<u64, PricingMode>,
+    /// Absolute license prices per sample: sample_id -> AllLicensePrices
+    sample_absolute_prices: Mapping<u64, AllLicensePrices>,
+
+    /// Seller's default pricing: seller -> LicensePricing
+    seller_default_pricing: Mapping<Address, LicensePricing>,
+    /// Seller's named pricing templates: (seller, name) -> LicensePricing
+    seller_templates: Mapping<(Address, String), LicensePricing>,
+    /// Count of templates created by each seller
+    seller_template_count: Mapping<Address, u64>,
+    /// Indexed template names: (seller, index) -> name
+    seller_template_at: Mapping<(Address, u64), String>,
+
+    /// Territory multipliers per sample: (sample_id, territory) -> multiplier (0 = not offered)
+    territory_multipliers: Mapping<(u64, String), u64>,
+    /// Count of territories ever priced per sample
+    sample_territory_count: Mapping<u64, u64>,
+    /// Indexed territories: (sample_id, index) -> territory
+    sample_territory_at: Mapping<(u64, u64), String>,
+}
+
+impl LicensePricingStore {
+    /// Store custom multipliers for a sample and switch it to multiplier mode
+    pub fn set_multipliers(&mut self, sample_id: u64, pricing: LicensePricing) {
+        self.sample_license_pricing.set(&sample_id, Some(pricing));
+        self.sample_pricing_mode.set(&sample_id, PricingMode::Multiplier);
+    }
+
+    /// Drop a sample's custom multipliers so it follows the seller's default pricing
+    pub fn clear_multipliers(&mut self, sample_id: u64) {
+        self.sample_license_pricing.set(&sample_id, None);
+        self.sample_pricing_mode.set(&sample_id, PricingMode::Multiplier);
+    }
+
+    /// Store absolute prices for a sample and switch it to absolute mode
+    pub fn set_absolute_prices(&mut self, sample_id: u64, prices: AllLicensePrices) {
+        self.sample_absolute_prices.set(&sample_id, prices);
+        self.sample_pricing_mode.set(&sample_id, PricingMode::Absolute);
+    }
+
+    /// Store the seller's default pricing
+    pub fn set_seller_default(&mut self, seller: Address, pricing: LicensePricing) {
+        self.seller_default_pricing.set(&seller, pricing);
+    }
+
+    /// Create or update a named pricing template for a seller
+    pub fn set_template(&mut self, seller: Address, name: String, pricing: LicensePricing) {
+        let key = (seller, name.clone());
+        if self.seller_templates.get(&key).is_none() {
+            let count = self.seller_template_count.get_or_default(&seller);
+            self.seller_template_at.set(&(seller, count), name);
+            self.seller_template_count.set(&seller, count + 1);
+        }
+        self.seller_templates.set(&key, pricing);
+    }
+
+    /// Set the multiplier for a territory of a sample (0 stops offering it)
+    pub fn set_territory_multiplier(&mut self, sample_id: u64, territory: String, multiplier: u64) {
+        let key = (sample_id, territory.clone());
+        if self.territory_multipliers.get(&key).is_none() {
+            let count = self.sample_territory_count.get_or_default(&sample_id);
+            self.sample_territory_at.set(&(sample_id, count), territory);
+            self.sample_territory_count.set(&sample_id, count + 1);
+        }
+        self.territory_multipliers.set(&key, multiplier);
+    }
+
+    /// Get the multiplier for a territory of a sample (None if not offered)
+    pub fn territory_multiplier(&self, sample_id: u64, territory: &str) -> Option<u64> {
+        self.territory_multipliers.get(&(sample_id, String::from(territory)))
+            .filter(|multiplier| *multiplier > 0)
+    }
+
+    /// Get every territory offered for a sample with its multiplier
+    pub fn territory_prices(&self, sample_id: u64) -> Vec<TerritoryPrice> {
+        let count = self.sample_territory_count.get_or_default(&sample_id);
+        let mut result = Vec::new();
+        for i in 0..count {
+            if let Some(territory) = self.sample_territory_at.get(&(sample_id, i)) {
+                if let Some(multiplier) = self.territory_multiplier(sample_id, &territory) {
+                    result.push(TerritoryPrice { territory, multiplier });
+                }
+            }
+        }
+        result
+    }
+
+    /// Price of a license restricted to the given territories:
+    /// the sum of the worldwide price scaled by each territory's multiplier
+    /// Returns None if any territory is not offered
+    pub fn territorial_price(&self, sample_id: u64, worldwide_price: U512, territories: &[String]) -> Option<U512> {
+        let mut total = U512::zero();
+        for territory in territories {
+            let multiplier = self.territory_multiplier(sample_id, territory)?;
+            total += worldwide_price * multiplier / 100;
+        }
+        Some(total)
+    }
+
+    /// Get the seller's default pricing (if any was set)
+    pub fn seller_default(&self, seller: &Address) -> Option<LicensePricing> {
+        self.seller_default_pricing.get(seller)
+    }
+
+    /// Get a seller's named pricing template
+    pub fn template(&self, seller: &Address, name: &str) -> Option<LicensePricing> {
+        self.seller_templates.get(&(*seller, String::from(name)))
+    }
+
+    /// Get the names of all templates created by a seller
+    pub fn template_names(&self, seller: &Address) -> Vec<String> {
+        let count = self.seller_template_count.get_or_default(seller);
+        let mut result = Vec::new();
+        for i in 0..count {
+            if let Some(name) = self.seller_template_at.get(&(*seller, i)) {
+                result.push(name);
+            }
+        }
+        result
+    }
+
+    /// Get the custom multipliers for a sample (if any were set)
+    pub fn multipliers(&self, sample_id: u64) -> Option<LicensePricing> {
+        self.sample_license_pricing.get(&sample_id).flatten()
+    }
+
+    /// Get the active pricing mode for a sample
+    pub fn mode(&self, sample_id: u64) -> PricingMode {
+        self.sample_pricing_mode.get_or_default(&sample_id)
+    }
+
+    /// Get the absolute prices for a sample (if any were set)
+    pub fn absolute_prices(&self, sample_id: u64) -> Option<AllLicensePrices> {
+        self.sample_absolute_prices.get(&sample_id)
+    }
+
+    /// Get the multipliers in effect for a sample
+    /// Falls back to the seller's default, then to the platform default
+    pub fn effective_multipliers(&self, sample_id: u64, seller: &Address) -> LicensePricing {
+        self.multipliers(sample_id)
+            .or_else(|| self.seller_default(seller))
+            .unwrap_or_else(LicensePricing::default_pricing)
+    }
+
+    /// Calculate the license price based on the sample's pricing mode and tier
+    /// Absolute prices only cover the built-in tiers; custom tiers always use multipliers
+    pub fn calculate_price(
+        &self,
+        sample_id: u64,
+        seller: &Address,
+        tier: &LicenseTier,
+        base_price: U512,
+    ) -> U512 {
+        if let PricingMode::Absolute = self.mode(sample_id) {
+            if let Some(price) = self.absolute_prices(sample_id)
+                .and_then(|prices| prices.get_price(tier.tier_id))
+            {
+                return price;
+            }
+        }
+
+        self.effective_multipliers(sample_id, seller)
+            .calculate_price(base_price, tier)
+    }
+}
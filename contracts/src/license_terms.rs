@@ -0,0 +1,48 @@
+//! License Terms Storage for the License NFT contract
+//!
+//! Content hashes (or IPFS CIDs) of the license agreement documents
+//! attached to each license tier:
+//! - Per-sample terms set by the seller
+//! - Platform default terms per license type, used when a sample has none
+
+use odra::prelude::*;
+
+/// Terms document hashes per license tier
+#[odra::module]
+pub struct LicenseTerms {
+    /// Seller terms: (sample_id, license_type) -> terms hash
+    sample_terms: Mapping<(u64, u8), String>,
+    /// Platform default terms: license_type -> terms hash
+    default_terms: Mapping<u8, String>,
+}
+
+impl LicenseTerms {
+    /// Set or clear the seller's terms for a sample's license tier
+    pub fn set_sample_terms(&mut self, sample_id: u64, license_type: u8, terms_hash: Option<String>) {
+        self.sample_terms.set(&(sample_id, license_type), terms_hash.unwrap_or_default());
+    }
+
+    /// Set or clear the platform default terms for a license type
+    pub fn set_default_terms(&mut self, license_type: u8, terms_hash: Option<String>) {
+        self.default_terms.set(&license_type, terms_hash.unwrap_or_default());
+    }
+
+    /// Get the seller's terms for a sample's license tier (if any were set)
+    pub fn sample_terms(&self, sample_id: u64, license_type: u8) -> Option<String> {
+        self.sample_terms.get(&(sample_id, license_type))
+            .filter(|hash| !hash.is_empty())
+    }
+
+    /// Get the platform default terms for a license type (if any were set)
+    pub fn default_terms(&self, license_type: u8) -> Option<String> {
+        self.default_terms.get(&license_type)
+            .filter(|hash| !hash.is_empty())
+    }
+
+    /// Get the terms in force for a sample's license tier
+    /// Falls back to the platform default when the seller has set none
+    pub fn terms_in_force(&self, sample_id: u64, license_type: u8) -> Option<String> {
+        self.sample_terms(sample_id, license_type)
+            .or_else(|| self.default_terms(license_type))
+    }
+}
@@ -6,7 +6,8 @@
 use odra::prelude::*;
 use odra::casper_types::U512;
 
-/// License types representing different usage rights
+/// Built-in license types representing different usage rights
+/// These are the default entries (IDs 0-3) of the license tier registry
 #[odra::odra_type]
 #[derive(Default, Copy)]
 pub enum LicenseType {
@@ -42,6 +43,343 @@ impl LicenseType {
             LicenseType::Exclusive => 3,
         }
     }
+
+    /// All license types, from least to most permissive
+    pub fn all() -> [LicenseType; 4] {
+        [
+            LicenseType::Personal,
+            LicenseType::Commercial,
+            LicenseType::Broadcast,
+            LicenseType::Exclusive,
+        ]
+    }
+
+    /// Default registry entry for this license type
+    pub fn default_tier(&self) -> LicenseTier {
+        let (name, default_multiplier) = match self {
+            LicenseType::Personal => ("Personal", constants::DEFAULT_PERSONAL_MULT),
+            LicenseType::Commercial => ("Commercial", constants::DEFAULT_COMMERCIAL_MULT),
+            LicenseType::Broadcast => ("Broadcast", constants::DEFAULT_BROADCAST_MULT),
+            LicenseType::Exclusive => ("Exclusive", constants::DEFAULT_EXCLUSIVE_MULT),
+        };
+        let is_exclusive = matches!(self, LicenseType::Exclusive);
+        LicenseTier {
+            tier_id: self.to_u8(),
+            name: String::from(name),
+            rights: self.default_usage_rights(),
+            default_multiplier,
+            is_transferable: !is_exclusive,
+            is_exclusive,
+            is_active: true,
+        }
+    }
+
+    /// Default usage rights granted by this license type
+    pub fn default_usage_rights(&self) -> UsageRights {
+        match self {
+            LicenseType::Personal => UsageRights {
+                streaming: true,
+                sync: false,
+                broadcast: false,
+                commercial_distribution: false,
+                attribution_required: true,
+                max_units: Some(constants::DEFAULT_PERSONAL_STREAM_CAP),
+                duration: None,
+            },
+            LicenseType::Commercial => UsageRights {
+                streaming: true,
+                sync: false,
+                broadcast: false,
+                commercial_distribution: true,
+                attribution_required: true,
+                max_units: None,
+                duration: None,
+            },
+            LicenseType::Broadcast => UsageRights {
+                streaming: true,
+                sync: true,
+                broadcast: true,
+                commercial_distribution: true,
+                attribution_required: true,
+                max_units: None,
+                duration: None,
+            },
+            LicenseType::Exclusive => UsageRights {
+                streaming: true,
+                sync: true,
+                broadcast: true,
+                commercial_distribution: true,
+                attribution_required: false,
+                max_units: None,
+                duration: None,
+            },
+        }
+    }
+}
+
+/// A license tier in the registry (built-in or admin-registered)
+#[odra::odra_type]
+pub struct LicenseTier {
+    /// Tier ID (0=Personal, 1=Commercial, 2=Broadcast, 3=Exclusive, 4+ custom)
+    pub tier_id: u8,
+    /// Display name (e.g. "Sync-only", "Non-profit")
+    pub name: String,
+    /// Usage rights granted by licenses of this tier
+    pub rights: UsageRights,
+    /// Price multiplier used unless the seller sets one (100 = 1x base price)
+    pub default_multiplier: u64,
+    /// Whether licenses of this tier can be resold
+    pub is_transferable: bool,
+    /// Whether a license of this tier takes the sample off the marketplace (one per sample)
+    pub is_exclusive: bool,
+    /// Whether new licenses of this tier can be sold
+    pub is_active: bool,
+}
+
+impl LicenseTier {
+    /// Check if the tier grants anything beyond streaming (and so can be sublicensed)
+    pub fn is_sublicensable(&self) -> bool {
+        self.rights.sync || self.rights.broadcast || self.rights.commercial_distribution
+    }
+}
+
+/// Seller multiplier for a custom tier
+#[odra::odra_type]
+pub struct TierMultiplier {
+    /// Tier ID
+    pub tier_id: u8,
+    /// Multiplier (100 = 1x base price)
+    pub multiplier: u64,
+}
+
+/// Issued and remaining licenses of one tier for a sample
+#[odra::odra_type]
+pub struct TierSupply {
+    /// Tier ID
+    pub tier_id: u8,
+    /// Number of licenses issued
+    pub issued: u64,
+    /// Licenses still available (None = unlimited)
+    pub remaining: Option<u64>,
+}
+
+/// Price of one tier for a sample (for view function return)
+#[odra::odra_type]
+pub struct TierPrice {
+    /// Tier ID
+    pub tier_id: u8,
+    /// Tier name
+    pub name: String,
+    /// License price
+    pub price: U512,
+}
+
+/// Kinds of usage a third party can verify a license for
+#[odra::odra_type]
+#[derive(Default, Copy)]
+pub enum UsageType {
+    /// Streaming on DSPs (Spotify, Apple Music, ...)
+    #[default]
+    Streaming = 0,
+    /// Sync placement in film, TV, games or adverts
+    Sync = 1,
+    /// Radio or TV broadcast
+    Broadcast = 2,
+    /// Sale of physical or digital copies
+    CommercialDistribution = 3,
+}
+
+impl UsageType {
+    /// Convert from u8 to UsageType
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(UsageType::Streaming),
+            1 => Some(UsageType::Sync),
+            2 => Some(UsageType::Broadcast),
+            3 => Some(UsageType::CommercialDistribution),
+            _ => None,
+        }
+    }
+}
+
+/// Rights matrix entry for a license type
+#[odra::odra_type]
+pub struct UsageRights {
+    /// Streaming on DSPs is allowed
+    pub streaming: bool,
+    /// Sync placement is allowed
+    pub sync: bool,
+    /// Radio or TV broadcast is allowed
+    pub broadcast: bool,
+    /// Selling copies is allowed
+    pub commercial_distribution: bool,
+    /// The creator must be credited
+    pub attribution_required: bool,
+    /// Maximum streams or units sold (None = unlimited)
+    /// Reported to verifiers but not enforced: usage is not metered on-chain
+    pub max_units: Option<u64>,
+    /// How long a license lasts after minting, in milliseconds (None = perpetual)
+    pub duration: Option<u64>,
+}
+
+impl UsageRights {
+    /// Check if the rights cover a usage
+    pub fn permits(&self, usage: &UsageType) -> bool {
+        match usage {
+            UsageType::Streaming => self.streaming,
+            UsageType::Sync => self.sync,
+            UsageType::Broadcast => self.broadcast,
+            UsageType::CommercialDistribution => self.commercial_distribution,
+        }
+    }
+
+    /// Check that caps and durations, when set, are greater than zero
+    pub fn is_valid(&self) -> bool {
+        self.max_units != Some(0) && self.duration != Some(0)
+    }
+
+    /// Narrow these rights to a sublicense scope (usages both allow, the lower unit cap)
+    pub fn narrowed_to(&self, scope: &UsageRights) -> UsageRights {
+        let max_units = match (self.max_units, scope.max_units) {
+            (Some(cap), Some(units)) => Some(cap.min(units)),
+            (cap, units) => cap.or(units),
+        };
+        UsageRights {
+            streaming: self.streaming && scope.streaming,
+            sync: self.sync && scope.sync,
+            broadcast: self.broadcast && scope.broadcast,
+            commercial_distribution: self.commercial_distribution && scope.commercial_distribution,
+            attribution_required: self.attribution_required || scope.attribution_required,
+            max_units,
+            duration: self.duration,
+        }
+    }
+
+    /// Check that these rights grant nothing beyond `parent`
+    /// (no extra usages, no higher unit cap, attribution kept if the parent requires it)
+    pub fn is_within(&self, parent: &UsageRights) -> bool {
+        let units_within = match (self.max_units, parent.max_units) {
+            (_, None) => true,
+            (Some(units), Some(cap)) => units <= cap,
+            (None, Some(_)) => false,
+        };
+        (!self.streaming || parent.streaming)
+            && (!self.sync || parent.sync)
+            && (!self.broadcast || parent.broadcast)
+            && (!self.commercial_distribution || parent.commercial_distribution)
+            && (self.attribution_required || !parent.attribution_required)
+            && units_within
+    }
+}
+
+/// Outcome of a usage verification
+#[odra::odra_type]
+#[derive(Default, Copy)]
+pub enum VerificationStatus {
+    /// A valid license covers the usage
+    #[default]
+    Permitted = 0,
+    /// The address holds no license for the sample
+    NoLicense = 1,
+    /// The address holds licenses, but none of their types covers the usage
+    NotPermitted = 2,
+    /// The covering license has expired
+    Expired = 3,
+    /// The covering license is suspended or revoked
+    Inactive = 4,
+    /// The address holds a covering license, but not for the territory
+    OutsideTerritory = 5,
+}
+
+impl VerificationStatus {
+    /// Convert VerificationStatus to u8
+    pub fn to_u8(&self) -> u8 {
+        match self {
+            VerificationStatus::Permitted => 0,
+            VerificationStatus::NoLicense => 1,
+            VerificationStatus::NotPermitted => 2,
+            VerificationStatus::Expired => 3,
+            VerificationStatus::Inactive => 4,
+            VerificationStatus::OutsideTerritory => 5,
+        }
+    }
+
+    /// How close the outcome is to a permit, used to report the most relevant license
+    pub fn closeness(&self) -> u8 {
+        match self {
+            VerificationStatus::NoLicense => 0,
+            VerificationStatus::NotPermitted => 1,
+            VerificationStatus::OutsideTerritory => 2,
+            VerificationStatus::Expired => 3,
+            VerificationStatus::Inactive => 4,
+            VerificationStatus::Permitted => 5,
+        }
+    }
+}
+
+/// Result of `verify_usage` (for view function return)
+#[odra::odra_type]
+#[derive(Default)]
+pub struct UsageVerification {
+    /// Whether the usage is permitted
+    pub is_permitted: bool,
+    /// Why the usage is or is not permitted
+    pub status: VerificationStatus,
+    /// License the result is based on (None if no license is held)
+    pub license_id: Option<u64>,
+    /// Type of that license
+    pub license_type: Option<u8>,
+    /// Whether the creator must be credited
+    pub attribution_required: bool,
+    /// Maximum streams or units sold (None = unlimited); the verifier enforces it off-chain
+    pub max_units: Option<u64>,
+    /// When the license expires (None = perpetual)
+    pub expires_at: Option<u64>,
+}
+
+/// How license prices are derived for a sample
+#[odra::odra_type]
+#[derive(Default, Copy)]
+pub enum PricingMode {
+    /// Percentage multipliers of the sample base price
+    #[default]
+    Multiplier = 0,
+    /// Fixed absolute price per license type, set by the seller
+    Absolute = 1,
+}
+
+impl PricingMode {
+    /// Convert PricingMode to u8
+    pub fn to_u8(&self) -> u8 {
+        match self {
+            PricingMode::Multiplier => 0,
+            PricingMode::Absolute => 1,
+        }
+    }
+}
+
+/// Lifecycle status of a license NFT
+#[odra::odra_type]
+#[derive(Default, Copy)]
+pub enum LicenseStatus {
+    /// License is valid
+    #[default]
+    Active = 0,
+    /// License is temporarily invalid pending review
+    Suspended = 1,
+    /// License has been permanently revoked
+    Revoked = 2,
+}
+
+impl LicenseStatus {
+    /// Convert LicenseStatus to u8
+    pub fn to_u8(&self) -> u8 {
+        match self {
+            LicenseStatus::Active => 0,
+            LicenseStatus::Suspended => 1,
+            LicenseStatus::Revoked => 2,
+        }
+    }
 }
 
 /// Pricing multipliers for each license type
@@ -57,6 +395,8 @@ pub struct LicensePricing {
     pub broadcast_multiplier: u64,
     /// Multiplier for exclusive license (default: 2000 = 20x)
     pub exclusive_multiplier: u64,
+    /// Multipliers for custom tiers (tiers not listed use their default multiplier)
+    pub tier_multipliers: Vec<TierMultiplier>,
 }
 
 impl LicensePricing {
@@ -67,22 +407,45 @@ impl LicensePricing {
             commercial_multiplier: 250,  // 2.5x
             broadcast_multiplier: 500,   // 5x
             exclusive_multiplier: 2000,  // 20x
+            tier_multipliers: Vec::new(),
         }
     }
 
-    /// Get the multiplier for a specific license type
-    pub fn get_multiplier(&self, license_type: &LicenseType) -> u64 {
-        match license_type {
-            LicenseType::Personal => self.personal_multiplier,
-            LicenseType::Commercial => self.commercial_multiplier,
-            LicenseType::Broadcast => self.broadcast_multiplier,
-            LicenseType::Exclusive => self.exclusive_multiplier,
+    /// Check that every multiplier is greater than zero
+    pub fn is_valid(&self) -> bool {
+        self.personal_multiplier > 0
+            && self.commercial_multiplier > 0
+            && self.broadcast_multiplier > 0
+            && self.exclusive_multiplier > 0
+            && self.tier_multipliers.iter().all(|entry| entry.multiplier > 0)
+    }
+
+    /// Get the multiplier for a tier
+    /// Built-in tiers use their fields; custom tiers their entry, else the tier's default
+    pub fn get_multiplier(&self, tier: &LicenseTier) -> u64 {
+        match LicenseType::from_u8(tier.tier_id) {
+            Some(LicenseType::Personal) => self.personal_multiplier,
+            Some(LicenseType::Commercial) => self.commercial_multiplier,
+            Some(LicenseType::Broadcast) => self.broadcast_multiplier,
+            Some(LicenseType::Exclusive) => self.exclusive_multiplier,
+            None => self.tier_multipliers.iter()
+                .find(|entry| entry.tier_id == tier.tier_id)
+                .map(|entry| entry.multiplier)
+                .unwrap_or(tier.default_multiplier),
+        }
+    }
+
+    /// Set the multiplier for a custom tier
+    pub fn set_tier_multiplier(&mut self, tier_id: u8, multiplier: u64) {
+        match self.tier_multipliers.iter_mut().find(|entry| entry.tier_id == tier_id) {
+            Some(entry) => entry.multiplier = multiplier,
+            None => self.tier_multipliers.push(TierMultiplier { tier_id, multiplier }),
         }
     }
 
-    /// Calculate the price for a license type given the base price
-    pub fn calculate_price(&self, base_price: U512, license_type: &LicenseType) -> U512 {
-        let multiplier = self.get_multiplier(license_type);
+    /// Calculate the price for a tier given the base price
+    pub fn calculate_price(&self, base_price: U512, tier: &LicenseTier) -> U512 {
+        let multiplier = self.get_multiplier(tier);
         base_price * multiplier / 100
     }
 }
@@ -94,20 +457,116 @@ pub struct LicenseMetadata {
     pub license_id: u64,
     /// ID of the sample this license is for
     pub sample_id: u64,
-    /// Type of license (Personal, Commercial, Broadcast, Exclusive)
-    pub license_type: LicenseType,
+    /// License tier ID (0=Personal, 1=Commercial, 2=Broadcast, 3=Exclusive, 4+ custom)
+    pub license_type: u8,
     /// Address of the original sample creator (receives royalties on resale)
     pub original_creator: Address,
     /// Current owner of this license NFT
     pub current_owner: Address,
-    /// Price paid when this license was first purchased
+    /// Price paid when this license was first purchased, in `currency`
     pub purchase_price: U512,
+    /// Currency of the purchase price (None = CSPR motes, Some = CEP-18 token contract)
+    pub currency: Option<Address>,
     /// Timestamp when the license was purchased
     pub purchase_timestamp: u64,
-    /// Whether this license is active (can be transferred)
+    /// Whether this license is active (can be transferred); false while suspended or revoked
     pub is_active: bool,
     /// Number of times this license has been transferred
     pub transfer_count: u64,
+    /// Whether this license was granted for free by the creator (not purchased)
+    pub is_promotional: bool,
+    /// Current status (Active, Suspended or Revoked)
+    pub status: LicenseStatus,
+    /// Reason code given for the last suspension, reinstatement or revocation
+    pub status_reason: Option<u8>,
+    /// Whether the creator has asked moderators to suspend the license (awaiting review)
+    pub suspension_requested: bool,
+    /// When the license expires, from the type's usage rights at mint (None = perpetual)
+    pub expires_at: Option<u64>,
+    /// Hash or CID of the license agreement accepted at mint (None if no terms were in force)
+    pub terms_hash: Option<String>,
+    /// License this sublicense was issued under (None for licenses minted by a minter)
+    pub parent_license_id: Option<u64>,
+    /// ISO 3166-1 alpha-2 codes of the territories covered (empty = worldwide)
+    pub territories: Vec<String>,
+}
+
+impl LicenseMetadata {
+    /// Check if the license has expired
+    pub fn is_expired(&self, now: u64) -> bool {
+        self.expires_at.is_some_and(|expires_at| now >= expires_at)
+    }
+
+    /// Check if the license covers a territory
+    /// Only worldwide licenses cover an unspecified territory (None)
+    pub fn covers_territory(&self, territory: &Option<String>) -> bool {
+        match territory {
+            _ if self.territories.is_empty() => true,
+            Some(territory) => self.territories.contains(territory),
+            None => false,
+        }
+    }
+}
+
+/// Check that a territory code is an ISO 3166-1 alpha-2 code (two uppercase letters)
+pub fn is_valid_territory(territory: &str) -> bool {
+    territory.len() == 2 && territory.bytes().all(|b| b.is_ascii_uppercase())
+}
+
+/// Check that a set of territory codes is valid for a license (no duplicates, within the limit)
+pub fn is_valid_territory_set(territories: &[String]) -> bool {
+    territories.len() <= constants::MAX_LICENSE_TERRITORIES
+        && territories.iter().enumerate().all(|(i, territory)| {
+            is_valid_territory(territory) && !territories[..i].contains(territory)
+        })
+}
+
+/// Exclusive license held on a sample, worldwide or in a territory
+#[odra::odra_type]
+pub struct ExclusiveGrant {
+    /// Exclusive license the grant belongs to
+    pub license_id: u64,
+    /// Holder of the exclusive license
+    pub holder: Address,
+    /// When the exclusive license expires (None = perpetual)
+    pub expires_at: Option<u64>,
+}
+
+impl ExclusiveGrant {
+    /// Check if the grant still blocks other licenses
+    pub fn is_current(&self, now: u64) -> bool {
+        self.expires_at.is_none_or(|expires_at| now < expires_at)
+    }
+}
+
+/// Seller price multiplier for a territory (for view function return)
+#[odra::odra_type]
+pub struct TerritoryPrice {
+    /// ISO 3166-1 alpha-2 territory code
+    pub territory: String,
+    /// Multiplier applied to the worldwide license price (100 = 1x)
+    pub multiplier: u64,
+}
+
+/// A released work (track, album cut, ...) that uses a licensed sample
+#[odra::odra_type]
+pub struct Release {
+    /// Unique identifier for this release record
+    pub release_id: u64,
+    /// License the release was made under
+    pub license_id: u64,
+    /// ID of the sample used in the release
+    pub sample_id: u64,
+    /// License owner who registered the release
+    pub owner: Address,
+    /// International Standard Recording Code (12 characters, no hyphens)
+    pub isrc: String,
+    /// Title of the released work
+    pub title: String,
+    /// Release date (timestamp)
+    pub release_date: u64,
+    /// Timestamp when the release was registered
+    pub registered_at: u64,
 }
 
 /// Record of a royalty payment made during license transfer
@@ -131,6 +590,28 @@ pub struct RoyaltyPayment {
     pub timestamp: u64,
 }
 
+/// A contract authorized to mint licenses
+#[odra::odra_type]
+pub struct MinterInfo {
+    /// Address of the minter contract
+    pub minter: Address,
+    /// Whether the minter is on the allowlist
+    pub is_authorized: bool,
+    /// Whether minting is temporarily paused for this minter
+    pub is_paused: bool,
+    /// Maximum number of licenses this minter may mint (None = unlimited)
+    pub cap: Option<u64>,
+    /// Number of licenses minted so far
+    pub minted: u64,
+}
+
+impl MinterInfo {
+    /// Check if the minter has reached its cap
+    pub fn is_cap_reached(&self) -> bool {
+        self.cap.is_some_and(|cap| self.minted >= cap)
+    }
+}
+
 /// Summary of licenses for a sample
 #[odra::odra_type]
 #[derive(Default)]
@@ -143,10 +624,28 @@ pub struct SampleLicenseInfo {
     pub commercial_count: u64,
     /// Number of broadcast licenses issued
     pub broadcast_count: u64,
-    /// Whether an exclusive license has been issued
+    /// Whether a worldwide exclusive license is in force (not revoked or expired)
     pub has_exclusive: bool,
-    /// Address of exclusive license holder (if any)
+    /// Current owner of that worldwide exclusive license (if any)
     pub exclusive_holder: Option<Address>,
+    /// Remaining personal licenses (None = unlimited)
+    pub personal_remaining: Option<u64>,
+    /// Remaining commercial licenses (None = unlimited)
+    pub commercial_remaining: Option<u64>,
+    /// Remaining broadcast licenses (None = unlimited)
+    pub broadcast_remaining: Option<u64>,
+    /// Issued and remaining licenses for every registered tier
+    pub tiers: Vec<TierSupply>,
+}
+
+impl SampleLicenseInfo {
+    /// Get the number of licenses issued for a tier
+    pub fn issued_count(&self, tier_id: u8) -> u64 {
+        self.tiers.iter()
+            .find(|supply| supply.tier_id == tier_id)
+            .map(|supply| supply.issued)
+            .unwrap_or_default()
+    }
 }
 
 /// All license prices for a sample (for view function return)
@@ -163,6 +662,18 @@ pub struct AllLicensePrices {
     pub exclusive: U512,
 }
 
+impl AllLicensePrices {
+    /// Get the price for a built-in tier (None for custom tiers)
+    pub fn get_price(&self, tier_id: u8) -> Option<U512> {
+        match LicenseType::from_u8(tier_id)? {
+            LicenseType::Personal => Some(self.personal),
+            LicenseType::Commercial => Some(self.commercial),
+            LicenseType::Broadcast => Some(self.broadcast),
+            LicenseType::Exclusive => Some(self.exclusive),
+        }
+    }
+}
+
 /// Constants for license system
 pub mod constants {
     /// Royalty percentage for original creator on resales (10%)
@@ -179,4 +690,16 @@ pub mod constants {
     pub const DEFAULT_EXCLUSIVE_MULT: u64 = 2000;
     /// Multiplier denominator (for calculating prices)
     pub const MULTIPLIER_DENOMINATOR: u64 = 100;
+    /// Default stream cap for personal licenses
+    pub const DEFAULT_PERSONAL_STREAM_CAP: u64 = 10_000;
+    /// Maximum length of a terms document hash or CID
+    pub const MAX_TERMS_HASH_LENGTH: usize = 128;
+    /// Length of an ISRC without hyphens
+    pub const ISRC_LENGTH: usize = 12;
+    /// Maximum length of a release title
+    pub const MAX_RELEASE_TITLE_LENGTH: usize = 100;
+    /// Maximum length of a license tier name
+    pub const MAX_TIER_NAME_LENGTH: usize = 32;
+    /// Maximum number of territories on a territory-restricted license
+    pub const MAX_LICENSE_TERRITORIES: usize = 50;
 }
@@ -0,0 +1,67 @@
+//! Meta-Transaction Storage for Sampled Marketplace
+//!
+//! Lets relayers submit actions signed by users who hold no CSPR for gas:
+//! - Per-user nonces, so each signed action runs once and in order
+//!   (users can skip their current nonce to cancel a signed action before it is relayed)
+//! - An optional allowlist of relayers (any account may relay when disabled)
+
+use odra::prelude::*;
+
+/// Meta-transaction nonces and relayer allowlist
+#[odra::module]
+pub struct MetaTxStore {
+    /// Next nonce expected from each signer: signer -> nonce
+    nonces: Mapping<Address, u64>,
+    /// Whether only allowlisted relayers may submit meta-transactions
+    allowlist_enabled: Var<bool>,
+    /// Allowlisted relayers: relayer -> bool
+    relayers: Mapping<Address, bool>,
+}
+
+impl MetaTxStore {
+    /// Get the next nonce expected from a signer
+    pub fn nonce(&self, signer: &Address) -> u64 {
+        self.nonces.get_or_default(signer)
+    }
+
+    /// Consume a signer's nonce; returns false if it is not the expected one
+    pub fn use_nonce(&mut self, signer: Address, nonce: u64) -> bool {
+        if self.nonce(&signer) != nonce {
+            return false;
+        }
+        self.nonces.set(&signer, nonce + 1);
+        true
+    }
+
+    /// Skip a signer's current nonce and return the next one
+    pub fn bump_nonce(&mut self, signer: Address) -> u64 {
+        let nonce = self.nonce(&signer) + 1;
+        self.nonces.set(&signer, nonce);
+        nonce
+    }
+
+    /// Turn the relayer allowlist on or off
+    pub fn set_allowlist_enabled(&mut self, enabled: bool) {
+        self.allowlist_enabled.set(enabled);
+    }
+
+    /// Check if the relayer allowlist is enforced
+    pub fn is_allowlist_enabled(&self) -> bool {
+        self.allowlist_enabled.get_or_default()
+    }
+
+    /// Add or remove a relayer from the allowlist
+    pub fn set_relayer(&mut self, relayer: Address, allowed: bool) {
+        self.relayers.set(&relayer, allowed);
+    }
+
+    /// Check if an address is on the relayer allowlist
+    pub fn is_allowlisted(&self, relayer: &Address) -> bool {
+        self.relayers.get_or_default(relayer)
+    }
+
+    /// Check if an address may relay (always true while the allowlist is off)
+    pub fn can_relay(&self, relayer: &Address) -> bool {
+        !self.is_allowlist_enabled() || self.is_allowlisted(relayer)
+    }
+}
@@ -0,0 +1,142 @@
+//! Minter Registry for the License NFT contract
+//!
+//! Admin-managed allowlist of contracts authorized to mint licenses
+//! (marketplace, pack store, auction house, promo airdrops, ...).
+//! Each minter has:
+//! - An optional cap on the number of licenses it may mint
+//! - A pause switch that stops only that minter
+//!
+//! One minter is the primary marketplace: it owns the sample IDs, so only it
+//! may set supply caps and terms, or mint exclusive licenses.
+
+use odra::prelude::*;
+
+use crate::errors::Error;
+use crate::license_types::MinterInfo;
+
+/// Allowlist of license minters
+#[odra::module]
+pub struct MinterRegistry {
+    /// Minter data: minter -> MinterInfo
+    minters: Mapping<Address, MinterInfo>,
+    /// Number of minters ever registered
+    minter_count: Var<u64>,
+    /// Indexed minter addresses: index -> minter
+    minter_at: Mapping<u64, Address>,
+    /// Primary marketplace (source of sample IDs and creators)
+    primary: Var<Address>,
+}
+
+impl MinterRegistry {
+    /// Authorize a minter (or re-authorize a revoked one, keeping its minted count)
+    pub fn authorize(&mut self, minter: Address, cap: Option<u64>) -> MinterInfo {
+        let info = match self.minters.get(&minter) {
+            Some(info) => MinterInfo {
+                is_authorized: true,
+                cap,
+                ..info
+            },
+            None => {
+                let count = self.minter_count.get_or_default();
+                self.minter_at.set(&count, minter);
+                self.minter_count.set(count + 1);
+                MinterInfo {
+                    minter,
+                    is_authorized: true,
+                    is_paused: false,
+                    cap,
+                    minted: 0,
+                }
+            }
+        };
+        self.minters.set(&minter, info.clone());
+        info
+    }
+
+    /// Remove a minter from the allowlist
+    pub fn revoke(&mut self, minter: Address) -> MinterInfo {
+        self.update(minter, |info| info.is_authorized = false)
+    }
+
+    /// Pause or resume a single minter
+    pub fn set_paused(&mut self, minter: Address, paused: bool) -> MinterInfo {
+        self.update(minter, |info| info.is_paused = paused)
+    }
+
+    /// Change a minter's cap (None = unlimited)
+    pub fn set_cap(&mut self, minter: Address, cap: Option<u64>) -> MinterInfo {
+        self.update(minter, |info| info.cap = cap)
+    }
+
+    /// Get a minter's data
+    pub fn info(&self, minter: &Address) -> Option<MinterInfo> {
+        self.minters.get(minter)
+    }
+
+    /// Get all minters that are currently authorized
+    pub fn authorized_minters(&self) -> Vec<MinterInfo> {
+        let count = self.minter_count.get_or_default();
+        let mut result = Vec::new();
+        for i in 0..count {
+            if let Some(minter) = self.minter_at.get(&i) {
+                if let Some(info) = self.minters.get(&minter) {
+                    if info.is_authorized {
+                        result.push(info);
+                    }
+                }
+            }
+        }
+        result
+    }
+
+    /// Set the primary marketplace
+    pub fn set_primary(&mut self, marketplace: Address) {
+        self.primary.set(marketplace);
+    }
+
+    /// Get the primary marketplace
+    pub fn primary(&self) -> Option<Address> {
+        self.primary.get()
+    }
+
+    /// Check if an address is the primary marketplace and an active minter
+    pub fn is_active_primary(&self, minter: &Address) -> bool {
+        self.primary.get().is_some_and(|primary| primary == *minter) && self.is_active(minter)
+    }
+
+    /// Check if an address is an authorized, unpaused minter
+    pub fn is_active(&self, minter: &Address) -> bool {
+        self.minters.get(minter)
+            .is_some_and(|info| info.is_authorized && !info.is_paused)
+    }
+
+    /// Revert unless the minter is authorized, unpaused and below its cap
+    pub fn assert_can_mint(&self, minter: &Address) {
+        let info = self.minters.get(minter)
+            .filter(|info| info.is_authorized)
+            .unwrap_or_else(|| self.env().revert(Error::Unauthorized));
+        if info.is_paused {
+            self.env().revert(Error::MinterPaused);
+        }
+        if info.is_cap_reached() {
+            self.env().revert(Error::MinterCapReached);
+        }
+    }
+
+    /// Count a license minted by a minter
+    pub fn record_mint(&mut self, minter: &Address) {
+        if let Some(mut info) = self.minters.get(minter) {
+            info.minted += 1;
+            self.minters.set(minter, info);
+        }
+    }
+
+    /// Apply a change to an existing minter and store it
+    fn update(&mut self, minter: Address, change: impl FnOnce(&mut MinterInfo)) -> MinterInfo {
+        let mut info = self.minters.get(&minter)
+            .unwrap_or_else(|| self.env().revert(Error::MinterNotFound));
+        change(&mut info);
+        self.minters.set(&minter, info.clone());
+        info
+    }
+}
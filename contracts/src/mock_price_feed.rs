@@ -0,0 +1,47 @@
+//! Mock CSPR/USD Price Feed
+//!
+//! A minimal `PriceFeed` implementation for tests; it is not compiled into the contracts.
+//! Anyone can push a price; every push starts a new round, so consecutive
+//! rounds can be used to exercise the oracle's staleness and deviation checks.
+
+use odra::prelude::*;
+use odra::casper_types::U512;
+
+use crate::errors::Error;
+use crate::types::PriceRound;
+
+/// Price feed whose rounds are set by hand
+#[odra::module]
+pub struct MockPriceFeed {
+    /// Number of rounds pushed so far
+    round_count: Var<u64>,
+    /// Rounds by id: round_id -> round
+    rounds: Mapping<u64, PriceRound>,
+}
+
+#[odra::module]
+impl MockPriceFeed {
+    /// Push a new price round
+    pub fn set_price(&mut self, motes_per_usd: U512, updated_at: u64) {
+        let round_id = self.round_count.get_or_default();
+        self.rounds.set(&round_id, PriceRound {
+            round_id,
+            motes_per_usd,
+            updated_at,
+        });
+        self.round_count.set(round_id + 1);
+    }
+
+    /// Get the latest price round (reverts until a price has been pushed)
+    pub fn latest_price(&self) -> PriceRound {
+        self.round_count.get_or_default()
+            .checked_sub(1)
+            .and_then(|round_id| self.rounds.get(&round_id))
+            .unwrap_or_else(|| self.env().revert(Error::InvalidOraclePrice))
+    }
+
+    /// Get a past price round
+    pub fn get_round(&self, round_id: u64) -> Option<PriceRound> {
+        self.rounds.get(&round_id)
+    }
+}
@@ -0,0 +1,86 @@
+//! Moderation Storage for Sampled Marketplace
+//!
+//! Keeps the state used by moderators to police the catalog:
+//! - User reports filed against samples, with a reason code and status
+//! - A blocklist of addresses barred from uploading or buying
+
+use odra::prelude::*;
+
+use crate::types::{Report, ReportStatus};
+
+/// Reports and blocklist storage
+#[odra::module]
+pub struct ModerationStore {
+    /// Mapping of report ID to report data
+    reports: Mapping<u64, Report>,
+    /// Total number of reports (used for generating IDs)
+    report_count: Var<u64>,
+    /// Whether a user has an open report against a sample: (reporter, sample_id) -> bool
+    open_report_by: Mapping<(Address, u64), bool>,
+    /// Whether an address is barred from uploading or buying
+    blocked: Mapping<Address, bool>,
+}
+
+impl ModerationStore {
+    /// Store a new open report and return its ID
+    pub fn add_report(&mut self, sample_id: u64, reporter: Address, reason_code: u8, now: u64) -> u64 {
+        let report_id = self.report_count.get_or_default() + 1;
+        self.report_count.set(report_id);
+        self.reports.set(&report_id, Report {
+            report_id,
+            sample_id,
+            reporter,
+            reason_code,
+            status: ReportStatus::Open,
+            created_at: now,
+            resolved_by: None,
+            resolved_at: None,
+        });
+        self.open_report_by.set(&(reporter, sample_id), true);
+        report_id
+    }
+
+    /// Close a report with the given status
+    pub fn close_report(&mut self, mut report: Report, status: ReportStatus, moderator: Address, now: u64) {
+        self.open_report_by.set(&(report.reporter, report.sample_id), false);
+        report.status = status;
+        report.resolved_by = Some(moderator);
+        report.resolved_at = Some(now);
+        let report_id = report.report_id;
+        self.reports.set(&report_id, report);
+    }
+
+    /// Get a report by ID
+    pub fn report(&self, report_id: u64) -> Option<Report> {
+        self.reports.get(&report_id)
+    }
+
+    /// Check if a user already has an open report against a sample
+    pub fn has_open_report(&self, reporter: &Address, sample_id: u64) -> bool {
+        self.open_report_by.get_or_default(&(*reporter, sample_id))
+    }
+
+    /// Get all reports that are still open
+    pub fn open_reports(&self) -> Vec<Report> {
+        let count = self.report_count.get_or_default();
+        let mut result = Vec::new();
+        for report_id in 1..=count {
+            if let Some(report) = self.reports.get(&report_id) {
+                if let ReportStatus::Open = report.status {
+                    result.push(report);
+                }
+            }
+        }
+        result
+    }
+
+    /// Add or remove an address from the blocklist
+    pub fn set_blocked(&mut self, account: Address, blocked: bool) {
+        self.blocked.set(&account, blocked);
+    }
+
+    /// Check if an address is on the blocklist
+    pub fn is_blocked(&self, account: &Address) -> bool {
+        self.blocked.get_or_default(account)
+    }
+}
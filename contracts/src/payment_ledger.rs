@@ -0,0 +1,187 @@
+//! Payment Ledger for Sampled Marketplace
+//!
+//! Marketplace bookkeeping on top of the shared `CurrencyBalances`.
+//! Features:
+//! - Withdrawable earnings, the token allowlist and transfers (via `CurrencyBalances`)
+//! - Lifetime spending per user and currency
+//! - Trading volume and platform fees per currency
+//! - Prepaid CSPR credit balances for buyers
+//! - Tip totals per producer and tipper
+
+use odra::prelude::*;
+use odra::casper_types::U512;
+
+use crate::currency_balances::CurrencyBalances;
+use crate::errors::Error;
+
+/// Multi-currency balance storage
+#[odra::module]
+pub struct PaymentLedger {
+    /// Withdrawable earnings per user and currency, and the accepted token allowlist
+    balances: SubModule<CurrencyBalances>,
+    /// Lifetime spending: (user, currency) -> amount
+    total_spent: Mapping<(Address, Option<Address>), U512>,
+    /// Trading volume per currency
+    volume: Mapping<Option<Address>, U512>,
+    /// Platform fees collected per currency
+    fees_collected: Mapping<Option<Address>, U512>,
+
+    /// Prepaid CSPR credit balance per buyer (motes)
+    credit: Mapping<Address, U512>,
+
+    /// Tips received per producer, after the platform fee (motes)
+    tips_received: Mapping<Address, U512>,
+    /// Tips given per tipper (motes)
+    tips_given: Mapping<Address, U512>,
+}
+
+impl PaymentLedger {
+    // ============================================
+    // Token Allowlist
+    // ============================================
+
+    /// Add or remove a CEP-18 token from the allowlist
+    pub fn set_accepted(&mut self, token: Address, accepted: bool) {
+        self.balances.set_accepted(token, accepted);
+    }
+
+    /// Check if a currency can be used for payments (CSPR is always accepted)
+    pub fn is_accepted(&self, currency: &Option<Address>) -> bool {
+        self.balances.is_accepted(currency)
+    }
+
+    /// Revert unless the currency can be used for payments
+    pub fn assert_accepted(&self, currency: &Option<Address>) {
+        self.balances.assert_accepted(currency);
+    }
+
+    /// Get all currently accepted CEP-18 tokens
+    pub fn accepted_tokens(&self) -> Vec<Address> {
+        self.balances.accepted_tokens()
+    }
+
+    // ============================================
+    // Balances
+    // ============================================
+
+    /// Add to a user's withdrawable balance and lifetime earnings
+    pub fn credit_earnings(&mut self, user: Address, currency: Option<Address>, amount: U512) {
+        self.balances.credit_earnings(user, currency, amount);
+    }
+
+    /// Add to a user's lifetime spending
+    pub fn record_spent(&mut self, user: Address, currency: Option<Address>, amount: U512) {
+        let key = (user, currency);
+        let spent = self.total_spent.get_or_default(&key) + amount;
+        self.total_spent.set(&key, spent);
+    }
+
+    /// Add a sale to the volume and platform fee totals
+    pub fn record_sale(&mut self, currency: Option<Address>, amount: U512, platform_fee: U512) {
+        let volume = self.volume.get_or_default(&currency) + amount;
+        self.volume.set(&currency, volume);
+        let fees = self.fees_collected.get_or_default(&currency) + platform_fee;
+        self.fees_collected.set(&currency, fees);
+    }
+
+    /// Add a platform fee that is not part of a sale (tips) to the fee totals
+    pub fn record_fee(&mut self, currency: Option<Address>, platform_fee: U512) {
+        let fees = self.fees_collected.get_or_default(&currency) + platform_fee;
+        self.fees_collected.set(&currency, fees);
+    }
+
+    /// Reset a user's withdrawable balance and return the previous amount
+    pub fn take_earnings(&mut self, user: Address, currency: Option<Address>) -> U512 {
+        self.balances.take_earnings(user, currency)
+    }
+
+    /// Get a user's withdrawable balance
+    pub fn earnings(&self, user: &Address, currency: &Option<Address>) -> U512 {
+        self.balances.earnings(user, currency)
+    }
+
+    /// Get a user's lifetime earnings
+    pub fn total_earned(&self, user: &Address, currency: &Option<Address>) -> U512 {
+        self.balances.total_earned(user, currency)
+    }
+
+    /// Get a user's lifetime spending
+    pub fn total_spent(&self, user: &Address, currency: &Option<Address>) -> U512 {
+        self.total_spent.get_or_default(&(*user, *currency))
+    }
+
+    /// Get the trading volume for a currency
+    pub fn volume(&self, currency: &Option<Address>) -> U512 {
+        self.volume.get_or_default(currency)
+    }
+
+    /// Get the platform fees collected for a currency
+    pub fn fees_collected(&self, currency: &Option<Address>) -> U512 {
+        self.fees_collected.get_or_default(currency)
+    }
+
+    // ============================================
+    // Prepaid Credit
+    // ============================================
+
+    /// Add to a buyer's credit balance and return the new balance
+    pub fn add_credit(&mut self, user: Address, amount: U512) -> U512 {
+        let balance = self.credit.get_or_default(&user) + amount;
+        self.credit.set(&user, balance);
+        balance
+    }
+
+    /// Deduct from a buyer's credit balance and return the new balance
+    /// Reverts with `InsufficientCredit` if the balance is too low
+    pub fn debit_credit(&mut self, user: Address, amount: U512) -> U512 {
+        let balance = self.credit.get_or_default(&user);
+        if balance < amount {
+            self.env().revert(Error::InsufficientCredit);
+        }
+        let balance = balance - amount;
+        self.credit.set(&user, balance);
+        balance
+    }
+
+    /// Get a buyer's credit balance
+    pub fn credit(&self, user: &Address) -> U512 {
+        self.credit.get_or_default(user)
+    }
+
+    // ============================================
+    // Tips
+    // ============================================
+
+    /// Add a tip to the tipper's and producer's totals
+    pub fn record_tip(&mut self, tipper: Address, seller: Address, amount: U512, seller_amount: U512) {
+        let given = self.tips_given.get_or_default(&tipper) + amount;
+        self.tips_given.set(&tipper, given);
+        let received = self.tips_received.get_or_default(&seller) + seller_amount;
+        self.tips_received.set(&seller, received);
+    }
+
+    /// Get the tips a producer has received, after the platform fee
+    pub fn tips_received(&self, user: &Address) -> U512 {
+        self.tips_received.get_or_default(user)
+    }
+
+    /// Get the tips a user has given
+    pub fn tips_given(&self, user: &Address) -> U512 {
+        self.tips_given.get_or_default(user)
+    }
+
+    // ============================================
+    // Transfers
+    // ============================================
+
+    /// Pay an amount out of the contract in the given currency
+    pub fn pay_out(&self, to: &Address, currency: &Option<Address>, amount: U512) {
+        self.balances.pay_out(to, currency, amount);
+    }
+
+    /// Pull CEP-18 tokens from `from` into this contract
+    /// Requires `from` to have approved this contract for at least `amount`
+    pub fn collect_token(&self, from: &Address, token: &Address, amount: U512) {
+        self.balances.collect_token(from, token, amount);
+    }
+}
@@ -0,0 +1,92 @@
+//! USD Price Oracle for Sampled Marketplace
+//!
+//! Converts USD-denominated sample prices into motes using an external
+//! price feed contract configured by the admin.
+//! Features:
+//! - Minimal `PriceFeed` interface any feed (or mock) contract can implement
+//! - Staleness check on the latest price round
+//! - Max-deviation guard between consecutive price rounds
+
+use odra::prelude::*;
+use odra::casper_types::U512;
+use odra::ContractRef;
+
+use crate::errors::Error;
+use crate::types::{constants, PriceFeedConfig, PriceRound};
+
+/// Interface a CSPR/USD price feed contract must expose
+#[odra::external_contract]
+pub trait PriceFeed {
+    /// Get the latest CSPR/USD price round
+    fn latest_price(&self) -> PriceRound;
+    /// Get a past price round (None if the feed does not have it)
+    fn get_round(&self, round_id: u64) -> Option<PriceRound>;
+}
+
+/// Price feed configuration
+#[odra::module]
+pub struct UsdPriceOracle {
+    /// Price feed configuration (None until the admin sets one)
+    config: Var<PriceFeedConfig>,
+}
+
+impl UsdPriceOracle {
+    /// Set the price feed
+    pub fn set_config(&mut self, config: PriceFeedConfig) {
+        self.config.set(config);
+    }
+
+    /// Get the price feed configuration
+    pub fn config(&self) -> Option<PriceFeedConfig> {
+        self.config.get()
+    }
+
+    /// Fetch the current rate and check it against the staleness and deviation limits
+    /// Returns the error instead of reverting so views can degrade gracefully
+    pub fn current_rate(&self) -> Result<U512, Error> {
+        let config = self.config.get().ok_or(Error::PriceFeedNotSet)?;
+        let feed = PriceFeedContractRef::new(self.env(), config.feed);
+        let round = feed.latest_price();
+
+        if round.motes_per_usd.is_zero() {
+            return Err(Error::InvalidOraclePrice);
+        }
+
+        let now = self.env().get_block_time();
+        if round.updated_at > now || now - round.updated_at > config.max_staleness {
+            return Err(Error::StalePrice);
+        }
+
+        // Compare against the previous round, so a single manipulated update is caught
+        // no matter how long ago the last purchase was
+        let previous = match round.round_id {
+            0 => None,
+            round_id => feed.get_round(round_id - 1),
+        };
+        let reference = previous.map(|previous| previous.motes_per_usd).unwrap_or_default();
+        if config.max_deviation_bps > 0 && !reference.is_zero() {
+            let diff = if round.motes_per_usd > reference {
+                round.motes_per_usd - reference
+            } else {
+                reference - round.motes_per_usd
+            };
+            let max_diff = reference * U512::from(config.max_deviation_bps)
+                / U512::from(constants::BPS_DENOMINATOR);
+            if diff > max_diff {
+                return Err(Error::PriceDeviationTooHigh);
+            }
+        }
+
+        Ok(round.motes_per_usd)
+    }
+
+    /// Fetch the current rate for a purchase, reverting on failure
+    pub fn rate_for_purchase(&self) -> U512 {
+        self.current_rate().unwrap_or_else(|error| self.env().revert(error))
+    }
+
+    /// Convert a USD amount in cents to motes at the given rate
+    pub fn to_motes(usd_cents: u64, motes_per_usd: U512) -> U512 {
+        U512::from(usd_cents) * motes_per_usd / U512::from(constants::CENTS_PER_USD)
+    }
+}
@@ -0,0 +1,77 @@
+//! Referral Program for Sampled Marketplace
+//!
+//! Attributes sales to referrers (curators, blogs, affiliates) and
+//! computes their commission.
+//! Features:
+//! - Admin-set share of the platform fee paid to the referrer
+//! - Optional seller-funded bonus, taken from the seller's proceeds
+//! - Referral counts and lifetime commission per referrer and currency
+
+use odra::prelude::*;
+use odra::casper_types::U512;
+
+use crate::types::constants::BPS_DENOMINATOR;
+
+/// Referral rates and per-referrer statistics
+#[odra::module]
+pub struct ReferralProgram {
+    /// Share of the platform fee paid to referrers, in basis points
+    platform_share_bps: Var<u64>,
+    /// Seller-funded bonus on their proceeds, in basis points: seller -> bps
+    seller_bonus_bps: Mapping<Address, u64>,
+    /// Number of referred sales per referrer
+    referral_count: Mapping<Address, u64>,
+    /// Lifetime commission: (referrer, currency) -> amount
+    referral_earned: Mapping<(Address, Option<Address>), U512>,
+}
+
+impl ReferralProgram {
+    /// Set the share of the platform fee paid to referrers
+    pub fn set_platform_share(&mut self, bps: u64) {
+        self.platform_share_bps.set(bps);
+    }
+
+    /// Set a seller's bonus for referrers
+    pub fn set_seller_bonus(&mut self, seller: Address, bps: u64) {
+        self.seller_bonus_bps.set(&seller, bps);
+    }
+
+    /// Get the share of the platform fee paid to referrers (bps)
+    pub fn platform_share_bps(&self) -> u64 {
+        self.platform_share_bps.get_or_default()
+    }
+
+    /// Get a seller's bonus for referrers (bps)
+    pub fn seller_bonus_bps(&self, seller: &Address) -> u64 {
+        self.seller_bonus_bps.get_or_default(seller)
+    }
+
+    /// Calculate the referrer's share of a platform fee
+    pub fn platform_share(&self, platform_fee: U512) -> U512 {
+        platform_fee * U512::from(self.platform_share_bps()) / U512::from(BPS_DENOMINATOR)
+    }
+
+    /// Calculate a seller's bonus on their proceeds from a sale
+    pub fn seller_bonus(&self, seller: &Address, seller_amount: U512) -> U512 {
+        seller_amount * U512::from(self.seller_bonus_bps(seller)) / U512::from(BPS_DENOMINATOR)
+    }
+
+    /// Record a referred sale and the commission paid
+    pub fn record(&mut self, referrer: Address, currency: Option<Address>, commission: U512) {
+        let count = self.referral_count.get_or_default(&referrer);
+        self.referral_count.set(&referrer, count + 1);
+        let key = (referrer, currency);
+        let earned = self.referral_earned.get_or_default(&key) + commission;
+        self.referral_earned.set(&key, earned);
+    }
+
+    /// Get the number of referred sales
+    pub fn referral_count(&self, referrer: &Address) -> u64 {
+        self.referral_count.get_or_default(referrer)
+    }
+
+    /// Get a referrer's lifetime commission in a currency
+    pub fn referral_earned(&self, referrer: &Address, currency: &Option<Address>) -> U512 {
+        self.referral_earned.get_or_default(&(*referrer, *currency))
+    }
+}
@@ -0,0 +1,93 @@
+//! Release Registry for the License NFT contract
+//!
+//! On-chain records of released works (identified by ISRC) that use a licensed sample:
+//! - Releases per license, for the license holder's catalog
+//! - Releases per sample, so producers can see where their sample ended up
+//! - ISRC lookup, for settling content-ID disputes
+
+use odra::prelude::*;
+
+use crate::license_types::Release;
+
+/// Registered releases indexed by license, sample and ISRC
+#[odra::module]
+pub struct ReleaseRegistry {
+    /// Mapping of release ID to release data
+    releases: Mapping<u64, Release>,
+    /// Total number of releases (used for generating IDs)
+    release_count: Var<u64>,
+    /// Release registered for an ISRC: isrc -> release_id
+    isrc_release: Mapping<String, u64>,
+
+    /// Count of releases per license: license_id -> count
+    license_release_count: Mapping<u64, u64>,
+    /// Indexed releases: (license_id, index) -> release_id
+    license_release_at: Mapping<(u64, u64), u64>,
+
+    /// Count of releases per sample: sample_id -> count
+    sample_release_count: Mapping<u64, u64>,
+    /// Indexed releases: (sample_id, index) -> release_id
+    sample_release_at: Mapping<(u64, u64), u64>,
+}
+
+impl ReleaseRegistry {
+    /// Store a release, index it and return it with its new ID
+    pub fn add(&mut self, mut release: Release) -> Release {
+        let release_id = self.release_count.get_or_default() + 1;
+        self.release_count.set(release_id);
+        release.release_id = release_id;
+
+        let license_id = release.license_id;
+        let count = self.license_release_count.get_or_default(&license_id);
+        self.license_release_at.set(&(license_id, count), release_id);
+        self.license_release_count.set(&license_id, count + 1);
+
+        let sample_id = release.sample_id;
+        let count = self.sample_release_count.get_or_default(&sample_id);
+        self.sample_release_at.set(&(sample_id, count), release_id);
+        self.sample_release_count.set(&sample_id, count + 1);
+
+        self.isrc_release.set(&release.isrc, release_id);
+        self.releases.set(&release_id, release.clone());
+        release
+    }
+
+    /// Get a release by ID
+    pub fn release(&self, release_id: u64) -> Option<Release> {
+        self.releases.get(&release_id)
+    }
+
+    /// Get the release registered for an ISRC (if any)
+    pub fn release_by_isrc(&self, isrc: &str) -> Option<Release> {
+        self.isrc_release.get(&String::from(isrc))
+            .and_then(|release_id| self.releases.get(&release_id))
+    }
+
+    /// Get all releases registered against a license
+    pub fn license_releases(&self, license_id: u64) -> Vec<Release> {
+        let count = self.license_release_count.get_or_default(&license_id);
+        let mut result = Vec::new();
+        for i in 0..count {
+            if let Some(release) = self.license_release_at.get(&(license_id, i))
+                .and_then(|release_id| self.releases.get(&release_id))
+            {
+                result.push(release);
+            }
+        }
+        result
+    }
+
+    /// Get all releases that use a sample
+    pub fn sample_releases(&self, sample_id: u64) -> Vec<Release> {
+        let count = self.sample_release_count.get_or_default(&sample_id);
+        let mut result = Vec::new();
+        for i in 0..count {
+            if let Some(release) = self.sample_release_at.get(&(sample_id, i))
+                .and_then(|release_id| self.releases.get(&release_id))
+            {
+                result.push(release);
+            }
+        }
+        result
+    }
+}
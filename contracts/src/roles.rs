@@ -0,0 +1,117 @@
+//! Role-Based Access Control for Sampled Marketplace
+//!
+//! Shared sub-module used by both contracts, built on odra-modules'
+//! `AccessControl` and `Pauseable`.
+//! Roles:
+//! - `ADMIN_ROLE`: grants and revokes every other role, wires contracts together
+//! - `MODERATOR_ROLE`: handles reports, delists samples and manages the blocklist
+//! - `TREASURER_ROLE`: manages accepted tokens and referral rates
+//! - `PAUSER_ROLE`: pauses and unpauses purchases, uploads and license minting
+//! - `PRICING_MANAGER_ROLE`: configures the USD price feed
+//! - `LICENSE_MINTER_ROLE`: may mint licenses on `LicenseNft`, as a registered minter
+//!   bound by its pause switch and cap
+//!
+//! Holders of `ADMIN_ROLE` pass every role check.
+
+use odra::prelude::*;
+use odra_modules::access::{AccessControl, Role, DEFAULT_ADMIN_ROLE};
+use odra_modules::security::Pauseable;
+
+use crate::errors::Error;
+
+/// Role that administers all other roles
+pub const ADMIN_ROLE: Role = DEFAULT_ADMIN_ROLE;
+/// Role for content moderators
+pub const MODERATOR_ROLE: Role = role_id(b"moderator");
+/// Role for treasury and payment settings
+pub const TREASURER_ROLE: Role = role_id(b"treasurer");
+/// Role allowed to pause and unpause the contract
+pub const PAUSER_ROLE: Role = role_id(b"pauser");
+/// Role for platform-level pricing configuration
+pub const PRICING_MANAGER_ROLE: Role = role_id(b"pricing_manager");
+/// Role allowed to mint license NFTs
+pub const LICENSE_MINTER_ROLE: Role = role_id(b"license_minter");
+
+/// Build a role identifier from its name (zero-padded ASCII)
+const fn role_id(name: &[u8]) -> Role {
+    let mut id = [0u8; 32];
+    let mut i = 0;
+    while i < name.len() {
+        id[i] = name[i];
+        i += 1;
+    }
+    id
+}
+
+/// Role membership and pause state
+#[odra::module]
+pub struct Roles {
+    /// Role membership (odra-modules access control)
+    access: SubModule<AccessControl>,
+    /// Emergency stop switch
+    pause_state: SubModule<Pauseable>,
+}
+
+impl Roles {
+    /// Grant the admin role to the initial admin
+    pub fn init(&mut self, admin: Address) {
+        self.access.unchecked_grant_role(&ADMIN_ROLE, &admin);
+    }
+
+    /// Grant a role (caller must hold the role's admin role)
+    pub fn grant_role(&mut self, role: &Role, account: &Address) {
+        self.access.grant_role(role, account);
+    }
+
+    /// Revoke a role (caller must hold the role's admin role)
+    pub fn revoke_role(&mut self, role: &Role, account: &Address) {
+        self.access.revoke_role(role, account);
+    }
+
+    /// Give up a role held by the caller
+    pub fn renounce_role(&mut self, role: &Role) {
+        let caller = self.env().caller();
+        self.access.renounce_role(role, &caller);
+    }
+
+    /// Check if an account has been granted a role
+    pub fn has_role(&self, role: &Role, account: &Address) -> bool {
+        self.access.has_role(role, account)
+    }
+
+    /// Check if an account holds a role or the admin role
+    pub fn is_authorized(&self, role: &Role, account: &Address) -> bool {
+        self.has_role(role, account) || self.has_role(&ADMIN_ROLE, account)
+    }
+
+    /// Revert unless the caller holds the role (or the admin role); returns the caller
+    pub fn assert_role(&self, role: &Role) -> Address {
+        let caller = self.env().caller();
+        if !self.is_authorized(role, &caller) {
+            self.env().revert(Error::Unauthorized);
+        }
+        caller
+    }
+
+    /// Pause the contract (pauser only)
+    pub fn pause(&mut self) {
+        self.assert_role(&PAUSER_ROLE);
+        self.pause_state.pause();
+    }
+
+    /// Unpause the contract (pauser only)
+    pub fn unpause(&mut self) {
+        self.assert_role(&PAUSER_ROLE);
+        self.pause_state.unpause();
+    }
+
+    /// Check if the contract is paused
+    pub fn is_paused(&self) -> bool {
+        self.pause_state.is_paused()
+    }
+
+    /// Revert if the contract is paused
+    pub fn require_not_paused(&self) {
+        self.pause_state.require_not_paused();
+    }
+}
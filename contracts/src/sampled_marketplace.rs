@@ -5,22 +5,60 @@
 //! Now includes NFT-based license system integration.
 
 use odra::prelude::*;
-use odra::casper_types::U512;
+use odra::casper_types::{U512, PublicKey};
+use odra::casper_types::bytesrepr::{self, Bytes, FromBytes, ToBytes};
 
 use crate::errors::Error;
 use crate::events::{
     SampleUploaded, SamplePurchased, EarningsWithdrawn,
-    SampleDeactivated, PriceUpdated, LicensePricingUpdated,
+    SampleDeactivated, PriceUpdated, LicensePricingUpdated, SaleWindowUpdated,
+    SellerPricingUpdated, CatalogRepriced, SampleCurrencyUpdated, AcceptedTokenUpdated,
+    UsdPriceUpdated, PriceFeedUpdated, CreditDeposited, CreditWithdrawn, CreditSpent,
+    SubscriptionPlanUpdated, Subscribed, PayWhatYouWantUpdated, TipReceived,
+    ReferralPaid, ReferralShareUpdated, SellerReferralBonusUpdated, PromotionalLicensesGranted,
+    SampleReported, ReportClosed, SampleListingUpdated, AddressBlockUpdated, TerritoryPricingUpdated,
+    SignedListingRedeemed, SignedListingCancelled, MetaTransactionExecuted, RelayerUpdated,
+    MetaTxNonceBumped, ContentHashUpdated, SampleReactivated,
+};
+use crate::types::{
+    Sample, PurchaseRecord, UserStats, MarketplaceStats, PriceFeedConfig,
+    SubscriptionPlan, Subscription, ReferrerStats, Report, ReportReason, ReportStatus,
+    SignedListing, MetaTransaction, MetaTxAction, constants::*,
+};
+use crate::license_types::{
+    LicenseType, LicensePricing, AllLicensePrices, PricingMode, Release, LicenseTier, TierPrice,
+    TerritoryPrice, is_valid_territory,
 };
-use crate::types::{Sample, PurchaseRecord, UserStats, MarketplaceStats, constants::*};
-use crate::license_types::{LicenseType, LicensePricing, AllLicensePrices};
 use crate::license_nft::LicenseNftContractRef;
+use crate::license_pricing::LicensePricingStore;
+use crate::payment_ledger::PaymentLedger;
+use crate::price_oracle::UsdPriceOracle;
+use crate::subscriptions::SubscriptionStore;
+use crate::referrals::ReferralProgram;
+use crate::moderation::ModerationStore;
+use crate::signed_listings::SignedListingStore;
+use crate::meta_transactions::MetaTxStore;
+use crate::content_registry::{ContentRegistry, is_valid_content_hash};
+use crate::roles::{Roles, ADMIN_ROLE, MODERATOR_ROLE, TREASURER_ROLE, PRICING_MANAGER_ROLE};
+use crate::user_activity::UserActivity;
+use odra_modules::access::Role;
+use odra_modules::access::events::{RoleGranted, RoleRevoked, RoleAdminChanged};
+use odra_modules::security::events::{Paused, Unpaused};
 use odra::ContractRef;
 
 
 /// Main marketplace contract module
 #[odra::module(
-    events = [SampleUploaded, SamplePurchased, EarningsWithdrawn, SampleDeactivated, PriceUpdated, LicensePricingUpdated],
+    events = [
+        SampleUploaded, SamplePurchased, EarningsWithdrawn, SampleDeactivated, PriceUpdated,
+        LicensePricingUpdated, SaleWindowUpdated, SellerPricingUpdated, CatalogRepriced,
+        SampleCurrencyUpdated, AcceptedTokenUpdated, UsdPriceUpdated, PriceFeedUpdated,
+        CreditDeposited, CreditWithdrawn, CreditSpent, SubscriptionPlanUpdated, Subscribed,
+        PayWhatYouWantUpdated, TipReceived, ReferralPaid, ReferralShareUpdated,
+        SellerReferralBonusUpdated, PromotionalLicensesGranted, SampleReported, ReportClosed, SampleListingUpdated,
+        AddressBlockUpdated, TerritoryPricingUpdated, SignedListingRedeemed, SignedListingCancelled,
+        MetaTransactionExecuted, RelayerUpdated, MetaTxNonceBumped, ContentHashUpdated, SampleReactivated, RoleGranted, RoleRevoked, RoleAdminChanged, Paused, Unpaused
+    ],
     errors = Error
 )]
 pub struct SampledMarketplace {
@@ -32,35 +70,27 @@ pub struct SampledMarketplace {
     samples: Mapping<u64, Sample>,
     /// Total number of samples (used for generating IDs)
     sample_count: Var<u64>,
-    /// Total trading volume in motes
-    total_volume: Var<U512>,
-    /// Total platform fees collected
-    platform_fee_collected: Var<U512>,
     /// Admin address (receives platform fees)
     admin: Var<Address>,
 
+    /// Role membership and pause state
+    roles: SubModule<Roles>,
+
     // ============================================
     // User Data Storage (using indexed mappings)
     // ============================================
 
-    /// Count of samples uploaded by each user
-    user_uploaded_count: Mapping<Address, u64>,
-    /// Indexed sample IDs: (user, index) -> sample_id
-    user_uploaded_at: Mapping<(Address, u64), u64>,
+    /// Uploaded and purchased samples per user, with purchase records
+    activity: SubModule<UserActivity>,
 
-    /// Count of samples purchased by each user
-    user_purchased_count: Mapping<Address, u64>,
-    /// Indexed purchased sample IDs: (user, index) -> sample_id
-    user_purchased_at: Mapping<(Address, u64), u64>,
+    // ============================================
+    // Payment Storage
+    // ============================================
 
-    /// User's available earnings (withdrawable)
-    user_earnings: Mapping<Address, U512>,
-    /// User's total lifetime earnings
-    user_total_earned: Mapping<Address, U512>,
-    /// User's total lifetime spending
-    user_total_spent: Mapping<Address, U512>,
-    /// Purchase records for each user (buyer, sample_id) -> PurchaseRecord
-    user_purchase_records: Mapping<(Address, u64), PurchaseRecord>,
+    /// Earnings, spending, volume and fees per currency, the token allowlist and buyer credit
+    ledger: SubModule<PaymentLedger>,
+    /// CSPR/USD price feed used to convert USD-priced samples
+    price_oracle: SubModule<UsdPriceOracle>,
 
     // ============================================
     // License System Storage
@@ -68,8 +98,30 @@ pub struct SampledMarketplace {
 
     /// License NFT contract reference
     license_nft_contract: Var<Address>,
-    /// Custom license pricing per sample: sample_id -> LicensePricing
-    sample_license_pricing: Mapping<u64, LicensePricing>,
+    /// License pricing (per-sample multipliers/absolute prices, seller defaults and templates)
+    license_pricing: SubModule<LicensePricingStore>,
+
+    // ============================================
+    // Subscription Storage
+    // ============================================
+
+    /// Producer subscription plans and subscriber access
+    subscriptions: SubModule<SubscriptionStore>,
+    /// Referral rates and referrer statistics
+    referrals: SubModule<ReferralProgram>,
+
+    // ============================================
+    // Moderation Storage
+    // ============================================
+
+    /// Reports and address blocklist (moderators hold `MODERATOR_ROLE`)
+    moderation: SubModule<ModerationStore>,
+    /// Redeemed and cancelled signed listing nonces
+    signed_listings: SubModule<SignedListingStore>,
+    /// Meta-transaction nonces and relayer allowlist
+    meta_transactions: SubModule<MetaTxStore>,
+    /// Audio content hashes and fingerprints of uploaded samples
+    content: SubModule<ContentRegistry>,
 }
 
 #[odra::module]
@@ -83,8 +135,7 @@ impl SampledMarketplace {
     pub fn init(&mut self, admin: Address) {
         self.admin.set(admin);
         self.sample_count.set(0);
-        self.total_volume.set(U512::zero());
-        self.platform_fee_collected.set(U512::zero());
+        self.roles.init(admin);
     }
 
     // ============================================
@@ -92,164 +143,208 @@ impl SampledMarketplace {
     // ============================================
 
     /// Upload a new sample to the marketplace
+    ///
+    /// `pay_what_you_want` makes `price` the minimum buyers pay (zero allows free downloads).
+    /// `sale_starts_at` / `sale_ends_at` optionally schedule the release and
+    /// automatically end sales; `None` means "immediately" / "never".
+    /// `content_hash` is the SHA-256 of the audio file as 64 lowercase hex characters,
+    /// and must not be registered to another sample;
+    /// `fingerprint` is an optional perceptual fingerprint for near-duplicate detection.
+    /// `pricing_template` names one of the caller's pricing templates to apply (None = default pricing).
+    #[allow(clippy::too_many_arguments)]
     pub fn upload_sample(
         &mut self,
         price: U512,
+        pay_what_you_want: bool,
         ipfs_link: String,
         title: String,
         bpm: u64,
         genre: String,
         cover_image: String,
         video_preview_link: String,
+        sale_starts_at: Option<u64>,
+        sale_ends_at: Option<u64>,
+        content_hash: String,
+        fingerprint: Option<String>,
+        pricing_template: Option<String>,
     ) {
         let caller = self.env().caller();
-
-        // Validate inputs
-        if price == U512::zero() {
-            self.env().revert(Error::InvalidPrice);
-        }
-        if title.len() > MAX_TITLE_LENGTH {
-            self.env().revert(Error::TitleTooLong);
-        }
-        if ipfs_link.len() > MAX_IPFS_LINK_LENGTH {
-            self.env().revert(Error::IpfsLinkTooLong);
-        }
-        if genre.len() > MAX_GENRE_LENGTH {
-            self.env().revert(Error::GenreTooLong);
-        }
-        if cover_image.len() > MAX_COVER_IMAGE_LENGTH {
-            self.env().revert(Error::CoverImageTooLong);
-        }
-        if video_preview_link.len() > MAX_VIDEO_PREVIEW_LENGTH {
-            self.env().revert(Error::VideoPreviewTooLong);
-        }
-
-        // Generate new sample ID
-        let sample_count = self.sample_count.get_or_default();
-        let sample_id = sample_count + 1;
-        self.sample_count.set(sample_id);
-
-        // Get current timestamp
-        let timestamp = self.env().get_block_time();
-
-        // Create sample
-        let sample = Sample {
-            sample_id,
-            seller: caller,
+        self.upload_sample_internal(
+            caller,
             price,
-            ipfs_link: ipfs_link.clone(),
-            title: title.clone(),
+            pay_what_you_want,
+            ipfs_link,
+            title,
             bpm,
             genre,
-            cover_image: cover_image.clone(),
+            cover_image,
             video_preview_link,
-            total_sales: 0,
-            is_active: true,
-            created_at: timestamp,
-        };
-
-        // Store sample
-        self.samples.set(&sample_id, sample);
+            sale_starts_at,
+            sale_ends_at,
+            content_hash,
+            fingerprint,
+            pricing_template,
+        );
+    }
 
-        // Add to user's uploaded samples using indexed mapping
-        let user_count = self.user_uploaded_count.get_or_default(&caller);
-        self.user_uploaded_at.set(&(caller, user_count), sample_id);
-        self.user_uploaded_count.set(&caller, user_count + 1);
+    /// Create a sample from a seller-signed listing and purchase it in one call
+    /// The seller pays no gas to list; `signature` is the seller's signature over
+    /// `get_signed_listing_message(listing)` and `seller_key` their public key
+    #[odra(payable)]
+    pub fn purchase_signed_listing(
+        &mut self,
+        listing: SignedListing,
+        seller_key: PublicKey,
+        signature: Bytes,
+        max_price: Option<U512>,
+    ) {
+        let caller = self.env().caller();
+        let sample_id = self.redeem_signed_listing(caller, listing, seller_key, signature);
+        self.purchase_sample_internal(caller, sample_id, max_price, PaymentMethod::Attached, None);
+    }
 
-        // Emit event
-        self.env().emit_event(SampleUploaded {
+    /// Create a sample from a seller-signed listing and purchase a license for it in one call
+    #[odra(payable)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn purchase_signed_listing_license(
+        &mut self,
+        listing: SignedListing,
+        seller_key: PublicKey,
+        signature: Bytes,
+        license_type: u8,
+        accepted_terms: Option<String>,
+        territories: Vec<String>,
+        max_price: Option<U512>,
+    ) {
+        let caller = self.env().caller();
+        let sample_id = self.redeem_signed_listing(caller, listing, seller_key, signature);
+        self.purchase_license_internal(
+            caller,
             sample_id,
+            license_type,
+            accepted_terms,
+            territories,
+            max_price,
+            PaymentMethod::Attached,
+            None,
+        );
+    }
+
+    /// Cancel a signed listing that has not been redeemed yet (seller only)
+    pub fn cancel_signed_listing(&mut self, nonce: u64) {
+        let caller = self.env().caller();
+
+        if self.signed_listings.is_used(&caller, nonce) {
+            self.env().revert(Error::ListingNonceUsed);
+        }
+
+        self.signed_listings.cancel(caller, nonce);
+
+        self.env().emit_event(SignedListingCancelled {
             seller: caller,
-            price,
-            title,
-            ipfs_link,
-            cover_image,
-            timestamp,
+            nonce,
+            timestamp: self.env().get_block_time(),
         });
     }
 
+    /// Get the bytes a seller signs for a listing (bound to this marketplace)
+    pub fn get_signed_listing_message(&self, listing: SignedListing) -> Bytes {
+        self.signed_listing_message(&listing)
+    }
+
+    /// Check if a seller's listing nonce has been redeemed or cancelled
+    pub fn is_listing_nonce_used(&self, seller: Address, nonce: u64) -> bool {
+        self.signed_listings.is_used(&seller, nonce)
+    }
+
+    /// Get the sample created from a redeemed signed listing (if any)
+    pub fn get_signed_listing_sample(&self, seller: Address, nonce: u64) -> Option<u64> {
+        self.signed_listings.sample(&seller, nonce)
+    }
+
     /// Purchase a sample from the marketplace
+    /// `max_price` reverts the purchase if the price has risen above it since the buyer signed
     #[odra(payable)]
-    pub fn purchase_sample(&mut self, sample_id: u64) {
+    pub fn purchase_sample(&mut self, sample_id: u64, max_price: Option<U512>) {
         let caller = self.env().caller();
-        let attached_value = self.env().attached_value();
-
-        // Get sample
-        let mut sample = self.samples.get(&sample_id)
-            .unwrap_or_else(|| self.env().revert(Error::SampleNotFound));
-
-        // Validate
-        if !sample.is_active {
-            self.env().revert(Error::SampleInactive);
-        }
-        if attached_value < sample.price {
-            self.env().revert(Error::InsufficientPayment);
-        }
+        self.purchase_sample_internal(caller, sample_id, max_price, PaymentMethod::Attached, None);
+    }
 
-        // Check if already purchased
-        if self.has_purchased_internal(&caller, sample_id) {
-            self.env().revert(Error::AlreadyPurchased);
-        }
+    /// Purchase a sample priced in a CEP-18 token
+    /// The buyer must first approve this contract to spend the sample price
+    pub fn purchase_sample_with_token(&mut self, sample_id: u64, max_price: Option<U512>) {
+        let caller = self.env().caller();
+        self.purchase_sample_internal(caller, sample_id, max_price, PaymentMethod::Token, None);
+    }
 
-        // Calculate fees
-        let platform_fee = sample.price * PLATFORM_FEE_NUMERATOR / PLATFORM_FEE_DENOMINATOR;
-        let seller_amount = sample.price - platform_fee;
+    /// Purchase a sample and credit a referrer with a commission
+    /// Pays in the sample's currency: attached CSPR, or previously approved CEP-18 tokens
+    #[odra(payable)]
+    pub fn purchase_sample_with_referrer(&mut self, sample_id: u64, max_price: Option<U512>, referrer: Address) {
+        let caller = self.env().caller();
+        let method = self.default_payment_method(sample_id);
+        self.purchase_sample_internal(caller, sample_id, max_price, method, Some(referrer));
+    }
 
-        // Update sample stats
-        sample.total_sales += 1;
-        self.samples.set(&sample_id, sample.clone());
+    /// Update the price of a sample
+    pub fn update_price(&mut self, sample_id: u64, new_price: U512) {
+        let caller = self.env().caller();
+        self.update_price_internal(caller, sample_id, new_price);
+    }
 
-        // Update marketplace stats
-        let total_volume = self.total_volume.get_or_default() + sample.price;
-        self.total_volume.set(total_volume);
-        let fee_collected = self.platform_fee_collected.get_or_default() + platform_fee;
-        self.platform_fee_collected.set(fee_collected);
+    /// Change the currency a sample is priced in (seller only)
+    /// `currency` is None for CSPR or an accepted CEP-18 token; `price` is in that currency
+    /// Samples with absolute license prices (in motes) cannot move to a token
+    pub fn set_sample_currency(&mut self, sample_id: u64, currency: Option<Address>, price: U512) {
+        let caller = self.env().caller();
 
-        // Update buyer's purchased samples using indexed mapping
-        let buyer_count = self.user_purchased_count.get_or_default(&caller);
-        self.user_purchased_at.set(&(caller, buyer_count), sample_id);
-        self.user_purchased_count.set(&caller, buyer_count + 1);
+        if price == U512::zero() {
+            self.env().revert(Error::InvalidPrice);
+        }
+        self.ledger.assert_accepted(&currency);
 
-        let buyer_spent = self.user_total_spent.get_or_default(&caller) + sample.price;
-        self.user_total_spent.set(&caller, buyer_spent);
+        let mut sample = self.samples.get(&sample_id)
+            .unwrap_or_else(|| self.env().revert(Error::SampleNotFound));
 
-        // Store purchase record
-        let timestamp = self.env().get_block_time();
-        let purchase_record = PurchaseRecord {
-            sample_id,
-            seller: sample.seller,
-            price: sample.price,
-            timestamp,
-            ipfs_link: sample.ipfs_link.clone(),
-        };
-        self.user_purchase_records.set(&(caller, sample_id), purchase_record);
+        if sample.seller != caller {
+            self.env().revert(Error::NotSeller);
+        }
+        if currency.is_some() && matches!(self.license_pricing.mode(sample_id), PricingMode::Absolute) {
+            self.env().revert(Error::AbsolutePricingRequiresCspr);
+        }
 
-        // Update seller's earnings
-        let seller_earnings = self.user_earnings.get_or_default(&sample.seller) + seller_amount;
-        self.user_earnings.set(&sample.seller, seller_earnings);
-        let seller_total = self.user_total_earned.get_or_default(&sample.seller) + seller_amount;
-        self.user_total_earned.set(&sample.seller, seller_total);
+        // USD pricing only applies to CSPR-priced samples
+        let clears_usd_price = currency.is_some() && sample.usd_price_cents.is_some();
+        sample.currency = currency;
+        sample.price = price;
+        if clears_usd_price {
+            sample.usd_price_cents = None;
+        }
+        self.samples.set(&sample_id, sample);
 
-        // Transfer platform fee to admin
-        let admin = self.admin.get().unwrap();
-        self.env().transfer_tokens(&admin, &platform_fee);
+        if clears_usd_price {
+            self.env().emit_event(UsdPriceUpdated {
+                sample_id,
+                usd_price_cents: None,
+                timestamp: self.env().get_block_time(),
+            });
+        }
 
-        // Emit event
-        self.env().emit_event(SamplePurchased {
+        self.env().emit_event(SampleCurrencyUpdated {
             sample_id,
-            buyer: caller,
-            seller: sample.seller,
-            price: sample.price,
-            platform_fee,
-            timestamp,
+            currency,
+            price,
+            timestamp: self.env().get_block_time(),
         });
     }
 
-    /// Update the price of a sample
-    pub fn update_price(&mut self, sample_id: u64, new_price: U512) {
+    /// Price a CSPR sample in USD cents (seller only)
+    /// The motes price is derived from the price feed at purchase time;
+    /// `None` switches the sample back to its fixed motes price
+    pub fn set_usd_price(&mut self, sample_id: u64, usd_price_cents: Option<u64>) {
         let caller = self.env().caller();
 
-        if new_price == U512::zero() {
+        if usd_price_cents == Some(0) {
             self.env().revert(Error::InvalidPrice);
         }
 
@@ -259,23 +354,33 @@ impl SampledMarketplace {
         if sample.seller != caller {
             self.env().revert(Error::NotSeller);
         }
+        if usd_price_cents.is_some() && sample.currency.is_some() {
+            self.env().revert(Error::WrongCurrency);
+        }
 
-        let old_price = sample.price;
-        sample.price = new_price;
+        sample.usd_price_cents = usd_price_cents;
         self.samples.set(&sample_id, sample);
 
-        self.env().emit_event(PriceUpdated {
+        self.env().emit_event(UsdPriceUpdated {
             sample_id,
-            old_price,
-            new_price,
+            usd_price_cents,
             timestamp: self.env().get_block_time(),
         });
     }
 
-    /// Deactivate a sample (soft delete)
-    pub fn deactivate_sample(&mut self, sample_id: u64) {
+    /// Enable or disable pay-what-you-want pricing (seller only)
+    ///
+    /// When enabled, `min_price` is the minimum (zero allows free downloads) and
+    /// buyers attaching CSPR pay whatever they attach; token and credit purchases
+    /// pay the minimum. Licenses are not sold while enabled, since their prices
+    /// derive from the base price. When disabled, `min_price` becomes the fixed price.
+    pub fn set_pay_what_you_want(&mut self, sample_id: u64, enabled: bool, min_price: U512) {
         let caller = self.env().caller();
 
+        if !enabled && min_price == U512::zero() {
+            self.env().revert(Error::InvalidPrice);
+        }
+
         let mut sample = self.samples.get(&sample_id)
             .unwrap_or_else(|| self.env().revert(Error::SampleNotFound));
 
@@ -283,389 +388,3620 @@ impl SampledMarketplace {
             self.env().revert(Error::NotSeller);
         }
 
-        sample.is_active = false;
+        sample.pay_what_you_want = enabled;
+        sample.price = min_price;
         self.samples.set(&sample_id, sample);
 
-        self.env().emit_event(SampleDeactivated {
+        self.env().emit_event(PayWhatYouWantUpdated {
             sample_id,
-            seller: caller,
+            enabled,
+            min_price,
             timestamp: self.env().get_block_time(),
         });
     }
 
-    /// Withdraw accumulated earnings
-    pub fn withdraw_earnings(&mut self) {
+    /// Tip the producer of a sample with the attached CSPR
+    /// The tip is credited to the producer's earnings after the platform fee;
+    /// tips are tracked apart from sales volume and buyer spending, but the fee
+    /// counts toward the platform's fee totals. Delisted samples and samples
+    /// outside their sale window cannot be tipped
+    #[odra(payable)]
+    pub fn tip_producer(&mut self, seller: Address, sample_id: u64) {
         let caller = self.env().caller();
+        self.roles.require_not_paused();
+        let amount = self.env().attached_value();
 
-        let earnings = self.user_earnings.get_or_default(&caller);
-        if earnings == U512::zero() {
-            self.env().revert(Error::NoEarnings);
+        if amount == U512::zero() {
+            self.env().revert(Error::InvalidAmount);
         }
 
-        // Reset earnings before transfer (CEI pattern)
-        self.user_earnings.set(&caller, U512::zero());
+        let sample = self.samples.get(&sample_id)
+            .unwrap_or_else(|| self.env().revert(Error::SampleNotFound));
 
-        // Transfer earnings to user
-        self.env().transfer_tokens(&caller, &earnings);
+        if sample.seller != seller {
+            self.env().revert(Error::NotSeller);
+        }
+        self.ensure_not_blocked(&caller);
+        self.ensure_listed(&sample);
 
-        self.env().emit_event(EarningsWithdrawn {
-            user: caller,
-            amount: earnings,
+        let platform_fee = amount * PLATFORM_FEE_NUMERATOR / PLATFORM_FEE_DENOMINATOR;
+        let seller_amount = amount - platform_fee;
+        self.ledger.credit_earnings(seller, None, seller_amount);
+        self.ledger.record_tip(caller, seller, amount, seller_amount);
+        self.ledger.record_fee(None, platform_fee);
+
+        let admin = self.admin.get().unwrap();
+        self.ledger.pay_out(&admin, &None, platform_fee);
+
+        self.env().emit_event(TipReceived {
+            seller,
+            tipper: caller,
+            sample_id,
+            amount,
+            platform_fee,
             timestamp: self.env().get_block_time(),
         });
     }
 
-    // ============================================
-    // License System Entry Points
-    // ============================================
+    /// Deactivate a sample (soft delete)
+    pub fn deactivate_sample(&mut self, sample_id: u64) {
+        let caller = self.env().caller();
+        self.deactivate_sample_internal(caller, sample_id);
+    }
 
-    /// Set the License NFT contract address (admin only)
-    pub fn set_license_nft_contract(&mut self, license_contract: Address) {
+    /// Schedule or change the sale window of a sample (seller only)
+    pub fn set_sale_window(
+        &mut self,
+        sample_id: u64,
+        sale_starts_at: Option<u64>,
+        sale_ends_at: Option<u64>,
+    ) {
         let caller = self.env().caller();
-        let admin = self.admin.get().unwrap();
-        if caller != admin {
-            self.env().revert(Error::Unauthorized);
+
+        let mut sample = self.samples.get(&sample_id)
+            .unwrap_or_else(|| self.env().revert(Error::SampleNotFound));
+
+        if sample.seller != caller {
+            self.env().revert(Error::NotSeller);
         }
-        self.license_nft_contract.set(license_contract);
+
+        self.validate_sale_window(sale_starts_at, sale_ends_at);
+
+        sample.sale_starts_at = sale_starts_at;
+        sample.sale_ends_at = sale_ends_at;
+        self.samples.set(&sample_id, sample);
+
+        self.env().emit_event(SaleWindowUpdated {
+            sample_id,
+            sale_starts_at,
+            sale_ends_at,
+            timestamp: self.env().get_block_time(),
+        });
     }
 
-    /// Get the License NFT contract address
-    pub fn get_license_nft_contract(&self) -> Option<Address> {
-        self.license_nft_contract.get()
+    /// Withdraw accumulated earnings
+    pub fn withdraw_earnings(&mut self) {
+        let caller = self.env().caller();
+        self.withdraw_earnings_internal(caller, None);
     }
 
-    /// Purchase a sample license (mints a License NFT)
-    #[odra(payable)]
-    pub fn purchase_sample_license(&mut self, sample_id: u64, license_type: u8) {
+    /// Withdraw accumulated earnings in a CEP-18 token
+    pub fn withdraw_token_earnings(&mut self, token: Address) {
         let caller = self.env().caller();
-        let attached_value = self.env().attached_value();
+        self.withdraw_earnings_internal(caller, Some(token));
+    }
 
-        // Get sample
-        let mut sample = self.samples.get(&sample_id)
-            .unwrap_or_else(|| self.env().revert(Error::SampleNotFound));
+    // ============================================
+    // Meta-Transactions
+    // ============================================
 
-        // Validate sample is active
-        if !sample.is_active {
-            self.env().revert(Error::SampleInactive);
+    /// Run a seller action signed by `signer_key`, submitted (and paid for) by the caller
+    /// `signature` is the signer's signature over `get_meta_tx_message(meta_tx)`
+    pub fn execute_meta_transaction(
+        &mut self,
+        meta_tx: MetaTransaction,
+        signer_key: PublicKey,
+        signature: Bytes,
+    ) {
+        let relayer = self.env().caller();
+        if !self.meta_transactions.can_relay(&relayer) {
+            self.env().revert(Error::RelayerNotAllowed);
         }
 
-        // Cannot purchase own sample
-        if sample.seller == caller {
-            self.env().revert(Error::CannotPurchaseOwnSample);
+        let message = self.meta_tx_message(&meta_tx);
+        if !self.env().verify_signature(&message, &signature, &signer_key) {
+            self.env().revert(Error::InvalidMetaTxSignature);
+        }
+        let now = self.env().get_block_time();
+        if now >= meta_tx.deadline {
+            self.env().revert(Error::MetaTxExpired);
+        }
+        let signer = Address::from(signer_key);
+        if !self.meta_transactions.use_nonce(signer, meta_tx.nonce) {
+            self.env().revert(Error::InvalidMetaTxNonce);
         }
 
-        // Check for exclusive license by querying the LicenseNft contract
-        let license_contract_addr = self.license_nft_contract.get()
-            .unwrap_or_else(|| self.env().revert(Error::LicenseContractNotSet));
-        let mut license_nft = LicenseNftContractRef::new(self.env(), license_contract_addr);
-        if license_nft.is_exclusively_licensed(sample_id) {
-            self.env().revert(Error::SampleExclusivelyLicensed);
+        let action = MetaTxAction::from_u8(meta_tx.action)
+            .unwrap_or_else(|| self.env().revert(Error::InvalidMetaTxAction));
+        match action {
+            MetaTxAction::UploadSample => {
+                let (
+                    (price, pay_what_you_want),
+                    ipfs_link,
+                    title,
+                    bpm,
+                    genre,
+                    cover_image,
+                    video_preview_link,
+                    (sale_starts_at, sale_ends_at),
+                    (content_hash, fingerprint),
+                    pricing_template,
+                ) = self.decode_meta_tx_args(&meta_tx.args);
+                self.upload_sample_internal(
+                    signer,
+                    price,
+                    pay_what_you_want,
+                    ipfs_link,
+                    title,
+                    bpm,
+                    genre,
+                    cover_image,
+                    video_preview_link,
+                    sale_starts_at,
+                    sale_ends_at,
+                    content_hash,
+                    fingerprint,
+                    pricing_template,
+                );
+            }
+            MetaTxAction::UpdatePrice => {
+                let (sample_id, new_price) = self.decode_meta_tx_args(&meta_tx.args);
+                self.update_price_internal(signer, sample_id, new_price);
+            }
+            MetaTxAction::DeactivateSample => {
+                let sample_id = self.decode_meta_tx_args(&meta_tx.args);
+                self.deactivate_sample_internal(signer, sample_id);
+            }
+            MetaTxAction::SetLicensePricing => {
+                let (sample_id, personal_mult, commercial_mult, broadcast_mult, exclusive_mult) =
+                    self.decode_meta_tx_args(&meta_tx.args);
+                self.set_license_pricing_internal(
+                    signer,
+                    sample_id,
+                    personal_mult,
+                    commercial_mult,
+                    broadcast_mult,
+                    exclusive_mult,
+                );
+            }
+            MetaTxAction::WithdrawEarnings => {
+                let token = self.decode_meta_tx_args(&meta_tx.args);
+                self.withdraw_earnings_internal(signer, token);
+            }
         }
 
-        // Parse license type
-        let lt = LicenseType::from_u8(license_type)
-            .unwrap_or_else(|| self.env().revert(Error::InvalidLicenseType));
+        self.env().emit_event(MetaTransactionExecuted {
+            signer,
+            relayer,
+            action: meta_tx.action,
+            nonce: meta_tx.nonce,
+            timestamp: now,
+        });
+    }
 
-        // Calculate license price
-        let license_price = self.calculate_license_price(sample_id, &lt, sample.price);
+    /// Get the bytes a user signs for a meta-transaction (bound to this marketplace)
+    pub fn get_meta_tx_message(&self, meta_tx: MetaTransaction) -> Bytes {
+        self.meta_tx_message(&meta_tx)
+    }
 
-        // Validate payment
-        if attached_value < license_price {
-            self.env().revert(Error::InsufficientPayment);
-        }
+    /// Get the next meta-transaction nonce expected from a user
+    pub fn get_meta_tx_nonce(&self, user: Address) -> u64 {
+        self.meta_transactions.nonce(&user)
+    }
 
-        // License contract reference already obtained above
+    /// Skip the caller's current meta-transaction nonce, cancelling any action signed with it
+    pub fn bump_meta_tx_nonce(&mut self) {
+        let caller = self.env().caller();
+        let nonce = self.meta_transactions.bump_nonce(caller);
 
-        // Calculate fees
-        let platform_fee = license_price * PLATFORM_FEE_NUMERATOR / PLATFORM_FEE_DENOMINATOR;
-        let seller_amount = license_price - platform_fee;
+        self.env().emit_event(MetaTxNonceBumped {
+            user: caller,
+            nonce,
+            timestamp: self.env().get_block_time(),
+        });
+    }
 
-        // Update sample stats
-        sample.total_sales += 1;
-        self.samples.set(&sample_id, sample.clone());
+    /// Restrict meta-transactions to allowlisted relayers, or open them to anyone (admin only)
+    pub fn set_relayer_allowlist_enabled(&mut self, enabled: bool) {
+        self.roles.assert_role(&ADMIN_ROLE);
 
-        // Update marketplace stats
-        let total_volume = self.total_volume.get_or_default() + license_price;
-        self.total_volume.set(total_volume);
-        let fee_collected = self.platform_fee_collected.get_or_default() + platform_fee;
-        self.platform_fee_collected.set(fee_collected);
+        self.meta_transactions.set_allowlist_enabled(enabled);
 
-        // Update buyer stats
-        let buyer_count = self.user_purchased_count.get_or_default(&caller);
-        self.user_purchased_at.set(&(caller, buyer_count), sample_id);
-        self.user_purchased_count.set(&caller, buyer_count + 1);
-        let buyer_spent = self.user_total_spent.get_or_default(&caller) + license_price;
-        self.user_total_spent.set(&caller, buyer_spent);
+        self.env().emit_event(RelayerUpdated {
+            relayer: None,
+            allowed: false,
+            allowlist_enabled: enabled,
+            timestamp: self.env().get_block_time(),
+        });
+    }
 
-        // Store purchase record
-        let timestamp = self.env().get_block_time();
-        let purchase_record = PurchaseRecord {
-            sample_id,
-            seller: sample.seller,
-            price: license_price,
-            timestamp,
-            ipfs_link: sample.ipfs_link.clone(),
-        };
-        self.user_purchase_records.set(&(caller, sample_id), purchase_record);
+    /// Add or remove a relayer from the allowlist (admin only)
+    pub fn set_relayer(&mut self, relayer: Address, allowed: bool) {
+        self.roles.assert_role(&ADMIN_ROLE);
 
-        // Update seller's earnings
-        let seller_earnings = self.user_earnings.get_or_default(&sample.seller) + seller_amount;
-        self.user_earnings.set(&sample.seller, seller_earnings);
-        let seller_total = self.user_total_earned.get_or_default(&sample.seller) + seller_amount;
-        self.user_total_earned.set(&sample.seller, seller_total);
+        self.meta_transactions.set_relayer(relayer, allowed);
 
-        // Transfer platform fee to admin
-        let admin = self.admin.get().unwrap();
-        self.env().transfer_tokens(&admin, &platform_fee);
+        self.env().emit_event(RelayerUpdated {
+            relayer: Some(relayer),
+            allowed,
+            allowlist_enabled: self.meta_transactions.is_allowlist_enabled(),
+            timestamp: self.env().get_block_time(),
+        });
+    }
 
-        // Handle exclusive license - deactivate sample
-        if lt.to_u8() == LicenseType::Exclusive.to_u8() {
-            // Deactivate sample from marketplace
-            let mut sample = self.samples.get(&sample_id).unwrap();
-            sample.is_active = false;
-            self.samples.set(&sample_id, sample);
-        }
+    /// Check if an address may submit meta-transactions
+    pub fn can_relay(&self, relayer: Address) -> bool {
+        self.meta_transactions.can_relay(&relayer)
+    }
 
-        // Mint license NFT (LicenseNft contract handles exclusive tracking)
-        license_nft.mint_license(
-            sample_id,
-            license_type,
-            caller,
-            sample.seller,
-            license_price,
-        );
+    /// Check if only allowlisted relayers may submit meta-transactions
+    pub fn is_relayer_allowlist_enabled(&self) -> bool {
+        self.meta_transactions.is_allowlist_enabled()
+    }
 
-        // Emit event
-        self.env().emit_event(SamplePurchased {
-            sample_id,
-            buyer: caller,
-            seller: sample.seller,
-            price: license_price,
-            platform_fee,
-            timestamp,
+    // ============================================
+    // Payment Administration
+    // ============================================
+
+    /// Add or remove a CEP-18 token from the payment allowlist (treasurer only)
+    pub fn set_accepted_token(&mut self, token: Address, accepted: bool) {
+        self.roles.assert_role(&TREASURER_ROLE);
+
+        self.ledger.set_accepted(token, accepted);
+
+        self.env().emit_event(AcceptedTokenUpdated {
+            token,
+            accepted,
+            timestamp: self.env().get_block_time(),
         });
     }
 
-    /// Set custom license pricing for a sample (seller only)
-    pub fn set_license_pricing(
-        &mut self,
-        sample_id: u64,
-        personal_mult: u64,
-        commercial_mult: u64,
-        broadcast_mult: u64,
-        exclusive_mult: u64,
-    ) {
-        let caller = self.env().caller();
-
-        // Validate multipliers
-        if personal_mult == 0 || commercial_mult == 0 || broadcast_mult == 0 || exclusive_mult == 0 {
-            self.env().revert(Error::InvalidPricingMultiplier);
-        }
+    /// Check if a CEP-18 token is accepted for payments
+    pub fn is_accepted_token(&self, token: Address) -> bool {
+        self.ledger.is_accepted(&Some(token))
+    }
 
-        // Get sample and verify ownership
-        let sample = self.samples.get(&sample_id)
-            .unwrap_or_else(|| self.env().revert(Error::SampleNotFound));
+    /// Get all CEP-18 tokens accepted for payments
+    pub fn get_accepted_tokens(&self) -> Vec<Address> {
+        self.ledger.accepted_tokens()
+    }
 
-        if sample.seller != caller {
-            self.env().revert(Error::NotSeller);
-        }
+    // ============================================
+    // Price Oracle
+    // ============================================
 
-        // Set pricing
-        let pricing = LicensePricing {
-            personal_multiplier: personal_mult,
-            commercial_multiplier: commercial_mult,
-            broadcast_multiplier: broadcast_mult,
-            exclusive_multiplier: exclusive_mult,
-        };
-        self.sample_license_pricing.set(&sample_id, pricing);
+    /// Set the CSPR/USD price feed contract (pricing manager only)
+    ///
+    /// `max_staleness` is the maximum age of a price round (milliseconds);
+    /// `max_deviation_bps` limits the change between consecutive price rounds
+    /// of the feed (0 = no limit).
+    pub fn set_price_feed(&mut self, feed: Address, max_staleness: u64, max_deviation_bps: u64) {
+        self.roles.assert_role(&PRICING_MANAGER_ROLE);
+
+        self.price_oracle.set_config(PriceFeedConfig {
+            feed,
+            max_staleness,
+            max_deviation_bps,
+        });
 
-        self.env().emit_event(LicensePricingUpdated {
-            sample_id,
-            personal_mult,
-            commercial_mult,
-            broadcast_mult,
-            exclusive_mult,
+        self.env().emit_event(PriceFeedUpdated {
+            feed,
+            max_staleness,
+            max_deviation_bps,
             timestamp: self.env().get_block_time(),
         });
     }
 
-    /// Get the price for a specific license type
-    pub fn get_license_price(&self, sample_id: u64, license_type: u8) -> U512 {
-        if let Some(sample) = self.samples.get(&sample_id) {
-            let lt = LicenseType::from_u8(license_type).unwrap_or(LicenseType::Personal);
-            self.calculate_license_price(sample_id, &lt, sample.price)
-        } else {
-            U512::zero()
+    /// Get the price feed configuration
+    pub fn get_price_feed(&self) -> Option<PriceFeedConfig> {
+        self.price_oracle.config()
+    }
+
+    /// Get the current motes-per-USD rate (zero if the feed is unset, stale or out of bounds)
+    pub fn get_usd_rate(&self) -> U512 {
+        self.price_oracle.current_rate().unwrap_or_default()
+    }
+
+    // ============================================
+    // Prepaid Credit
+    // ============================================
+
+    /// Deposit attached CSPR as prepaid credit for later purchases
+    #[odra(payable)]
+    pub fn deposit_credit(&mut self) {
+        let caller = self.env().caller();
+        self.roles.require_not_paused();
+        let amount = self.env().attached_value();
+        if amount == U512::zero() {
+            self.env().revert(Error::InvalidAmount);
         }
+
+        let balance = self.ledger.add_credit(caller, amount);
+
+        self.env().emit_event(CreditDeposited {
+            user: caller,
+            amount,
+            balance,
+            timestamp: self.env().get_block_time(),
+        });
     }
 
-    /// Get all license prices for a sample
-    pub fn get_all_license_prices(&self, sample_id: u64) -> AllLicensePrices {
-        if let Some(sample) = self.samples.get(&sample_id) {
-            AllLicensePrices {
-                personal: self.calculate_license_price(sample_id, &LicenseType::Personal, sample.price),
-                commercial: self.calculate_license_price(sample_id, &LicenseType::Commercial, sample.price),
-                broadcast: self.calculate_license_price(sample_id, &LicenseType::Broadcast, sample.price),
-                exclusive: self.calculate_license_price(sample_id, &LicenseType::Exclusive, sample.price),
-            }
-        } else {
-            AllLicensePrices::default()
+    /// Withdraw unused prepaid credit
+    pub fn withdraw_credit(&mut self, amount: U512) {
+        let caller = self.env().caller();
+        if amount == U512::zero() {
+            self.env().revert(Error::InvalidAmount);
         }
+
+        // Deduct before transfer (CEI pattern)
+        let balance = self.ledger.debit_credit(caller, amount);
+        self.ledger.pay_out(&caller, &None, amount);
+
+        self.env().emit_event(CreditWithdrawn {
+            user: caller,
+            amount,
+            balance,
+            timestamp: self.env().get_block_time(),
+        });
     }
 
-    /// Get license pricing multipliers for a sample
-    pub fn get_license_pricing(&self, sample_id: u64) -> LicensePricing {
-        self.sample_license_pricing.get(&sample_id)
-            .unwrap_or_else(LicensePricing::default_pricing)
+    /// Purchase a CSPR-priced sample using prepaid credit
+    pub fn purchase_sample_with_credit(&mut self, sample_id: u64, max_price: Option<U512>) {
+        let caller = self.env().caller();
+        self.purchase_sample_internal(caller, sample_id, max_price, PaymentMethod::Credit, None);
     }
 
-    /// Check if a sample has an exclusive license
-    pub fn is_exclusively_licensed(&self, sample_id: u64) -> bool {
-        if let Some(license_contract_addr) = self.license_nft_contract.get() {
-            let license_nft = LicenseNftContractRef::new(self.env(), license_contract_addr);
-            license_nft.is_exclusively_licensed(sample_id)
-        } else {
-            false
-        }
+    /// Purchase a license for a CSPR-priced sample using prepaid credit
+    pub fn purchase_sample_license_with_credit(
+        &mut self,
+        sample_id: u64,
+        license_type: u8,
+        accepted_terms: Option<String>,
+        territories: Vec<String>,
+        max_price: Option<U512>,
+    ) {
+        let caller = self.env().caller();
+        self.purchase_license_internal(caller, sample_id, license_type, accepted_terms, territories, max_price, PaymentMethod::Credit, None);
     }
 
-    /// Get the exclusive license holder for a sample
-    pub fn get_exclusive_holder(&self, sample_id: u64) -> Option<Address> {
-        if let Some(license_contract_addr) = self.license_nft_contract.get() {
-            let license_nft = LicenseNftContractRef::new(self.env(), license_contract_addr);
-            license_nft.get_exclusive_holder(sample_id)
-        } else {
-            None
-        }
+    /// Get a buyer's prepaid credit balance
+    pub fn get_credit_balance(&self, user: Address) -> U512 {
+        self.ledger.credit(&user)
     }
 
     // ============================================
-    // View Functions
+    // Subscriptions
     // ============================================
 
-    /// Get a sample by ID
-    pub fn get_sample(&self, sample_id: u64) -> Option<Sample> {
-        self.samples.get(&sample_id)
+    /// Create a subscription plan for the caller's catalog
+    /// `license_type` is the access granted on every sample; plans only grant
+    /// Personal use, so it must be `LicenseType::Personal`
+    pub fn create_subscription_plan(&mut self, price: U512, period: u64, license_type: u8) {
+        let caller = self.env().caller();
+        self.validate_subscription_plan(price, period, license_type);
+
+        let plan = SubscriptionPlan {
+            plan_id: self.subscriptions.next_plan_id(),
+            producer: caller,
+            price,
+            period,
+            license_type,
+            is_active: true,
+            created_at: self.env().get_block_time(),
+        };
+        self.subscriptions.add_plan(plan.clone());
+        self.emit_plan_updated(&plan);
     }
 
-    /// Get user statistics
-    pub fn get_user_stats(&self, user: Address) -> UserStats {
-        UserStats {
-            uploaded_count: self.user_uploaded_count.get_or_default(&user),
-            purchased_count: self.user_purchased_count.get_or_default(&user),
-            earnings: self.user_earnings.get_or_default(&user),
-            total_earned: self.user_total_earned.get_or_default(&user),
-            total_spent: self.user_total_spent.get_or_default(&user),
+    /// Update a subscription plan's terms (producer only)
+    /// Existing subscriptions keep their expiry; new terms apply from the next payment
+    pub fn update_subscription_plan(
+        &mut self,
+        plan_id: u64,
+        price: U512,
+        period: u64,
+        license_type: u8,
+        is_active: bool,
+    ) {
+        let caller = self.env().caller();
+        self.validate_subscription_plan(price, period, license_type);
+
+        let mut plan = self.subscriptions.plan(plan_id)
+            .unwrap_or_else(|| self.env().revert(Error::PlanNotFound));
+        if plan.producer != caller {
+            self.env().revert(Error::NotSeller);
         }
+
+        plan.price = price;
+        plan.period = period;
+        plan.license_type = license_type;
+        plan.is_active = is_active;
+        self.subscriptions.update_plan(plan.clone());
+        self.emit_plan_updated(&plan);
     }
 
-    /// Get marketplace statistics
-    pub fn get_marketplace_stats(&self) -> MarketplaceStats {
-        MarketplaceStats {
-            sample_count: self.sample_count.get_or_default(),
-            total_volume: self.total_volume.get_or_default(),
-            platform_fee_collected: self.platform_fee_collected.get_or_default(),
+    /// Subscribe to a producer plan for one period
+    #[odra(payable)]
+    pub fn subscribe(&mut self, plan_id: u64) {
+        let caller = self.env().caller();
+        let plan = self.active_plan(plan_id);
+
+        if plan.producer == caller {
+            self.env().revert(Error::CannotSubscribeToSelf);
+        }
+        let now = self.env().get_block_time();
+        if self.subscriptions.is_subscribed(&caller, &plan.producer, now) {
+            self.env().revert(Error::AlreadySubscribed);
         }
-    }
 
-    /// Check if a user has purchased a specific sample
-    pub fn has_purchased(&self, buyer: Address, sample_id: u64) -> bool {
-        self.has_purchased_internal(&buyer, sample_id)
+        let subscription = Subscription {
+            plan_id,
+            license_type: plan.license_type,
+            started_at: now,
+            expires_at: now + plan.period,
+        };
+        self.settle_subscription(caller, &plan, subscription, false);
     }
 
-    /// Get user's uploaded sample IDs
-    pub fn get_user_samples(&self, user: Address) -> Vec<u64> {
-        let count = self.user_uploaded_count.get_or_default(&user);
-        let mut result = Vec::new();
-        for i in 0..count {
-            if let Some(id) = self.user_uploaded_at.get(&(user, i)) {
-                result.push(id);
-            }
+    /// Renew a subscription for one more period
+    /// Extends from the current expiry, or from now if the subscription has lapsed
+    #[odra(payable)]
+    pub fn renew(&mut self, plan_id: u64) {
+        let caller = self.env().caller();
+        let plan = self.active_plan(plan_id);
+
+        let mut subscription = self.subscriptions.subscription(&caller, &plan.producer)
+            .filter(|subscription| subscription.plan_id == plan_id)
+            .unwrap_or_else(|| self.env().revert(Error::SubscriptionNotFound));
+
+        let now = self.env().get_block_time();
+        if !subscription.is_active(now) {
+            subscription.started_at = now;
         }
-        result
+        subscription.expires_at = subscription.expires_at.max(now) + plan.period;
+        subscription.license_type = plan.license_type;
+        self.settle_subscription(caller, &plan, subscription, true);
     }
 
-    /// Get user's purchased sample IDs
-    pub fn get_user_purchases(&self, user: Address) -> Vec<u64> {
-        let count = self.user_purchased_count.get_or_default(&user);
-        let mut result = Vec::new();
-        for i in 0..count {
-            if let Some(id) = self.user_purchased_at.get(&(user, i)) {
-                result.push(id);
-            }
-        }
-        result
+    /// Get a subscription plan by ID
+    pub fn get_subscription_plan(&self, plan_id: u64) -> Option<SubscriptionPlan> {
+        self.subscriptions.plan(plan_id)
     }
 
-    /// Get user's available earnings
-    pub fn get_earnings(&self, user: Address) -> U512 {
-        self.user_earnings.get_or_default(&user)
+    /// Get all subscription plans created by a producer
+    pub fn get_producer_plans(&self, producer: Address) -> Vec<SubscriptionPlan> {
+        self.subscriptions.producer_plans(&producer)
     }
 
-    /// Get all active samples
-    pub fn get_all_samples(&self) -> Vec<Sample> {
-        let count = self.sample_count.get_or_default();
-        let mut result = Vec::new();
-        for id in 1..=count {
-            if let Some(sample) = self.samples.get(&id) {
-                if sample.is_active {
-                    result.push(sample);
-                }
-            }
-        }
-        result
+    /// Get a user's subscription to a producer (active or lapsed)
+    pub fn get_subscription(&self, subscriber: Address, producer: Address) -> Option<Subscription> {
+        self.subscriptions.subscription(&subscriber, &producer)
     }
 
-    /// Get full sample data for user's uploaded samples
-    pub fn get_user_samples_full(&self, user: Address) -> Vec<Sample> {
-        let sample_ids = self.get_user_samples(user);
-        let mut result = Vec::new();
-        for id in sample_ids {
-            if let Some(sample) = self.samples.get(&id) {
-                result.push(sample);
-            }
-        }
-        result
+    /// Check if a user has an active subscription to a producer
+    pub fn is_subscribed(&self, subscriber: Address, producer: Address) -> bool {
+        self.subscriptions.is_subscribed(&subscriber, &producer, self.env().get_block_time())
     }
 
-    /// Get full sample data for user's purchased samples
-    pub fn get_user_purchases_full(&self, user: Address) -> Vec<Sample> {
-        let sample_ids = self.get_user_purchases(user);
-        let mut result = Vec::new();
-        for id in sample_ids {
-            if let Some(sample) = self.samples.get(&id) {
-                result.push(sample);
-            }
+    /// Check if a user may use a sample: purchased it, holds a license NFT for it,
+    /// or has an active subscription to its producer granting Personal use
+    pub fn has_access(&self, user: Address, sample_id: u64) -> bool {
+        let sample = match self.samples.get(&sample_id) {
+            Some(sample) => sample,
+            None => return false,
+        };
+
+        if sample.seller == user || self.has_purchased_internal(&user, sample_id) {
+            return true;
         }
-        result
-    }
 
-    /// Get the admin address
-    pub fn get_admin(&self) -> Option<Address> {
-        self.admin.get()
+        // Subscriptions do not cover samples removed by moderators
+        let personal = LicenseType::Personal.to_u8();
+        if !sample.is_delisted
+            && self.subscriptions.grants(&user, &sample.seller, personal, self.env().get_block_time())
+        {
+            return true;
+        }
+
+        if let Some(license_contract_addr) = self.license_nft_contract.get() {
+            let license_nft = LicenseNftContractRef::new(self.env(), license_contract_addr);
+            return license_nft.holds_any_license(user, sample_id);
+        }
+
+        false
     }
 
     // ============================================
-    // Internal Functions
+    // Referrals
     // ============================================
 
-    fn has_purchased_internal(&self, buyer: &Address, sample_id: u64) -> bool {
-        self.user_purchase_records.get(&(*buyer, sample_id)).is_some()
+    /// Set the share of the platform fee paid to referrers, in basis points (treasurer only)
+    pub fn set_referral_share(&mut self, share_bps: u64) {
+        self.roles.assert_role(&TREASURER_ROLE);
+        if share_bps > BPS_DENOMINATOR {
+            self.env().revert(Error::InvalidReferralRate);
+        }
+
+        self.referrals.set_platform_share(share_bps);
+
+        self.env().emit_event(ReferralShareUpdated {
+            share_bps,
+            timestamp: self.env().get_block_time(),
+        });
     }
 
-    /// Calculate the license price based on sample base price and license type
-    fn calculate_license_price(&self, sample_id: u64, license_type: &LicenseType, base_price: U512) -> U512 {
-        // Get custom pricing or use defaults
-        let pricing = self.sample_license_pricing.get(&sample_id)
-            .unwrap_or_else(LicensePricing::default_pricing);
+    /// Set the caller's referral bonus, in basis points of their proceeds from referred sales
+    pub fn set_referral_bonus(&mut self, bonus_bps: u64) {
+        let caller = self.env().caller();
+        if bonus_bps > BPS_DENOMINATOR {
+            self.env().revert(Error::InvalidReferralRate);
+        }
+
+        self.referrals.set_seller_bonus(caller, bonus_bps);
+
+        self.env().emit_event(SellerReferralBonusUpdated {
+            seller: caller,
+            bonus_bps,
+            timestamp: self.env().get_block_time(),
+        });
+    }
 
-        pricing.calculate_price(base_price, license_type)
+    /// Get the share of the platform fee paid to referrers (bps)
+    pub fn get_referral_share(&self) -> u64 {
+        self.referrals.platform_share_bps()
     }
-}
 
-// ============================================
-// Tests - TODO: Fix test configuration for cross-contract references
-// ============================================
+    /// Get a seller's referral bonus (bps)
+    pub fn get_referral_bonus(&self, seller: Address) -> u64 {
+        self.referrals.seller_bonus_bps(&seller)
+    }
 
-// Tests are temporarily disabled due to Odra macro limitations with cross-contract references.
-// The contracts compile and build successfully. Integration tests should be run separately.
+    /// Get referral statistics for a referrer (CSPR commission)
+    pub fn get_referrer_stats(&self, referrer: Address) -> ReferrerStats {
+        ReferrerStats {
+            referral_count: self.referrals.referral_count(&referrer),
+            total_earned: self.referrals.referral_earned(&referrer, &None),
+        }
+    }
+
+    /// Get a referrer's lifetime commission in a CEP-18 token
+    pub fn get_token_referral_earnings(&self, referrer: Address, token: Address) -> U512 {
+        self.referrals.referral_earned(&referrer, &Some(token))
+    }
+
+    // ============================================
+    // Moderation
+    // ============================================
+
+    /// Report a sample to the moderators with a reason code (see `ReportReason`)
+    pub fn report_sample(&mut self, sample_id: u64, reason_code: u8) {
+        let caller = self.env().caller();
+
+        if self.samples.get(&sample_id).is_none() {
+            self.env().revert(Error::SampleNotFound);
+        }
+        if ReportReason::from_u8(reason_code).is_none() {
+            self.env().revert(Error::InvalidReportReason);
+        }
+        if self.moderation.has_open_report(&caller, sample_id) {
+            self.env().revert(Error::AlreadyReported);
+        }
+
+        let timestamp = self.env().get_block_time();
+        let report_id = self.moderation.add_report(sample_id, caller, reason_code, timestamp);
+
+        self.env().emit_event(SampleReported {
+            report_id,
+            sample_id,
+            reporter: caller,
+            reason_code,
+            timestamp,
+        });
+    }
+
+    /// Close an open report (moderator only)
+    /// `upheld` marks it resolved; otherwise it is dismissed. Delisting is a separate action.
+    pub fn resolve_report(&mut self, report_id: u64, upheld: bool) {
+        let moderator = self.assert_moderator();
+
+        let report = self.moderation.report(report_id)
+            .unwrap_or_else(|| self.env().revert(Error::ReportNotFound));
+        if !matches!(report.status, ReportStatus::Open) {
+            self.env().revert(Error::ReportAlreadyClosed);
+        }
+
+        let status = if upheld { ReportStatus::Resolved } else { ReportStatus::Dismissed };
+        let sample_id = report.sample_id;
+        let timestamp = self.env().get_block_time();
+        self.moderation.close_report(report, status, moderator, timestamp);
+
+        self.env().emit_event(ReportClosed {
+            report_id,
+            sample_id,
+            status: status.to_u8(),
+            moderator,
+            timestamp,
+        });
+    }
+
+    /// Remove a sample from sale (moderator only)
+    /// Its content hash stays reserved; moderators free it with `release_content_hash`
+    /// or `reassign_content_hash`
+    pub fn delist_sample(&mut self, sample_id: u64) {
+        self.set_delisted(sample_id, true);
+    }
+
+    /// Put a delisted sample back on sale (moderator only)
+    pub fn restore_sample(&mut self, sample_id: u64) {
+        self.set_delisted(sample_id, false);
+    }
+
+    /// Put a sample taken off sale by a perpetual exclusive license back on sale (moderator only),
+    /// e.g. after that license was revoked. Reverts while an exclusive license is still in force
+    pub fn reactivate_sample(&mut self, sample_id: u64) {
+        let moderator = self.assert_moderator();
+
+        let mut sample = self.samples.get(&sample_id)
+            .unwrap_or_else(|| self.env().revert(Error::SampleNotFound));
+        if sample.is_active {
+            self.env().revert(Error::SampleAlreadyActive);
+        }
+        if self.is_exclusively_licensed(sample_id) {
+            self.env().revert(Error::SampleExclusivelyLicensed);
+        }
+
+        sample.is_active = true;
+        self.samples.set(&sample_id, sample);
+
+        self.env().emit_event(SampleReactivated {
+            sample_id,
+            moderator,
+            timestamp: self.env().get_block_time(),
+        });
+    }
+
+    /// Release a content hash so the audio can be uploaded again (moderator only)
+    pub fn release_content_hash(&mut self, content_hash: String) {
+        let moderator = self.assert_moderator();
+        self.set_content_hash_sample(content_hash, None, moderator);
+    }
+
+    /// Register a content hash to another sample uploaded with it (moderator only),
+    /// e.g. to the original producer after a dispute
+    pub fn reassign_content_hash(&mut self, content_hash: String, sample_id: u64) {
+        let moderator = self.assert_moderator();
+
+        let sample = self.samples.get(&sample_id)
+            .unwrap_or_else(|| self.env().revert(Error::SampleNotFound));
+        if sample.content_hash != content_hash {
+            self.env().revert(Error::ContentHashMismatch);
+        }
+
+        self.set_content_hash_sample(content_hash, Some(sample_id), moderator);
+    }
+
+    /// Add or remove an address from the upload/purchase blocklist (moderator only)
+    pub fn set_blocked(&mut self, account: Address, blocked: bool) {
+        let moderator = self.assert_moderator();
+
+        self.moderation.set_blocked(account, blocked);
+
+        self.env().emit_event(AddressBlockUpdated {
+            account,
+            blocked,
+            moderator,
+            timestamp: self.env().get_block_time(),
+        });
+    }
+
+    /// Get a report by ID
+    pub fn get_report(&self, report_id: u64) -> Option<Report> {
+        self.moderation.report(report_id)
+    }
+
+    /// Get all reports awaiting moderator review
+    pub fn get_open_reports(&self) -> Vec<Report> {
+        self.moderation.open_reports()
+    }
+
+    /// Check if an address can act as a moderator
+    pub fn is_moderator(&self, account: Address) -> bool {
+        self.roles.is_authorized(&MODERATOR_ROLE, &account)
+    }
+
+    /// Check if an address is blocked from uploading or buying
+    pub fn is_blocked(&self, account: Address) -> bool {
+        self.moderation.is_blocked(&account)
+    }
+
+    // ============================================
+    // License System Entry Points
+    // ============================================
+
+    /// Set the License NFT contract address (admin only)
+    pub fn set_license_nft_contract(&mut self, license_contract: Address) {
+        self.roles.assert_role(&ADMIN_ROLE);
+        self.license_nft_contract.set(license_contract);
+    }
+
+    /// Get the License NFT contract address
+    pub fn get_license_nft_contract(&self) -> Option<Address> {
+        self.license_nft_contract.get()
+    }
+
+    /// Purchase a sample license (mints a License NFT)
+    /// `accepted_terms` is the terms hash the buyer agreed to; it must match the tier's terms in force
+    /// `territories` restricts the license to territories the seller has priced (empty = worldwide)
+    /// `max_price` reverts the purchase if the license price has risen above it since the buyer signed
+    #[odra(payable)]
+    pub fn purchase_sample_license(
+        &mut self,
+        sample_id: u64,
+        license_type: u8,
+        accepted_terms: Option<String>,
+        territories: Vec<String>,
+        max_price: Option<U512>,
+    ) {
+        let caller = self.env().caller();
+        self.purchase_license_internal(caller, sample_id, license_type, accepted_terms, territories, max_price, PaymentMethod::Attached, None);
+    }
+
+    /// Purchase a license for a sample priced in a CEP-18 token
+    /// The buyer must first approve this contract to spend the license price
+    pub fn purchase_sample_license_with_token(
+        &mut self,
+        sample_id: u64,
+        license_type: u8,
+        accepted_terms: Option<String>,
+        territories: Vec<String>,
+        max_price: Option<U512>,
+    ) {
+        let caller = self.env().caller();
+        self.purchase_license_internal(caller, sample_id, license_type, accepted_terms, territories, max_price, PaymentMethod::Token, None);
+    }
+
+    /// Purchase a sample license and credit a referrer with a commission
+    /// Pays in the sample's currency: attached CSPR, or previously approved CEP-18 tokens
+    #[odra(payable)]
+    pub fn purchase_sample_license_with_referrer(
+        &mut self,
+        sample_id: u64,
+        license_type: u8,
+        accepted_terms: Option<String>,
+        territories: Vec<String>,
+        max_price: Option<U512>,
+        referrer: Address,
+    ) {
+        let caller = self.env().caller();
+        let method = self.default_payment_method(sample_id);
+        self.purchase_license_internal(caller, sample_id, license_type, accepted_terms, territories, max_price, method, Some(referrer));
+    }
+
+    /// Grant free promotional licenses to a list of recipients (seller only)
+    /// Respects exclusivity and supply caps; not counted as sales or volume
+    pub fn grant_licenses(&mut self, sample_id: u64, license_type: u8, recipients: Vec<Address>) {
+        let caller = self.env().caller();
+        self.roles.require_not_paused();
+
+        let mut sample = self.samples.get(&sample_id)
+            .unwrap_or_else(|| self.env().revert(Error::SampleNotFound));
+
+        if sample.seller != caller {
+            self.env().revert(Error::NotSeller);
+        }
+        if sample.is_delisted {
+            self.env().revert(Error::SampleDelisted);
+        }
+
+        let tier = self.license_tier(license_type)
+            .filter(|tier| tier.is_active)
+            .unwrap_or_else(|| self.env().revert(Error::InvalidLicenseType));
+        let is_exclusive = tier.is_exclusive;
+
+        if recipients.is_empty()
+            || recipients.len() > MAX_GRANT_RECIPIENTS
+            || (is_exclusive && recipients.len() > 1)
+            || recipients.contains(&caller)
+        {
+            self.env().revert(Error::InvalidRecipients);
+        }
+
+        let license_contract_addr = self.license_nft_contract.get()
+            .unwrap_or_else(|| self.env().revert(Error::LicenseContractNotSet));
+        let mut license_nft = LicenseNftContractRef::new(self.env(), license_contract_addr);
+
+        // Granted licenses carry the terms in force for the tier
+        let terms_hash = license_nft.get_license_terms(sample_id, license_type);
+
+        // LicenseNft enforces exclusivity, duplicates and supply caps per mint
+        for recipient in recipients.iter() {
+            license_nft.mint_license(
+                sample_id,
+                license_type,
+                *recipient,
+                caller,
+                U512::zero(),
+                sample.currency,
+                true,
+                terms_hash.clone(),
+                Vec::new(),
+            );
+        }
+
+        // A perpetual exclusive grant takes the sample off the marketplace, as a purchase would
+        if is_exclusive && tier.rights.duration.is_none() {
+            sample.is_active = false;
+            self.samples.set(&sample_id, sample);
+        }
+
+        self.env().emit_event(PromotionalLicensesGranted {
+            sample_id,
+            seller: caller,
+            license_type,
+            recipients,
+            timestamp: self.env().get_block_time(),
+        });
+    }
+
+    /// Set custom license pricing for a sample (seller only)
+    pub fn set_license_pricing(
+        &mut self,
+        sample_id: u64,
+        personal_mult: u64,
+        commercial_mult: u64,
+        broadcast_mult: u64,
+        exclusive_mult: u64,
+    ) {
+        let caller = self.env().caller();
+        self.set_license_pricing_internal(caller, sample_id, personal_mult, commercial_mult, broadcast_mult, exclusive_mult);
+    }
+
+    /// Set the multiplier of a custom license tier for a sample (seller only)
+    /// Built-in tiers are priced with `set_license_pricing`
+    pub fn set_tier_multiplier(&mut self, sample_id: u64, tier_id: u8, multiplier: u64) {
+        let caller = self.env().caller();
+
+        if LicenseType::from_u8(tier_id).is_some() || self.license_tier(tier_id).is_none() {
+            self.env().revert(Error::InvalidLicenseType);
+        }
+        if multiplier == 0 {
+            self.env().revert(Error::InvalidPricingMultiplier);
+        }
+
+        let sample = self.samples.get(&sample_id)
+            .unwrap_or_else(|| self.env().revert(Error::SampleNotFound));
+
+        if sample.seller != caller {
+            self.env().revert(Error::NotSeller);
+        }
+
+        let mut pricing = self.license_pricing.effective_multipliers(sample_id, &caller);
+        pricing.set_tier_multiplier(tier_id, multiplier);
+        self.apply_license_pricing(sample_id, pricing);
+    }
+
+    /// Set fixed absolute license prices for a sample (seller only)
+    /// Switches the sample to absolute pricing mode; prices are in motes,
+    /// so the sample must be priced in CSPR (and stays so while the mode is active)
+    pub fn set_absolute_license_pricing(
+        &mut self,
+        sample_id: u64,
+        personal_price: U512,
+        commercial_price: U512,
+        broadcast_price: U512,
+        exclusive_price: U512,
+    ) {
+        let caller = self.env().caller();
+
+        // Validate prices
+        if personal_price == U512::zero()
+            || commercial_price == U512::zero()
+            || broadcast_price == U512::zero()
+            || exclusive_price == U512::zero()
+        {
+            self.env().revert(Error::InvalidPrice);
+        }
+
+        // Get sample and verify ownership
+        let sample = self.samples.get(&sample_id)
+            .unwrap_or_else(|| self.env().revert(Error::SampleNotFound));
+
+        if sample.seller != caller {
+            self.env().revert(Error::NotSeller);
+        }
+        if sample.currency.is_some() {
+            self.env().revert(Error::AbsolutePricingRequiresCspr);
+        }
+
+        // Set pricing
+        let prices = AllLicensePrices {
+            personal: personal_price,
+            commercial: commercial_price,
+            broadcast: broadcast_price,
+            exclusive: exclusive_price,
+        };
+        self.license_pricing.set_absolute_prices(sample_id, prices);
+
+        self.env().emit_event(LicensePricingUpdated {
+            sample_id,
+            personal_mult: 0,
+            commercial_mult: 0,
+            broadcast_mult: 0,
+            exclusive_mult: 0,
+            timestamp: self.env().get_block_time(),
+            pricing_mode: PricingMode::Absolute.to_u8(),
+            personal_price,
+            commercial_price,
+            broadcast_price,
+            exclusive_price,
+        });
+    }
+
+    /// Limit the number of licenses of a given type that can be sold (seller only)
+    /// A `max_supply` of 0 removes the cap
+    pub fn set_license_supply(&mut self, sample_id: u64, license_type: u8, max_supply: u64) {
+        let caller = self.env().caller();
+
+        let sample = self.samples.get(&sample_id)
+            .unwrap_or_else(|| self.env().revert(Error::SampleNotFound));
+
+        if sample.seller != caller {
+            self.env().revert(Error::NotSeller);
+        }
+
+        let license_contract_addr = self.license_nft_contract.get()
+            .unwrap_or_else(|| self.env().revert(Error::LicenseContractNotSet));
+        let mut license_nft = LicenseNftContractRef::new(self.env(), license_contract_addr);
+        license_nft.set_max_supply(sample_id, license_type, max_supply);
+    }
+
+    /// Attach a terms document hash or CID to a license tier of a sample (seller only)
+    /// `None` clears it, falling back to the platform default terms
+    pub fn set_license_terms(&mut self, sample_id: u64, license_type: u8, terms_hash: Option<String>) {
+        let caller = self.env().caller();
+
+        let sample = self.samples.get(&sample_id)
+            .unwrap_or_else(|| self.env().revert(Error::SampleNotFound));
+
+        if sample.seller != caller {
+            self.env().revert(Error::NotSeller);
+        }
+
+        let license_contract_addr = self.license_nft_contract.get()
+            .unwrap_or_else(|| self.env().revert(Error::LicenseContractNotSet));
+        let mut license_nft = LicenseNftContractRef::new(self.env(), license_contract_addr);
+        license_nft.set_license_terms(sample_id, license_type, terms_hash);
+    }
+
+    /// Get the terms a buyer must accept for a license tier of a sample
+    pub fn get_license_terms(&self, sample_id: u64, license_type: u8) -> Option<String> {
+        let license_contract_addr = self.license_nft_contract.get()?;
+        let license_nft = LicenseNftContractRef::new(self.env(), license_contract_addr);
+        license_nft.get_license_terms(sample_id, license_type)
+    }
+
+    /// Offer licenses restricted to a territory at a multiple of the worldwide price (seller only)
+    /// `multiplier` is a percentage (100 = 1x); 0 stops offering the territory
+    pub fn set_territory_pricing(&mut self, sample_id: u64, territory: String, multiplier: u64) {
+        let caller = self.env().caller();
+
+        if !is_valid_territory(&territory) {
+            self.env().revert(Error::InvalidTerritory);
+        }
+
+        let sample = self.samples.get(&sample_id)
+            .unwrap_or_else(|| self.env().revert(Error::SampleNotFound));
+
+        if sample.seller != caller {
+            self.env().revert(Error::NotSeller);
+        }
+
+        self.license_pricing.set_territory_multiplier(sample_id, territory.clone(), multiplier);
+
+        self.env().emit_event(TerritoryPricingUpdated {
+            sample_id,
+            territory,
+            multiplier,
+            timestamp: self.env().get_block_time(),
+        });
+    }
+
+    /// Get the territories offered for a sample with their multipliers
+    pub fn get_territory_pricing(&self, sample_id: u64) -> Vec<TerritoryPrice> {
+        self.license_pricing.territory_prices(sample_id)
+    }
+
+    /// Get the price of a license restricted to the given territories (zero if any is not offered)
+    pub fn get_territory_license_price(&self, sample_id: u64, license_type: u8, territories: Vec<String>) -> U512 {
+        let worldwide_price = self.get_license_price(sample_id, license_type);
+        if territories.is_empty() {
+            return worldwide_price;
+        }
+        self.license_pricing.territorial_price(sample_id, worldwide_price, &territories)
+            .unwrap_or_default()
+    }
+
+    /// Get the released works that use a sample
+    pub fn get_sample_releases(&self, sample_id: u64) -> Vec<Release> {
+        if let Some(license_contract_addr) = self.license_nft_contract.get() {
+            let license_nft = LicenseNftContractRef::new(self.env(), license_contract_addr);
+            license_nft.get_releases_by_sample(sample_id)
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// Get the remaining supply of a license type for a sample (None = unlimited)
+    pub fn get_remaining_license_supply(&self, sample_id: u64, license_type: u8) -> Option<u64> {
+        let license_contract_addr = self.license_nft_contract.get()?;
+        let license_nft = LicenseNftContractRef::new(self.env(), license_contract_addr);
+        license_nft.get_remaining_supply(sample_id, license_type)
+    }
+
+    /// Set the caller's default license pricing
+    /// Used for every sample without its own pricing
+    pub fn set_default_license_pricing(
+        &mut self,
+        personal_mult: u64,
+        commercial_mult: u64,
+        broadcast_mult: u64,
+        exclusive_mult: u64,
+    ) {
+        let caller = self.env().caller();
+
+        // Custom tier multipliers are carried over from the previous default
+        let pricing = LicensePricing {
+            personal_multiplier: personal_mult,
+            commercial_multiplier: commercial_mult,
+            broadcast_multiplier: broadcast_mult,
+            exclusive_multiplier: exclusive_mult,
+            tier_multipliers: self.license_pricing.seller_default(&caller)
+                .map(|pricing| pricing.tier_multipliers)
+                .unwrap_or_default(),
+        };
+        if !pricing.is_valid() {
+            self.env().revert(Error::InvalidPricingMultiplier);
+        }
+
+        self.license_pricing.set_seller_default(caller, pricing);
+
+        self.env().emit_event(SellerPricingUpdated {
+            seller: caller,
+            template_name: None,
+            personal_mult,
+            commercial_mult,
+            broadcast_mult,
+            exclusive_mult,
+            timestamp: self.env().get_block_time(),
+        });
+    }
+
+    /// Create or update a named license pricing template for the caller
+    pub fn set_pricing_template(
+        &mut self,
+        name: String,
+        personal_mult: u64,
+        commercial_mult: u64,
+        broadcast_mult: u64,
+        exclusive_mult: u64,
+    ) {
+        let caller = self.env().caller();
+
+        self.validate_template_name(&name);
+        // Custom tier multipliers are carried over when updating a template
+        let pricing = LicensePricing {
+            personal_multiplier: personal_mult,
+            commercial_multiplier: commercial_mult,
+            broadcast_multiplier: broadcast_mult,
+            exclusive_multiplier: exclusive_mult,
+            tier_multipliers: self.license_pricing.template(&caller, &name)
+                .map(|pricing| pricing.tier_multipliers)
+                .unwrap_or_default(),
+        };
+        if !pricing.is_valid() {
+            self.env().revert(Error::InvalidPricingMultiplier);
+        }
+
+        self.license_pricing.set_template(caller, name.clone(), pricing);
+
+        self.env().emit_event(SellerPricingUpdated {
+            seller: caller,
+            template_name: Some(name),
+            personal_mult,
+            commercial_mult,
+            broadcast_mult,
+            exclusive_mult,
+            timestamp: self.env().get_block_time(),
+        });
+    }
+
+    /// Apply one of the caller's pricing templates to a sample (seller only)
+    pub fn apply_pricing_template(&mut self, sample_id: u64, template_name: String) {
+        let caller = self.env().caller();
+
+        let sample = self.samples.get(&sample_id)
+            .unwrap_or_else(|| self.env().revert(Error::SampleNotFound));
+
+        if sample.seller != caller {
+            self.env().revert(Error::NotSeller);
+        }
+
+        let pricing = self.resolve_seller_pricing(&caller, &Some(template_name));
+        self.apply_license_pricing(sample_id, pricing);
+    }
+
+    /// Reprice a page of the caller's uploads (`limit` is capped at `MAX_REPRICE_BATCH`)
+    /// Applies the named template, or with `None` drops per-sample multipliers so the samples
+    /// follow the caller's default pricing (including later changes to it);
+    /// samples with absolute prices keep them
+    pub fn reprice_catalog(&mut self, template_name: Option<String>, offset: u64, limit: u64) {
+        let caller = self.env().caller();
+
+        let pricing = self.resolve_seller_pricing(&caller, &template_name);
+
+        let mut repriced = 0u64;
+        for sample_id in self.activity.uploaded_page(&caller, offset, limit.min(MAX_REPRICE_BATCH)) {
+            if let PricingMode::Absolute = self.license_pricing.mode(sample_id) {
+                continue;
+            }
+            if template_name.is_some() {
+                self.apply_license_pricing(sample_id, pricing.clone());
+            } else {
+                self.license_pricing.clear_multipliers(sample_id);
+                self.emit_license_pricing_updated(sample_id, &pricing);
+            }
+            repriced += 1;
+        }
+
+        self.env().emit_event(CatalogRepriced {
+            seller: caller,
+            template_name,
+            sample_count: repriced,
+            timestamp: self.env().get_block_time(),
+        });
+    }
+
+    /// Get a seller's default license pricing (if any was set)
+    pub fn get_seller_default_pricing(&self, seller: Address) -> Option<LicensePricing> {
+        self.license_pricing.seller_default(&seller)
+    }
+
+    /// Get one of a seller's named pricing templates
+    pub fn get_pricing_template(&self, seller: Address, template_name: String) -> Option<LicensePricing> {
+        self.license_pricing.template(&seller, &template_name)
+    }
+
+    /// Get the names of all pricing templates created by a seller
+    pub fn get_pricing_template_names(&self, seller: Address) -> Vec<String> {
+        self.license_pricing.template_names(&seller)
+    }
+
+    /// Get the price for a specific license type
+    pub fn get_license_price(&self, sample_id: u64, license_type: u8) -> U512 {
+        if let Some(sample) = self.samples.get(&sample_id) {
+            let tier = self.license_tier(license_type)
+                .unwrap_or_else(|| LicenseType::Personal.default_tier());
+            let base_price = self.quote_base_price(&sample);
+            self.calculate_license_price(&sample, &tier, base_price)
+        } else {
+            U512::zero()
+        }
+    }
+
+    /// Get all license prices for a sample
+    pub fn get_all_license_prices(&self, sample_id: u64) -> AllLicensePrices {
+        if let Some(sample) = self.samples.get(&sample_id) {
+            let base_price = self.quote_base_price(&sample);
+            let price = |lt: LicenseType| {
+                let tier = self.license_tier(lt.to_u8()).unwrap_or_else(|| lt.default_tier());
+                self.calculate_license_price(&sample, &tier, base_price)
+            };
+            AllLicensePrices {
+                personal: price(LicenseType::Personal),
+                commercial: price(LicenseType::Commercial),
+                broadcast: price(LicenseType::Broadcast),
+                exclusive: price(LicenseType::Exclusive),
+            }
+        } else {
+            AllLicensePrices::default()
+        }
+    }
+
+    /// Get the price of every active license tier for a sample, custom tiers included
+    pub fn get_license_tier_prices(&self, sample_id: u64) -> Vec<TierPrice> {
+        let sample = match self.samples.get(&sample_id) {
+            Some(sample) => sample,
+            None => return Vec::new(),
+        };
+        let base_price = self.quote_base_price(&sample);
+        self.license_tiers()
+            .into_iter()
+            .filter(|tier| tier.is_active)
+            .map(|tier| TierPrice {
+                tier_id: tier.tier_id,
+                price: self.calculate_license_price(&sample, &tier, base_price),
+                name: tier.name,
+            })
+            .collect()
+    }
+
+    /// Get license pricing multipliers in effect for a sample
+    pub fn get_license_pricing(&self, sample_id: u64) -> LicensePricing {
+        match self.samples.get(&sample_id) {
+            Some(sample) => self.license_pricing.effective_multipliers(sample_id, &sample.seller),
+            None => LicensePricing::default_pricing(),
+        }
+    }
+
+    /// Get the active pricing mode for a sample (0=Multiplier, 1=Absolute)
+    pub fn get_pricing_mode(&self, sample_id: u64) -> u8 {
+        self.license_pricing.mode(sample_id).to_u8()
+    }
+
+    /// Get the absolute license prices for a sample (if any were set)
+    pub fn get_absolute_license_prices(&self, sample_id: u64) -> Option<AllLicensePrices> {
+        self.license_pricing.absolute_prices(sample_id)
+    }
+
+    /// Check if a sample has an exclusive license
+    pub fn is_exclusively_licensed(&self, sample_id: u64) -> bool {
+        if let Some(license_contract_addr) = self.license_nft_contract.get() {
+            let license_nft = LicenseNftContractRef::new(self.env(), license_contract_addr);
+            license_nft.is_exclusively_licensed(sample_id)
+        } else {
+            false
+        }
+    }
+
+    /// Get the exclusive license holder for a sample
+    pub fn get_exclusive_holder(&self, sample_id: u64) -> Option<Address> {
+        if let Some(license_contract_addr) = self.license_nft_contract.get() {
+            let license_nft = LicenseNftContractRef::new(self.env(), license_contract_addr);
+            license_nft.get_exclusive_holder(sample_id)
+        } else {
+            None
+        }
+    }
+
+    // ============================================
+    // Roles & Emergency Stop
+    // ============================================
+
+    /// Grant a role to an account (admin only)
+    pub fn grant_role(&mut self, role: Role, account: Address) {
+        self.roles.grant_role(&role, &account);
+    }
+
+    /// Revoke a role from an account (admin only)
+    pub fn revoke_role(&mut self, role: Role, account: Address) {
+        self.roles.revoke_role(&role, &account);
+    }
+
+    /// Give up a role held by the caller
+    pub fn renounce_role(&mut self, role: Role) {
+        self.roles.renounce_role(&role);
+    }
+
+    /// Check if an account has been granted a role
+    pub fn has_role(&self, role: Role, account: Address) -> bool {
+        self.roles.has_role(&role, &account)
+    }
+
+    /// Pause uploads, purchases, subscriptions, tips and credit deposits (pauser only)
+    /// Withdrawals stay available while paused
+    pub fn pause(&mut self) {
+        self.roles.pause();
+    }
+
+    /// Resume normal operation (pauser only)
+    pub fn unpause(&mut self) {
+        self.roles.unpause();
+    }
+
+    /// Check if the marketplace is paused
+    pub fn is_paused(&self) -> bool {
+        self.roles.is_paused()
+    }
+
+    // ============================================
+    // View Functions
+    // ============================================
+
+    /// Get a sample by ID
+    pub fn get_sample(&self, sample_id: u64) -> Option<Sample> {
+        self.samples.get(&sample_id)
+    }
+
+    /// Get the sample registered with an audio content hash (if any)
+    pub fn get_sample_by_content_hash(&self, content_hash: String) -> Option<u64> {
+        self.content.sample_by_hash(&content_hash)
+    }
+
+    /// Get the uploader an audio content hash is registered to (proof of first publication,
+    /// unless moderators reassigned it); the upload time is the sample's `created_at`
+    pub fn get_first_uploader(&self, content_hash: String) -> Option<Address> {
+        self.content.sample_by_hash(&content_hash)
+            .and_then(|sample_id| self.samples.get(&sample_id))
+            .map(|sample| sample.seller)
+    }
+
+    /// Get all samples uploaded with a perceptual fingerprint, oldest first
+    pub fn get_samples_by_fingerprint(&self, fingerprint: String) -> Vec<u64> {
+        self.content.samples_by_fingerprint(&fingerprint)
+    }
+
+    /// Get the current price of a sample in its payment currency
+    /// USD-priced samples are converted at the current feed rate (zero if unavailable)
+    pub fn get_sample_price(&self, sample_id: u64) -> U512 {
+        self.samples.get(&sample_id)
+            .map(|sample| self.quote_base_price(&sample))
+            .unwrap_or_default()
+    }
+
+    /// Check if a sample is active and within its sale window
+    pub fn is_on_sale(&self, sample_id: u64) -> bool {
+        let now = self.env().get_block_time();
+        self.samples.get(&sample_id)
+            .map(|sample| sample.is_on_sale(now))
+            .unwrap_or(false)
+    }
+
+    /// Get user statistics
+    pub fn get_user_stats(&self, user: Address) -> UserStats {
+        UserStats {
+            uploaded_count: self.activity.uploaded_count(&user),
+            purchased_count: self.activity.purchased_count(&user),
+            earnings: self.ledger.earnings(&user, &None),
+            total_earned: self.ledger.total_earned(&user, &None),
+            total_spent: self.ledger.total_spent(&user, &None),
+            tips_received: self.ledger.tips_received(&user),
+            tips_given: self.ledger.tips_given(&user),
+        }
+    }
+
+    /// Get marketplace statistics
+    pub fn get_marketplace_stats(&self) -> MarketplaceStats {
+        MarketplaceStats {
+            sample_count: self.sample_count.get_or_default(),
+            total_volume: self.ledger.volume(&None),
+            platform_fee_collected: self.ledger.fees_collected(&None),
+        }
+    }
+
+    /// Check if a user has purchased a specific sample
+    pub fn has_purchased(&self, buyer: Address, sample_id: u64) -> bool {
+        self.has_purchased_internal(&buyer, sample_id)
+    }
+
+    /// Get user's uploaded sample IDs
+    pub fn get_user_samples(&self, user: Address) -> Vec<u64> {
+        self.activity.uploaded_samples(&user)
+    }
+
+    /// Get user's purchased sample IDs
+    pub fn get_user_purchases(&self, user: Address) -> Vec<u64> {
+        self.activity.purchased_samples(&user)
+    }
+
+    /// Get user's available earnings
+    pub fn get_earnings(&self, user: Address) -> U512 {
+        self.ledger.earnings(&user, &None)
+    }
+
+    /// Get user's available earnings in a CEP-18 token
+    pub fn get_token_earnings(&self, user: Address, token: Address) -> U512 {
+        self.ledger.earnings(&user, &Some(token))
+    }
+
+    /// Get the trading volume in a CEP-18 token
+    pub fn get_token_volume(&self, token: Address) -> U512 {
+        self.ledger.volume(&Some(token))
+    }
+
+    /// Get all active samples
+    /// Scheduled samples are hidden until their sale window opens; ended sales stay listed
+    pub fn get_all_samples(&self) -> Vec<Sample> {
+        let count = self.sample_count.get_or_default();
+        let now = self.env().get_block_time();
+        let mut result = Vec::new();
+        for id in 1..=count {
+            if let Some(sample) = self.samples.get(&id) {
+                if sample.is_active && !sample.is_delisted && sample.has_sale_started(now) {
+                    result.push(sample);
+                }
+            }
+        }
+        result
+    }
+
+    /// Get full sample data for user's uploaded samples
+    pub fn get_user_samples_full(&self, user: Address) -> Vec<Sample> {
+        let sample_ids = self.get_user_samples(user);
+        let mut result = Vec::new();
+        for id in sample_ids {
+            if let Some(sample) = self.samples.get(&id) {
+                result.push(sample);
+            }
+        }
+        result
+    }
+
+    /// Get full sample data for user's purchased samples
+    pub fn get_user_purchases_full(&self, user: Address) -> Vec<Sample> {
+        let sample_ids = self.get_user_purchases(user);
+        let mut result = Vec::new();
+        for id in sample_ids {
+            if let Some(sample) = self.samples.get(&id) {
+                result.push(sample);
+            }
+        }
+        result
+    }
+
+    /// Get the admin address
+    pub fn get_admin(&self) -> Option<Address> {
+        self.admin.get()
+    }
+
+    // ============================================
+    // Internal Functions
+    // ============================================
+
+    fn has_purchased_internal(&self, buyer: &Address, sample_id: u64) -> bool {
+        self.activity.purchase_record(buyer, sample_id).is_some()
+    }
+
+    /// Purchase a sample on behalf of `buyer`
+    fn purchase_sample_internal(
+        &mut self,
+        buyer: Address,
+        sample_id: u64,
+        max_price: Option<U512>,
+        method: PaymentMethod,
+        referrer: Option<Address>,
+    ) {
+        // Get sample
+        let mut sample = self.samples.get(&sample_id)
+            .unwrap_or_else(|| self.env().revert(Error::SampleNotFound));
+
+        // Validate
+        self.roles.require_not_paused();
+        self.ensure_not_blocked(&buyer);
+        self.ensure_on_sale(&sample);
+        self.validate_referrer(&referrer, &buyer, &sample);
+
+        // Check if already purchased
+        if self.has_purchased_internal(&buyer, sample_id) {
+            self.env().revert(Error::AlreadyPurchased);
+        }
+
+        // Timed exclusive licenses keep the sample listed, but block sales while they last
+        if self.is_exclusively_licensed(sample_id) {
+            self.env().revert(Error::SampleExclusivelyLicensed);
+        }
+
+        let (mut price, conversion_rate) = self.resolve_base_price(&sample);
+        self.ensure_within_max_price(price, max_price);
+
+        // Pay-what-you-want: attached CSPR above the minimum is the price paid
+        if sample.pay_what_you_want {
+            if let PaymentMethod::Attached = method {
+                price = price.max(self.env().attached_value());
+            }
+        }
+
+        self.collect_payment(buyer, &sample, price, method);
+        self.settle_sale(buyer, &mut sample, price, conversion_rate, referrer);
+    }
+
+    /// Purchase a sample license on behalf of `buyer` (mints a License NFT)
+    #[allow(clippy::too_many_arguments)]
+    fn purchase_license_internal(
+        &mut self,
+        buyer: Address,
+        sample_id: u64,
+        license_type: u8,
+        accepted_terms: Option<String>,
+        territories: Vec<String>,
+        max_price: Option<U512>,
+        method: PaymentMethod,
+        referrer: Option<Address>,
+    ) {
+        // Get sample
+        let mut sample = self.samples.get(&sample_id)
+            .unwrap_or_else(|| self.env().revert(Error::SampleNotFound));
+
+        // Validate buyer and that the sample is active and within its sale window
+        self.roles.require_not_paused();
+        self.ensure_not_blocked(&buyer);
+        self.ensure_on_sale(&sample);
+
+        // License prices scale the base price, which is only a minimum under pay-what-you-want
+        if sample.pay_what_you_want {
+            self.env().revert(Error::LicensesUnavailableForPayWhatYouWant);
+        }
+        self.validate_referrer(&referrer, &buyer, &sample);
+
+        // Cannot purchase own sample
+        if sample.seller == buyer {
+            self.env().revert(Error::CannotPurchaseOwnSample);
+        }
+
+        // Check for exclusive license by querying the LicenseNft contract
+        let license_contract_addr = self.license_nft_contract.get()
+            .unwrap_or_else(|| self.env().revert(Error::LicenseContractNotSet));
+        let mut license_nft = LicenseNftContractRef::new(self.env(), license_contract_addr);
+        if license_nft.is_exclusively_licensed(sample_id) {
+            self.env().revert(Error::SampleExclusivelyLicensed);
+        }
+
+        // Look up the license tier (it must still be on sale)
+        let tier = license_nft.get_tier(license_type)
+            .filter(|tier| tier.is_active)
+            .unwrap_or_else(|| self.env().revert(Error::InvalidLicenseType));
+
+        // Calculate license price (scaled to the territories, if restricted) and take payment
+        let (base_price, conversion_rate) = self.resolve_base_price(&sample);
+        let mut license_price = self.calculate_license_price(&sample, &tier, base_price);
+        if !territories.is_empty() {
+            license_price = self.license_pricing.territorial_price(sample_id, license_price, &territories)
+                .unwrap_or_else(|| self.env().revert(Error::TerritoryNotOffered));
+        }
+        self.ensure_within_max_price(license_price, max_price);
+        self.collect_payment(buyer, &sample, license_price, method);
+        self.settle_sale(buyer, &mut sample, license_price, conversion_rate, referrer);
+
+        // Handle perpetual worldwide exclusive license - deactivate sample from marketplace
+        // (territorial exclusives leave the other territories on sale, and timed exclusives
+        // block sales only until they expire, so the sample stays listed for renewal)
+        if tier.is_exclusive && territories.is_empty() && tier.rights.duration.is_none() {
+            sample.is_active = false;
+            self.samples.set(&sample_id, sample.clone());
+        }
+
+        // Mint license NFT (LicenseNft contract handles exclusive tracking)
+        license_nft.mint_license(
+            sample_id,
+            license_type,
+            buyer,
+            sample.seller,
+            license_price,
+            sample.currency,
+            false,
+            accepted_terms,
+            territories,
+        );
+    }
+
+    /// Take payment for a purchase in the sample's currency
+    fn collect_payment(&mut self, buyer: Address, sample: &Sample, price: U512, method: PaymentMethod) {
+        match (method, sample.currency) {
+            (PaymentMethod::Attached, None) => {
+                if self.env().attached_value() < price {
+                    self.env().revert(Error::InsufficientPayment);
+                }
+            }
+            (PaymentMethod::Token, Some(token)) => {
+                // Payable entry points (referrals) would otherwise keep CSPR sent by mistake
+                if !self.env().attached_value().is_zero() {
+                    self.env().revert(Error::UnexpectedAttachedValue);
+                }
+                self.ledger.assert_accepted(&Some(token));
+                self.ledger.collect_token(&buyer, &token, price);
+            }
+            (PaymentMethod::Credit, None) => {
+                let balance = self.ledger.debit_credit(buyer, price);
+                self.env().emit_event(CreditSpent {
+                    user: buyer,
+                    sample_id: sample.sample_id,
+                    amount: price,
+                    balance,
+                    timestamp: self.env().get_block_time(),
+                });
+            }
+            _ => self.env().revert(Error::WrongCurrency),
+        }
+    }
+
+    /// Split a payment between the seller, the referrer (if any) and the platform:
+    /// update volume and spending totals, credit the seller and referrer and
+    /// transfer the platform fee to the admin
+    fn distribute_revenue(
+        &mut self,
+        payer: Address,
+        seller: Address,
+        currency: Option<Address>,
+        amount: U512,
+        referrer: Option<Address>,
+    ) -> RevenueSplit {
+        // Calculate fees
+        let mut platform_fee = amount * PLATFORM_FEE_NUMERATOR / PLATFORM_FEE_DENOMINATOR;
+        let mut seller_amount = amount - platform_fee;
+
+        // Referrer commission: a share of the platform fee plus the seller's bonus
+        let mut referral_share = U512::zero();
+        let mut referral_bonus = U512::zero();
+        if let Some(referrer) = referrer {
+            referral_share = self.referrals.platform_share(platform_fee);
+            referral_bonus = self.referrals.seller_bonus(&seller, seller_amount);
+            platform_fee -= referral_share;
+            seller_amount -= referral_bonus;
+
+            let commission = referral_share + referral_bonus;
+            self.ledger.credit_earnings(referrer, currency, commission);
+            self.referrals.record(referrer, currency, commission);
+        }
+
+        // Update marketplace and payer stats
+        self.ledger.record_sale(currency, amount, platform_fee);
+        self.ledger.record_spent(payer, currency, amount);
+
+        // Update seller's earnings
+        self.ledger.credit_earnings(seller, currency, seller_amount);
+
+        // Transfer platform fee to admin
+        let admin = self.admin.get().unwrap();
+        self.ledger.pay_out(&admin, &currency, platform_fee);
+
+        RevenueSplit {
+            platform_fee,
+            referral_share,
+            referral_bonus,
+        }
+    }
+
+    /// Record a paid sale: update stats, store the purchase record
+    /// and split the payment between seller and platform
+    fn settle_sale(
+        &mut self,
+        buyer: Address,
+        sample: &mut Sample,
+        price: U512,
+        conversion_rate: Option<U512>,
+        referrer: Option<Address>,
+    ) {
+        let sample_id = sample.sample_id;
+        let currency = sample.currency;
+
+        // Update sample stats
+        sample.total_sales += 1;
+        self.samples.set(&sample_id, sample.clone());
+
+        // Update buyer's purchased samples using indexed mapping
+        self.activity.add_purchase(buyer, sample_id);
+
+        // Store purchase record
+        let timestamp = self.env().get_block_time();
+        let purchase_record = PurchaseRecord {
+            sample_id,
+            seller: sample.seller,
+            price,
+            timestamp,
+            ipfs_link: sample.ipfs_link.clone(),
+            currency,
+            conversion_rate,
+        };
+        self.activity.set_purchase_record(buyer, purchase_record);
+
+        let split = self.distribute_revenue(buyer, sample.seller, currency, price, referrer);
+        let platform_fee = split.platform_fee;
+
+        let commission = split.referral_share + split.referral_bonus;
+        if let Some(referrer) = referrer.filter(|_| !commission.is_zero()) {
+            self.env().emit_event(ReferralPaid {
+                referrer,
+                buyer,
+                seller: sample.seller,
+                sample_id,
+                platform_share: split.referral_share,
+                seller_bonus: split.referral_bonus,
+                currency,
+                timestamp,
+            });
+        }
+
+        // Emit event
+        self.env().emit_event(SamplePurchased {
+            sample_id,
+            buyer,
+            seller: sample.seller,
+            price,
+            platform_fee,
+            timestamp,
+            currency,
+            conversion_rate,
+        });
+    }
+
+    /// Take payment for a subscription, store it and emit the event
+    fn settle_subscription(
+        &mut self,
+        subscriber: Address,
+        plan: &SubscriptionPlan,
+        subscription: Subscription,
+        is_renewal: bool,
+    ) {
+        self.roles.require_not_paused();
+        self.ensure_not_blocked(&subscriber);
+        if self.env().attached_value() < plan.price {
+            self.env().revert(Error::InsufficientPayment);
+        }
+
+        let expires_at = subscription.expires_at;
+        self.subscriptions.set_subscription(subscriber, plan.producer, subscription);
+        let platform_fee = self.distribute_revenue(subscriber, plan.producer, None, plan.price, None)
+            .platform_fee;
+
+        self.env().emit_event(Subscribed {
+            subscriber,
+            producer: plan.producer,
+            plan_id: plan.plan_id,
+            price: plan.price,
+            platform_fee,
+            expires_at,
+            is_renewal,
+            timestamp: self.env().get_block_time(),
+        });
+    }
+
+    /// Get a plan that currently accepts subscribers
+    fn active_plan(&self, plan_id: u64) -> SubscriptionPlan {
+        let plan = self.subscriptions.plan(plan_id)
+            .unwrap_or_else(|| self.env().revert(Error::PlanNotFound));
+        if !plan.is_active {
+            self.env().revert(Error::PlanInactive);
+        }
+        plan
+    }
+
+    /// Validate subscription plan terms
+    fn validate_subscription_plan(&self, price: U512, period: u64, license_type: u8) {
+        if price == U512::zero() {
+            self.env().revert(Error::InvalidPrice);
+        }
+        if period == 0 {
+            self.env().revert(Error::InvalidSubscriptionPeriod);
+        }
+        // Catalog-wide access is for Personal use only; commercial and exclusive
+        // rights are sold per sample
+        if license_type != LicenseType::Personal.to_u8() {
+            self.env().revert(Error::InvalidPlanLicenseType);
+        }
+    }
+
+    /// Emit the plan update event
+    fn emit_plan_updated(&self, plan: &SubscriptionPlan) {
+        self.env().emit_event(SubscriptionPlanUpdated {
+            plan_id: plan.plan_id,
+            producer: plan.producer,
+            price: plan.price,
+            period: plan.period,
+            license_type: plan.license_type,
+            is_active: plan.is_active,
+            timestamp: self.env().get_block_time(),
+        });
+    }
+
+    /// Withdraw a user's earnings in the given currency
+    fn withdraw_earnings_internal(&mut self, user: Address, currency: Option<Address>) {
+        // Reset earnings before transfer (CEI pattern)
+        let earnings = self.ledger.take_earnings(user, currency);
+        if earnings == U512::zero() {
+            self.env().revert(Error::NoEarnings);
+        }
+
+        // Transfer earnings to user
+        self.ledger.pay_out(&user, &currency, earnings);
+
+        self.env().emit_event(EarningsWithdrawn {
+            user,
+            amount: earnings,
+            timestamp: self.env().get_block_time(),
+            currency,
+        });
+    }
+
+    /// Revert if the referrer is the buyer or the seller
+    fn validate_referrer(&self, referrer: &Option<Address>, buyer: &Address, sample: &Sample) {
+        if let Some(referrer) = referrer {
+            if referrer == buyer || *referrer == sample.seller {
+                self.env().revert(Error::InvalidReferrer);
+            }
+        }
+    }
+
+    /// Payment method matching a sample's currency (attached CSPR or CEP-18 tokens)
+    fn default_payment_method(&self, sample_id: u64) -> PaymentMethod {
+        match self.samples.get(&sample_id).and_then(|sample| sample.currency) {
+            Some(_) => PaymentMethod::Token,
+            None => PaymentMethod::Attached,
+        }
+    }
+
+    /// Revert if the address is on the moderation blocklist
+    fn ensure_not_blocked(&self, account: &Address) {
+        if self.moderation.is_blocked(account) {
+            self.env().revert(Error::AddressBlocked);
+        }
+    }
+
+    /// Revert unless the caller is a moderator (the admin always is); returns the caller
+    fn assert_moderator(&self) -> Address {
+        self.roles.assert_role(&MODERATOR_ROLE)
+    }
+
+    /// Delist or restore a sample (moderator only)
+    fn set_delisted(&mut self, sample_id: u64, is_delisted: bool) {
+        let moderator = self.assert_moderator();
+
+        let mut sample = self.samples.get(&sample_id)
+            .unwrap_or_else(|| self.env().revert(Error::SampleNotFound));
+        sample.is_delisted = is_delisted;
+        self.samples.set(&sample_id, sample);
+
+        self.env().emit_event(SampleListingUpdated {
+            sample_id,
+            is_delisted,
+            moderator,
+            timestamp: self.env().get_block_time(),
+        });
+    }
+
+    /// Register a content hash to a sample (None = release it) and emit the update event
+    fn set_content_hash_sample(&mut self, content_hash: String, sample_id: Option<u64>, moderator: Address) {
+        self.content.assign(&content_hash, sample_id);
+
+        self.env().emit_event(ContentHashUpdated {
+            content_hash,
+            sample_id,
+            moderator,
+            timestamp: self.env().get_block_time(),
+        });
+    }
+
+    /// Revert unless the sample is active and within its sale window
+    fn ensure_on_sale(&self, sample: &Sample) {
+        if !sample.is_active {
+            self.env().revert(Error::SampleInactive);
+        }
+        self.ensure_listed(sample);
+    }
+
+    /// Revert if the sample is delisted or outside its sale window
+    fn ensure_listed(&self, sample: &Sample) {
+        let now = self.env().get_block_time();
+        if sample.is_delisted {
+            self.env().revert(Error::SampleDelisted);
+        }
+        if !sample.has_sale_started(now) {
+            self.env().revert(Error::SaleNotStarted);
+        }
+        if sample.has_sale_ended(now) {
+            self.env().revert(Error::SaleEnded);
+        }
+    }
+
+    /// Upload a sample on behalf of `caller`
+    #[allow(clippy::too_many_arguments)]
+    fn upload_sample_internal(
+        &mut self,
+        caller: Address,
+        price: U512,
+        pay_what_you_want: bool,
+        ipfs_link: String,
+        title: String,
+        bpm: u64,
+        genre: String,
+        cover_image: String,
+        video_preview_link: String,
+        sale_starts_at: Option<u64>,
+        sale_ends_at: Option<u64>,
+        content_hash: String,
+        fingerprint: Option<String>,
+        pricing_template: Option<String>,
+    ) {
+        self.roles.require_not_paused();
+        self.ensure_not_blocked(&caller);
+        let sample_id = self.create_sample(
+            caller,
+            price,
+            pay_what_you_want,
+            ipfs_link,
+            title,
+            bpm,
+            genre,
+            cover_image,
+            video_preview_link,
+            sale_starts_at,
+            sale_ends_at,
+            content_hash,
+            fingerprint,
+        );
+        if pricing_template.is_some() {
+            let pricing = self.resolve_seller_pricing(&caller, &pricing_template);
+            self.apply_license_pricing(sample_id, pricing);
+        }
+    }
+
+    /// Validate and store a new sample, returning its ID
+    #[allow(clippy::too_many_arguments)]
+    fn create_sample(
+        &mut self,
+        seller: Address,
+        price: U512,
+        pay_what_you_want: bool,
+        ipfs_link: String,
+        title: String,
+        bpm: u64,
+        genre: String,
+        cover_image: String,
+        video_preview_link: String,
+        sale_starts_at: Option<u64>,
+        sale_ends_at: Option<u64>,
+        content_hash: String,
+        fingerprint: Option<String>,
+    ) -> u64 {
+        // Validate inputs (pay-what-you-want samples may be free)
+        if price == U512::zero() && !pay_what_you_want {
+            self.env().revert(Error::InvalidPrice);
+        }
+        if title.len() > MAX_TITLE_LENGTH {
+            self.env().revert(Error::TitleTooLong);
+        }
+        if ipfs_link.len() > MAX_IPFS_LINK_LENGTH {
+            self.env().revert(Error::IpfsLinkTooLong);
+        }
+        if genre.len() > MAX_GENRE_LENGTH {
+            self.env().revert(Error::GenreTooLong);
+        }
+        if cover_image.len() > MAX_COVER_IMAGE_LENGTH {
+            self.env().revert(Error::CoverImageTooLong);
+        }
+        if video_preview_link.len() > MAX_VIDEO_PREVIEW_LENGTH {
+            self.env().revert(Error::VideoPreviewTooLong);
+        }
+        self.validate_sale_window(sale_starts_at, sale_ends_at);
+        self.validate_content(&content_hash, &fingerprint);
+
+        // Generate new sample ID
+        let sample_count = self.sample_count.get_or_default();
+        let sample_id = sample_count + 1;
+        self.sample_count.set(sample_id);
+
+        // Get current timestamp
+        let timestamp = self.env().get_block_time();
+
+        // Create sample
+        let sample = Sample {
+            sample_id,
+            seller,
+            price,
+            ipfs_link: ipfs_link.clone(),
+            title: title.clone(),
+            bpm,
+            genre,
+            cover_image: cover_image.clone(),
+            video_preview_link,
+            total_sales: 0,
+            is_active: true,
+            created_at: timestamp,
+            sale_starts_at,
+            sale_ends_at,
+            currency: None,
+            usd_price_cents: None,
+            pay_what_you_want,
+            is_delisted: false,
+            content_hash: content_hash.clone(),
+            fingerprint: fingerprint.clone(),
+        };
+
+        // Store sample and register its audio as first published here
+        self.samples.set(&sample_id, sample);
+        self.content.register(sample_id, content_hash.clone(), fingerprint);
+
+        // Add to user's uploaded samples using indexed mapping
+        self.activity.add_upload(seller, sample_id);
+
+        // Emit event
+        self.env().emit_event(SampleUploaded {
+            sample_id,
+            seller,
+            price,
+            title,
+            ipfs_link,
+            cover_image,
+            timestamp,
+            sale_starts_at,
+            sale_ends_at,
+            content_hash,
+        });
+
+        sample_id
+    }
+
+    /// Update the price of a sample on behalf of its seller
+    fn update_price_internal(&mut self, caller: Address, sample_id: u64, new_price: U512) {
+        let mut sample = self.samples.get(&sample_id)
+            .unwrap_or_else(|| self.env().revert(Error::SampleNotFound));
+
+        if sample.seller != caller {
+            self.env().revert(Error::NotSeller);
+        }
+
+        // Pay-what-you-want samples may have a zero minimum
+        if new_price == U512::zero() && !sample.pay_what_you_want {
+            self.env().revert(Error::InvalidPrice);
+        }
+
+        let old_price = sample.price;
+        sample.price = new_price;
+        self.samples.set(&sample_id, sample);
+
+        self.env().emit_event(PriceUpdated {
+            sample_id,
+            old_price,
+            new_price,
+            timestamp: self.env().get_block_time(),
+        });
+    }
+
+    /// Deactivate a sample on behalf of its seller
+    fn deactivate_sample_internal(&mut self, caller: Address, sample_id: u64) {
+        let mut sample = self.samples.get(&sample_id)
+            .unwrap_or_else(|| self.env().revert(Error::SampleNotFound));
+
+        if sample.seller != caller {
+            self.env().revert(Error::NotSeller);
+        }
+
+        sample.is_active = false;
+        self.samples.set(&sample_id, sample);
+
+        self.env().emit_event(SampleDeactivated {
+            sample_id,
+            seller: caller,
+            timestamp: self.env().get_block_time(),
+        });
+    }
+
+    /// Set custom license pricing for a sample on behalf of its seller
+    fn set_license_pricing_internal(
+        &mut self,
+        caller: Address,
+        sample_id: u64,
+        personal_mult: u64,
+        commercial_mult: u64,
+        broadcast_mult: u64,
+        exclusive_mult: u64,
+    ) {
+        // Validate multipliers
+        let mut pricing = LicensePricing {
+            personal_multiplier: personal_mult,
+            commercial_multiplier: commercial_mult,
+            broadcast_multiplier: broadcast_mult,
+            exclusive_multiplier: exclusive_mult,
+            tier_multipliers: Vec::new(),
+        };
+        if !pricing.is_valid() {
+            self.env().revert(Error::InvalidPricingMultiplier);
+        }
+
+        // Get sample and verify ownership
+        let sample = self.samples.get(&sample_id)
+            .unwrap_or_else(|| self.env().revert(Error::SampleNotFound));
+
+        if sample.seller != caller {
+            self.env().revert(Error::NotSeller);
+        }
+
+        // Keep the sample's custom tier multipliers
+        pricing.tier_multipliers = self.license_pricing
+            .effective_multipliers(sample_id, &caller)
+            .tier_multipliers;
+
+        // Set pricing
+        self.apply_license_pricing(sample_id, pricing);
+    }
+
+    /// Verify a signed listing, consume its nonce and create its sample; returns the sample ID
+    fn redeem_signed_listing(
+        &mut self,
+        buyer: Address,
+        listing: SignedListing,
+        seller_key: PublicKey,
+        signature: Bytes,
+    ) -> u64 {
+        self.roles.require_not_paused();
+
+        let seller = Address::from(seller_key.clone());
+        self.ensure_not_blocked(&seller);
+
+        let message = self.signed_listing_message(&listing);
+        if !self.env().verify_signature(&message, &signature, &seller_key) {
+            self.env().revert(Error::InvalidListingSignature);
+        }
+        let now = self.env().get_block_time();
+        if now >= listing.expires_at {
+            self.env().revert(Error::ListingExpired);
+        }
+        if self.signed_listings.is_used(&seller, listing.nonce) {
+            self.env().revert(Error::ListingNonceUsed);
+        }
+
+        let sample_id = self.create_sample(
+            seller,
+            listing.price,
+            false,
+            listing.ipfs_link,
+            listing.title,
+            listing.bpm,
+            listing.genre,
+            listing.cover_image,
+            listing.video_preview_link,
+            None,
+            None,
+            listing.content_hash,
+            listing.fingerprint,
+        );
+        if let Some(pricing) = listing.license_pricing {
+            if !pricing.is_valid() {
+                self.env().revert(Error::InvalidPricingMultiplier);
+            }
+            self.apply_license_pricing(sample_id, pricing);
+        }
+        self.signed_listings.redeem(seller, listing.nonce, sample_id);
+
+        self.env().emit_event(SignedListingRedeemed {
+            sample_id,
+            seller,
+            nonce: listing.nonce,
+            buyer,
+            timestamp: now,
+        });
+
+        sample_id
+    }
+
+    /// Serialize a listing behind its domain prefix and this marketplace's address
+    /// (signatures cannot be replayed elsewhere, nor as a meta-transaction)
+    fn signed_listing_message(&self, listing: &SignedListing) -> Bytes {
+        let payload = (self.env().self_address(), listing.clone()).to_bytes()
+            .unwrap_or_else(|_| self.env().revert(Error::InvalidListingSignature));
+        Bytes::from([SIGNED_LISTING_DOMAIN, payload.as_slice()].concat())
+    }
+
+    /// Serialize a meta-transaction behind its domain prefix and this marketplace's address
+    /// (signatures cannot be replayed elsewhere, nor as a signed listing)
+    fn meta_tx_message(&self, meta_tx: &MetaTransaction) -> Bytes {
+        let payload = (self.env().self_address(), meta_tx.clone()).to_bytes()
+            .unwrap_or_else(|_| self.env().revert(Error::InvalidMetaTxSignature));
+        Bytes::from([META_TX_DOMAIN, payload.as_slice()].concat())
+    }
+
+    /// Decode a meta-transaction's arguments (reverts if they do not match the action)
+    fn decode_meta_tx_args<T: FromBytes>(&self, args: &Bytes) -> T {
+        bytesrepr::deserialize_from_slice(args)
+            .unwrap_or_else(|_| self.env().revert(Error::InvalidMetaTxAction))
+    }
+
+    /// Validate an upload's content hash and fingerprint, rejecting audio already uploaded
+    fn validate_content(&self, content_hash: &String, fingerprint: &Option<String>) {
+        if !is_valid_content_hash(content_hash) {
+            self.env().revert(Error::InvalidContentHash);
+        }
+        if fingerprint.as_ref()
+            .is_some_and(|fingerprint| fingerprint.is_empty() || fingerprint.len() > MAX_FINGERPRINT_LENGTH)
+        {
+            self.env().revert(Error::InvalidFingerprint);
+        }
+        if self.content.sample_by_hash(content_hash).is_some() {
+            self.env().revert(Error::DuplicateContent);
+        }
+    }
+
+    /// Revert if the price has risen above the buyer's maximum (front-running protection)
+    fn ensure_within_max_price(&self, price: U512, max_price: Option<U512>) {
+        if max_price.is_some_and(|max_price| price > max_price) {
+            self.env().revert(Error::PriceAboveMaximum);
+        }
+    }
+
+    /// Validate an optional sale window (end must be after start and in the future)
+    fn validate_sale_window(&self, sale_starts_at: Option<u64>, sale_ends_at: Option<u64>) {
+        if let Some(ends_at) = sale_ends_at {
+            if ends_at <= self.env().get_block_time() {
+                self.env().revert(Error::InvalidSaleWindow);
+            }
+            if sale_starts_at.is_some_and(|starts_at| starts_at >= ends_at) {
+                self.env().revert(Error::InvalidSaleWindow);
+            }
+        }
+    }
+
+    /// Resolve the base price of a sample for a purchase
+    /// USD-priced samples are converted at a checked feed rate, which is returned alongside
+    fn resolve_base_price(&mut self, sample: &Sample) -> (U512, Option<U512>) {
+        match sample.usd_price_cents {
+            Some(cents) => {
+                let rate = self.price_oracle.rate_for_purchase();
+                (UsdPriceOracle::to_motes(cents, rate), Some(rate))
+            }
+            None => (sample.price, None),
+        }
+    }
+
+    /// Quote the base price of a sample for views (zero if the feed rate is unavailable)
+    fn quote_base_price(&self, sample: &Sample) -> U512 {
+        match sample.usd_price_cents {
+            Some(cents) => self.price_oracle.current_rate()
+                .map(|rate| UsdPriceOracle::to_motes(cents, rate))
+                .unwrap_or_default(),
+            None => sample.price,
+        }
+    }
+
+    /// Calculate the license price based on the sample's pricing mode and tier
+    fn calculate_license_price(&self, sample: &Sample, tier: &LicenseTier, base_price: U512) -> U512 {
+        self.license_pricing.calculate_price(sample.sample_id, &sample.seller, tier, base_price)
+    }
+
+    /// Look up a license tier on the License NFT contract
+    /// Falls back to the built-in defaults while no contract is set
+    fn license_tier(&self, tier_id: u8) -> Option<LicenseTier> {
+        match self.license_nft_contract.get() {
+            Some(license_contract_addr) => {
+                LicenseNftContractRef::new(self.env(), license_contract_addr).get_tier(tier_id)
+            }
+            None => LicenseType::from_u8(tier_id).map(|lt| lt.default_tier()),
+        }
+    }
+
+    /// Get every license tier from the License NFT contract (built-in defaults while none is set)
+    fn license_tiers(&self) -> Vec<LicenseTier> {
+        match self.license_nft_contract.get() {
+            Some(license_contract_addr) => {
+                LicenseNftContractRef::new(self.env(), license_contract_addr).get_tiers()
+            }
+            None => LicenseType::all().iter().map(|lt| lt.default_tier()).collect(),
+        }
+    }
+
+    /// Validate a pricing template name
+    fn validate_template_name(&self, name: &str) {
+        if name.is_empty() || name.len() > MAX_TEMPLATE_NAME_LENGTH {
+            self.env().revert(Error::InvalidTemplateName);
+        }
+    }
+
+    /// Resolve a seller's named template, or their default pricing when no name is given
+    fn resolve_seller_pricing(&self, seller: &Address, template_name: &Option<String>) -> LicensePricing {
+        match template_name {
+            Some(name) => self.license_pricing.template(seller, name)
+                .unwrap_or_else(|| self.env().revert(Error::TemplateNotFound)),
+            None => self.license_pricing.seller_default(seller)
+                .unwrap_or_else(LicensePricing::default_pricing),
+        }
+    }
+
+    /// Apply multiplier pricing to a sample and emit the update event
+    fn apply_license_pricing(&mut self, sample_id: u64, pricing: LicensePricing) {
+        self.license_pricing.set_multipliers(sample_id, pricing.clone());
+        self.emit_license_pricing_updated(sample_id, &pricing);
+    }
+
+    /// Emit the update event for multiplier pricing now in effect for a sample
+    fn emit_license_pricing_updated(&self, sample_id: u64, pricing: &LicensePricing) {
+        self.env().emit_event(LicensePricingUpdated {
+            sample_id,
+            personal_mult: pricing.personal_multiplier,
+            commercial_mult: pricing.commercial_multiplier,
+            broadcast_mult: pricing.broadcast_multiplier,
+            exclusive_mult: pricing.exclusive_multiplier,
+            timestamp: self.env().get_block_time(),
+            pricing_mode: PricingMode::Multiplier.to_u8(),
+            personal_price: U512::zero(),
+            commercial_price: U512::zero(),
+            broadcast_price: U512::zero(),
+            exclusive_price: U512::zero(),
+        });
+    }
+}
+
+/// How a buyer pays for a purchase
+#[derive(Clone, Copy)]
+enum PaymentMethod {
+    /// CSPR attached to a payable call
+    Attached,
+    /// CEP-18 tokens pulled with `transfer_from` (requires prior approval)
+    Token,
+    /// CSPR drawn from the buyer's prepaid credit balance
+    Credit,
+}
+
+/// How a payment was split between the parties of a sale
+struct RevenueSplit {
+    /// Platform fee transferred to the admin (after the referrer's share)
+    platform_fee: U512,
+    /// Referrer commission taken from the platform fee
+    referral_share: U512,
+    /// Referrer commission funded by the seller's bonus
+    referral_bonus: U512,
+}
+
+// ============================================
+// Tests
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::license_nft::{LicenseNft, LicenseNftHostRef, LicenseNftInitArgs};
+    use crate::license_types::{UsageRights, UsageType};
+    use crate::mock_price_feed::MockPriceFeed;
+    use crate::roles::PAUSER_ROLE;
+    use odra::casper_types::U256;
+    use odra::host::{Deployer, HostEnv, HostRef, NoArgs};
+    use odra_modules::cep18_token::{Cep18, Cep18InitArgs};
+
+    const PRICE: u64 = 1_000_000_000;
+
+    fn setup() -> (HostEnv, SampledMarketplaceHostRef) {
+        let env = odra_test::env();
+        let admin = env.get_account(0);
+        let marketplace = SampledMarketplace::deploy(&env, SampledMarketplaceInitArgs { admin });
+        (env, marketplace)
+    }
+
+    /// Deploy the marketplace together with a License NFT contract it mints through
+    fn setup_with_licenses() -> (HostEnv, SampledMarketplaceHostRef, LicenseNftHostRef) {
+        let (env, mut marketplace) = setup();
+        let admin = env.get_account(0);
+        let mut licenses = LicenseNft::deploy(&env, LicenseNftInitArgs { admin });
+        licenses.set_marketplace(marketplace.address());
+        marketplace.set_license_nft_contract(licenses.address());
+        (env, marketplace, licenses)
+    }
+
+    /// A canonical content hash made of one repeated hex digit
+    fn content_hash(digit: char) -> String {
+        core::iter::repeat_n(digit, CONTENT_HASH_LENGTH).collect()
+    }
+
+    /// Upload a sample as the current caller and return its ID
+    fn upload(marketplace: &mut SampledMarketplaceHostRef, hash: String) -> u64 {
+        upload_scheduled(marketplace, hash, None, None)
+    }
+
+    /// Upload a sample with a sale window as the current caller and return its ID
+    fn upload_scheduled(
+        marketplace: &mut SampledMarketplaceHostRef,
+        hash: String,
+        sale_starts_at: Option<u64>,
+        sale_ends_at: Option<u64>,
+    ) -> u64 {
+        marketplace.upload_sample(
+            U512::from(PRICE),
+            false,
+            String::from("ipfs://audio"),
+            String::from("Loop"),
+            140,
+            String::from("trap"),
+            String::from("ipfs://cover"),
+            String::new(),
+            sale_starts_at,
+            sale_ends_at,
+            hash,
+            None,
+            None,
+        );
+        marketplace.get_marketplace_stats().sample_count
+    }
+
+    /// Upload a sample as the current caller, returning the call result
+    fn try_upload(marketplace: &mut SampledMarketplaceHostRef, hash: String) -> OdraResult<()> {
+        marketplace.try_upload_sample(
+            U512::from(PRICE),
+            false,
+            String::from("ipfs://audio"),
+            String::from("Loop"),
+            140,
+            String::from("trap"),
+            String::from("ipfs://cover"),
+            String::new(),
+            None,
+            None,
+            hash,
+            None,
+            None,
+        )
+    }
+
+    /// A listing payload for a seller to sign, valid for a minute
+    fn listing(env: &HostEnv, hash: String) -> SignedListing {
+        SignedListing {
+            price: U512::from(PRICE),
+            ipfs_link: String::from("ipfs://audio"),
+            title: String::from("Signed loop"),
+            bpm: 90,
+            genre: String::from("ambient"),
+            cover_image: String::new(),
+            video_preview_link: String::new(),
+            license_pricing: None,
+            expires_at: env.block_time() + 60_000,
+            nonce: 7,
+            content_hash: hash,
+            fingerprint: None,
+        }
+    }
+
+    /// An update-price action for a seller to sign, valid for a minute
+    fn update_price_meta_tx(env: &HostEnv, sample_id: u64, new_price: U512, nonce: u64) -> MetaTransaction {
+        MetaTransaction {
+            action: MetaTxAction::UpdatePrice as u8,
+            args: Bytes::from((sample_id, new_price).to_bytes().unwrap()),
+            nonce,
+            deadline: env.block_time() + 60_000,
+        }
+    }
+
+    // --- Sale windows ---
+
+    #[test]
+    fn scheduled_sample_goes_on_sale_at_its_start() {
+        let (env, mut marketplace) = setup();
+        let buyer = env.get_account(2);
+        let starts_at = env.block_time() + 10_000;
+        env.set_caller(env.get_account(1));
+        let sample_id = upload_scheduled(&mut marketplace, content_hash('a'), Some(starts_at), None);
+
+        assert!(marketplace.get_all_samples().is_empty());
+        assert!(!marketplace.is_on_sale(sample_id));
+        env.set_caller(buyer);
+        assert_eq!(
+            marketplace.with_tokens(U512::from(PRICE)).try_purchase_sample(sample_id, None),
+            Err(Error::SaleNotStarted.into())
+        );
+
+        env.advance_block_time(10_000);
+        assert_eq!(marketplace.get_all_samples().len(), 1);
+        marketplace.with_tokens(U512::from(PRICE)).purchase_sample(sample_id, None);
+        assert!(marketplace.has_purchased(buyer, sample_id));
+    }
+
+    #[test]
+    fn sales_end_at_the_window_end() {
+        let (env, mut marketplace) = setup();
+        let ends_at = env.block_time() + 10_000;
+        env.set_caller(env.get_account(1));
+        assert_eq!(
+            marketplace.try_upload_sample(
+                U512::from(PRICE),
+                false,
+                String::from("ipfs://audio"),
+                String::from("Loop"),
+                140,
+                String::from("trap"),
+                String::new(),
+                String::new(),
+                Some(ends_at),
+                Some(ends_at),
+                content_hash('a'),
+                None,
+                None,
+            ),
+            Err(Error::InvalidSaleWindow.into())
+        );
+        let sample_id = upload_scheduled(&mut marketplace, content_hash('a'), None, Some(ends_at));
+
+        env.advance_block_time(10_000);
+        env.set_caller(env.get_account(2));
+        assert_eq!(
+            marketplace.with_tokens(U512::from(PRICE)).try_purchase_sample(sample_id, None),
+            Err(Error::SaleEnded.into())
+        );
+        // Ended sales stay listed
+        assert_eq!(marketplace.get_all_samples().len(), 1);
+    }
+
+    // --- Absolute license prices ---
+
+    #[test]
+    fn absolute_license_prices_replace_multipliers() {
+        let (env, mut marketplace, licenses) = setup_with_licenses();
+        let seller = env.get_account(1);
+        let buyer = env.get_account(2);
+        env.set_caller(seller);
+        let sample_id = upload(&mut marketplace, content_hash('a'));
+        let commercial = U512::from(40 * PRICE);
+        marketplace.set_absolute_license_pricing(
+            sample_id,
+            U512::from(5 * PRICE),
+            commercial,
+            U512::from(120 * PRICE),
+            U512::from(500 * PRICE),
+        );
+
+        assert_eq!(marketplace.get_pricing_mode(sample_id), PricingMode::Absolute.to_u8());
+        assert_eq!(marketplace.get_license_price(sample_id, LicenseType::Commercial.to_u8()), commercial);
+        assert_eq!(marketplace.get_all_license_prices(sample_id).personal, U512::from(5 * PRICE));
+
+        env.set_caller(buyer);
+        let license_type = LicenseType::Commercial.to_u8();
+        assert_eq!(
+            marketplace.with_tokens(commercial - 1).try_purchase_sample_license(sample_id, license_type, None, Vec::new(), None),
+            Err(Error::InsufficientPayment.into())
+        );
+        marketplace.with_tokens(commercial).purchase_sample_license(sample_id, license_type, None, Vec::new(), None);
+        let license_id = licenses.get_user_license(buyer, sample_id, license_type).unwrap();
+        assert_eq!(licenses.get_license(license_id).unwrap().purchase_price, commercial);
+    }
+
+    #[test]
+    fn absolute_license_prices_require_a_cspr_sample() {
+        let (env, mut marketplace) = setup();
+        let token = env.get_account(9);
+        marketplace.set_accepted_token(token, true);
+
+        env.set_caller(env.get_account(1));
+        let sample_id = upload(&mut marketplace, content_hash('a'));
+        let price = U512::from(PRICE);
+        marketplace.set_absolute_license_pricing(sample_id, price, price, price, price);
+        assert_eq!(
+            marketplace.try_set_sample_currency(sample_id, Some(token), price),
+            Err(Error::AbsolutePricingRequiresCspr.into())
+        );
+
+        let token_sample_id = upload(&mut marketplace, content_hash('b'));
+        marketplace.set_sample_currency(token_sample_id, Some(token), price);
+        assert_eq!(
+            marketplace.try_set_absolute_license_pricing(token_sample_id, price, price, price, price),
+            Err(Error::AbsolutePricingRequiresCspr.into())
+        );
+    }
+
+    // --- CEP-18 payments ---
+
+    #[test]
+    fn token_priced_sample_is_paid_with_approved_tokens() {
+        let (env, mut marketplace) = setup();
+        let admin = env.get_account(0);
+        let seller = env.get_account(1);
+        let buyer = env.get_account(2);
+        let mut token = Cep18::deploy(
+            &env,
+            Cep18InitArgs {
+                symbol: String::from("USDX"),
+                name: String::from("Test dollar"),
+                decimals: 9,
+                initial_supply: U256::from(100 * PRICE),
+            },
+        );
+        token.transfer(&buyer, &U256::from(10 * PRICE));
+        marketplace.set_accepted_token(token.address(), true);
+
+        env.set_caller(seller);
+        let sample_id = upload(&mut marketplace, content_hash('a'));
+        marketplace.set_sample_currency(sample_id, Some(token.address()), U512::from(PRICE));
+
+        env.set_caller(buyer);
+        assert_eq!(
+            marketplace.with_tokens(U512::from(PRICE)).try_purchase_sample(sample_id, None),
+            Err(Error::WrongCurrency.into())
+        );
+        // Nothing is pulled without an allowance
+        assert!(marketplace.try_purchase_sample_with_token(sample_id, None).is_err());
+
+        token.approve(&marketplace.address(), &U256::from(PRICE));
+        marketplace.purchase_sample_with_token(sample_id, None);
+        assert!(marketplace.has_purchased(buyer, sample_id));
+        assert_eq!(token.balance_of(&buyer), U256::from(9 * PRICE));
+        assert_eq!(token.balance_of(&admin), U256::from(90 * PRICE + PRICE / 10));
+        assert_eq!(marketplace.get_token_volume(token.address()), U512::from(PRICE));
+        assert_eq!(marketplace.get_token_earnings(seller, token.address()), U512::from(PRICE * 9 / 10));
+
+        env.set_caller(seller);
+        marketplace.withdraw_token_earnings(token.address());
+        assert_eq!(token.balance_of(&seller), U256::from(PRICE * 9 / 10));
+        assert_eq!(marketplace.get_token_earnings(seller, token.address()), U512::zero());
+    }
+
+    // --- Seller pricing defaults and templates ---
+
+    #[test]
+    fn seller_default_pricing_applies_to_samples_without_their_own() {
+        let (env, mut marketplace) = setup();
+        let seller = env.get_account(1);
+        env.set_caller(seller);
+        let sample_id = upload(&mut marketplace, content_hash('a'));
+        let commercial = LicenseType::Commercial.to_u8();
+        assert_eq!(marketplace.get_license_price(sample_id, commercial), U512::from(PRICE) * 250 / 100);
+
+        marketplace.set_default_license_pricing(100, 300, 600, 3000);
+        assert_eq!(marketplace.get_license_price(sample_id, commercial), U512::from(PRICE) * 3);
+
+        // The sample's own multipliers take precedence
+        marketplace.set_license_pricing(sample_id, 100, 400, 600, 3000);
+        assert_eq!(marketplace.get_license_price(sample_id, commercial), U512::from(PRICE) * 4);
+    }
+
+    #[test]
+    fn templates_price_uploads_and_reprice_the_catalog() {
+        let (env, mut marketplace) = setup();
+        let seller = env.get_account(1);
+        env.set_caller(seller);
+        let commercial = LicenseType::Commercial.to_u8();
+        let first_id = upload(&mut marketplace, content_hash('a'));
+        let second_id = upload(&mut marketplace, content_hash('b'));
+        marketplace.set_pricing_template(String::from("club"), 100, 500, 800, 4000);
+
+        marketplace.upload_sample(
+            U512::from(PRICE),
+            false,
+            String::from("ipfs://audio"),
+            String::from("Club loop"),
+            128,
+            String::from("electronic"),
+            String::new(),
+            String::new(),
+            None,
+            None,
+            content_hash('c'),
+            None,
+            Some(String::from("club")),
+        );
+        assert_eq!(marketplace.get_license_price(3, commercial), U512::from(PRICE) * 5);
+        assert_eq!(
+            marketplace.try_apply_pricing_template(first_id, String::from("missing")),
+            Err(Error::TemplateNotFound.into())
+        );
+
+        marketplace.reprice_catalog(Some(String::from("club")), 0, 10);
+        assert_eq!(marketplace.get_license_price(first_id, commercial), U512::from(PRICE) * 5);
+        assert_eq!(marketplace.get_license_price(second_id, commercial), U512::from(PRICE) * 5);
+
+        // Repricing without a template falls back to the seller default
+        marketplace.reprice_catalog(None, 0, 10);
+        assert_eq!(marketplace.get_license_price(first_id, commercial), U512::from(PRICE) * 250 / 100);
+    }
+
+    // --- USD price oracle ---
+
+    #[test]
+    fn usd_price_checks_consecutive_feed_rounds() {
+        let (env, mut marketplace) = setup();
+        let seller = env.get_account(1);
+        let buyer = env.get_account(2);
+        let mut feed = MockPriceFeed::deploy(&env, NoArgs);
+        let rate = U512::from(50 * PRICE);
+
+        assert_eq!(marketplace.get_usd_rate(), U512::zero());
+        marketplace.set_price_feed(feed.address(), 60_000, 1_000);
+
+        env.set_caller(seller);
+        let sample_id = upload(&mut marketplace, content_hash('a'));
+        marketplace.set_usd_price(sample_id, Some(250));
+
+        // Within 10% of the previous round
+        feed.set_price(rate, env.block_time());
+        feed.set_price(rate * 105 / 100, env.block_time());
+        let price = marketplace.get_sample_price(sample_id);
+        assert_eq!(price, rate * 105 / 100 * 250 / 100);
+
+        // A 20% jump from the previous round is rejected, and views degrade to zero
+        feed.set_price(rate * 126 / 100, env.block_time());
+        assert_eq!(marketplace.get_usd_rate(), U512::zero());
+        env.set_caller(buyer);
+        assert_eq!(
+            marketplace.with_tokens(price * 2).try_purchase_sample(sample_id, None),
+            Err(Error::PriceDeviationTooHigh.into())
+        );
+
+        // The next round is compared to the jumped one, not to an older reference
+        feed.set_price(rate * 130 / 100, env.block_time());
+        let price = marketplace.get_sample_price(sample_id);
+        assert_eq!(price, rate * 130 / 100 * 250 / 100);
+        marketplace.with_tokens(price).purchase_sample(sample_id, Some(price));
+        assert!(marketplace.has_purchased(buyer, sample_id));
+    }
+
+    #[test]
+    fn usd_price_rejects_stale_rounds() {
+        let (env, mut marketplace) = setup();
+        let seller = env.get_account(1);
+        let buyer = env.get_account(2);
+        let mut feed = MockPriceFeed::deploy(&env, NoArgs);
+
+        marketplace.set_price_feed(feed.address(), 60_000, 0);
+        feed.set_price(U512::from(50 * PRICE), env.block_time());
+
+        env.set_caller(seller);
+        let sample_id = upload(&mut marketplace, content_hash('a'));
+        marketplace.set_usd_price(sample_id, Some(100));
+
+        env.advance_block_time(60_001);
+        env.set_caller(buyer);
+        assert_eq!(
+            marketplace.with_tokens(U512::from(100 * PRICE)).try_purchase_sample(sample_id, None),
+            Err(Error::StalePrice.into())
+        );
+    }
+
+    // --- Prepaid credit ---
+
+    #[test]
+    fn purchases_draw_from_prepaid_credit() {
+        let (env, mut marketplace, licenses) = setup_with_licenses();
+        let seller = env.get_account(1);
+        let buyer = env.get_account(2);
+        env.set_caller(seller);
+        let sample_id = upload(&mut marketplace, content_hash('a'));
+
+        env.set_caller(buyer);
+        assert_eq!(marketplace.try_deposit_credit(), Err(Error::InvalidAmount.into()));
+        marketplace.with_tokens(U512::from(3 * PRICE)).deposit_credit();
+        assert_eq!(marketplace.get_credit_balance(buyer), U512::from(3 * PRICE));
+
+        marketplace.purchase_sample_with_credit(sample_id, None);
+        assert!(marketplace.has_purchased(buyer, sample_id));
+        assert_eq!(marketplace.get_credit_balance(buyer), U512::from(2 * PRICE));
+        assert_eq!(marketplace.get_earnings(seller), U512::from(PRICE * 9 / 10));
+
+        // A commercial license costs 2.5x the sample price
+        let commercial = LicenseType::Commercial.to_u8();
+        assert_eq!(
+            marketplace.try_purchase_sample_license_with_credit(sample_id, commercial, None, Vec::new(), None),
+            Err(Error::InsufficientCredit.into())
+        );
+        let personal = LicenseType::Personal.to_u8();
+        marketplace.purchase_sample_license_with_credit(sample_id, personal, None, Vec::new(), None);
+        assert!(licenses.get_user_license(buyer, sample_id, personal).is_some());
+        assert_eq!(marketplace.get_credit_balance(buyer), U512::from(PRICE));
+    }
+
+    #[test]
+    fn unused_credit_can_be_withdrawn() {
+        let (env, mut marketplace) = setup();
+        let buyer = env.get_account(2);
+        env.set_caller(buyer);
+        let start = env.balance_of(&buyer);
+        marketplace.with_tokens(U512::from(2 * PRICE)).deposit_credit();
+
+        assert_eq!(
+            marketplace.try_withdraw_credit(U512::from(3 * PRICE)),
+            Err(Error::InsufficientCredit.into())
+        );
+        marketplace.withdraw_credit(U512::from(PRICE));
+        assert_eq!(marketplace.get_credit_balance(buyer), U512::from(PRICE));
+        assert_eq!(env.balance_of(&buyer), start - U512::from(PRICE));
+    }
+
+    // --- Subscriptions ---
+
+    #[test]
+    fn subscription_grants_catalog_access_until_it_lapses() {
+        let (env, mut marketplace) = setup();
+        let seller = env.get_account(1);
+        let fan = env.get_account(2);
+        const MONTH: u64 = 30 * 24 * 60 * 60 * 1000;
+        env.set_caller(seller);
+        let sample_id = upload(&mut marketplace, content_hash('a'));
+        assert_eq!(
+            marketplace.try_create_subscription_plan(U512::from(PRICE), MONTH, LicenseType::Commercial.to_u8()),
+            Err(Error::InvalidPlanLicenseType.into())
+        );
+        marketplace.create_subscription_plan(U512::from(PRICE), MONTH, LicenseType::Personal.to_u8());
+        let plan_id = marketplace.get_producer_plans(seller)[0].plan_id;
+
+        env.set_caller(fan);
+        assert!(!marketplace.has_access(fan, sample_id));
+        assert_eq!(
+            marketplace.with_tokens(U512::from(PRICE - 1)).try_subscribe(plan_id),
+            Err(Error::InsufficientPayment.into())
+        );
+        marketplace.with_tokens(U512::from(PRICE)).subscribe(plan_id);
+        assert!(marketplace.has_access(fan, sample_id));
+        assert!(!marketplace.has_purchased(fan, sample_id));
+        // Subscription revenue takes the normal fee and earnings path
+        assert_eq!(marketplace.get_earnings(seller), U512::from(PRICE * 9 / 10));
+        assert_eq!(
+            marketplace.with_tokens(U512::from(PRICE)).try_subscribe(plan_id),
+            Err(Error::AlreadySubscribed.into())
+        );
+
+        // Renewing early extends from the current expiry
+        let expires_at = marketplace.get_subscription(fan, seller).unwrap().expires_at;
+        marketplace.with_tokens(U512::from(PRICE)).renew(plan_id);
+        assert_eq!(marketplace.get_subscription(fan, seller).unwrap().expires_at, expires_at + MONTH);
+
+        env.advance_block_time(2 * MONTH + 1);
+        assert!(!marketplace.is_subscribed(fan, seller));
+        assert!(!marketplace.has_access(fan, sample_id));
+    }
+
+    #[test]
+    fn inactive_plans_accept_no_subscribers() {
+        let (env, mut marketplace) = setup();
+        let seller = env.get_account(1);
+        env.set_caller(seller);
+        marketplace.create_subscription_plan(U512::from(PRICE), 1_000, LicenseType::Personal.to_u8());
+        let plan_id = marketplace.get_producer_plans(seller)[0].plan_id;
+        assert_eq!(
+            marketplace.with_tokens(U512::from(PRICE)).try_subscribe(plan_id),
+            Err(Error::CannotSubscribeToSelf.into())
+        );
+
+        env.set_caller(env.get_account(2));
+        assert_eq!(
+            marketplace.try_update_subscription_plan(plan_id, U512::from(PRICE), 1_000, LicenseType::Personal.to_u8(), false),
+            Err(Error::NotSeller.into())
+        );
+
+        env.set_caller(seller);
+        marketplace.update_subscription_plan(plan_id, U512::from(PRICE), 1_000, LicenseType::Personal.to_u8(), false);
+        env.set_caller(env.get_account(2));
+        assert_eq!(
+            marketplace.with_tokens(U512::from(PRICE)).try_subscribe(plan_id),
+            Err(Error::PlanInactive.into())
+        );
+    }
+
+    // --- Pay-what-you-want and tips ---
+
+    #[test]
+    fn pay_what_you_want_charges_attached_value_and_blocks_licenses() {
+        let (env, mut marketplace) = setup();
+        let seller = env.get_account(1);
+        let buyer = env.get_account(2);
+        env.set_caller(seller);
+        let sample_id = upload(&mut marketplace, content_hash('a'));
+        marketplace.set_pay_what_you_want(sample_id, true, U512::zero());
+
+        env.set_caller(buyer);
+        assert_eq!(
+            marketplace.with_tokens(U512::from(PRICE)).try_purchase_sample_license(
+                sample_id,
+                LicenseType::Personal.to_u8(),
+                None,
+                Vec::new(),
+                None,
+            ),
+            Err(Error::LicensesUnavailableForPayWhatYouWant.into())
+        );
+
+        let paid = U512::from(3 * PRICE);
+        marketplace.with_tokens(paid).purchase_sample(sample_id, None);
+        assert_eq!(marketplace.get_marketplace_stats().total_volume, paid);
+        assert_eq!(marketplace.get_user_stats(buyer).total_spent, paid);
+        assert_eq!(marketplace.get_earnings(seller), paid * 9 / 10);
+    }
+
+    #[test]
+    fn pay_what_you_want_uploads_may_be_free() {
+        let (env, mut marketplace) = setup();
+        let seller = env.get_account(1);
+        let buyer = env.get_account(2);
+        env.set_caller(seller);
+        let upload_free = |marketplace: &mut SampledMarketplaceHostRef, pay_what_you_want: bool, hash: String| {
+            marketplace.try_upload_sample(
+                U512::zero(),
+                pay_what_you_want,
+                String::from("ipfs://audio"),
+                String::from("Free loop"),
+                140,
+                String::from("trap"),
+                String::new(),
+                String::new(),
+                None,
+                None,
+                hash,
+                None,
+                None,
+            )
+        };
+        assert_eq!(upload_free(&mut marketplace, false, content_hash('a')), Err(Error::InvalidPrice.into()));
+        upload_free(&mut marketplace, true, content_hash('a')).unwrap();
+        assert!(marketplace.get_sample(1).unwrap().pay_what_you_want);
+
+        // A free download, with an optional tip on top
+        env.set_caller(buyer);
+        marketplace.purchase_sample(1, None);
+        assert!(marketplace.has_purchased(buyer, 1));
+        marketplace.with_tokens(U512::from(PRICE)).tip_producer(seller, 1);
+        assert_eq!(marketplace.get_earnings(seller), U512::from(PRICE * 9 / 10));
+    }
+
+    #[test]
+    fn tips_are_kept_out_of_sales_totals() {
+        let (env, mut marketplace) = setup();
+        let seller = env.get_account(1);
+        let tipper = env.get_account(2);
+        env.set_caller(seller);
+        let sample_id = upload(&mut marketplace, content_hash('a'));
+
+        env.set_caller(tipper);
+        let tip = U512::from(PRICE);
+        marketplace.with_tokens(tip).tip_producer(seller, sample_id);
+
+        // The fee is collected, but no sale is counted
+        let stats = marketplace.get_marketplace_stats();
+        assert_eq!(stats.total_volume, U512::zero());
+        assert_eq!(stats.platform_fee_collected, tip / 10);
+        assert_eq!(marketplace.get_sample(sample_id).unwrap().total_sales, 0);
+        assert_eq!(marketplace.get_user_stats(tipper).total_spent, U512::zero());
+        assert_eq!(marketplace.get_user_stats(tipper).tips_given, tip);
+        assert_eq!(marketplace.get_user_stats(seller).tips_received, tip * 9 / 10);
+        assert_eq!(marketplace.get_earnings(seller), tip * 9 / 10);
+    }
+
+    // --- Tip guards ---
+
+    #[test]
+    fn tips_follow_blocklist_listing_and_sale_window() {
+        let (env, mut marketplace) = setup();
+        let seller = env.get_account(1);
+        let tipper = env.get_account(2);
+        let tip = U512::from(PRICE);
+        env.set_caller(seller);
+        let delisted_id = upload(&mut marketplace, content_hash('a'));
+        let ends_at = env.block_time() + 10_000;
+        let timed_id = upload_scheduled(&mut marketplace, content_hash('b'), None, Some(ends_at));
+
+        env.set_caller(env.get_account(0));
+        marketplace.delist_sample(delisted_id);
+        env.set_caller(tipper);
+        assert_eq!(
+            marketplace.with_tokens(tip).try_tip_producer(seller, delisted_id),
+            Err(Error::SampleDelisted.into())
+        );
+        marketplace.with_tokens(tip).tip_producer(seller, timed_id);
+
+        env.advance_block_time(10_000);
+        assert_eq!(
+            marketplace.with_tokens(tip).try_tip_producer(seller, timed_id),
+            Err(Error::SaleEnded.into())
+        );
+
+        env.set_caller(seller);
+        let listed_id = upload(&mut marketplace, content_hash('c'));
+        env.set_caller(env.get_account(0));
+        marketplace.set_blocked(tipper, true);
+        env.set_caller(tipper);
+        assert_eq!(
+            marketplace.with_tokens(tip).try_tip_producer(seller, listed_id),
+            Err(Error::AddressBlocked.into())
+        );
+    }
+
+    // --- Referrals ---
+
+    #[test]
+    fn referrer_earns_platform_share_and_seller_bonus() {
+        let (env, mut marketplace) = setup();
+        let seller = env.get_account(1);
+        let buyer = env.get_account(2);
+        let referrer = env.get_account(3);
+        assert_eq!(marketplace.try_set_referral_share(10_001), Err(Error::InvalidReferralRate.into()));
+        marketplace.set_referral_share(5_000);
+
+        env.set_caller(seller);
+        let sample_id = upload(&mut marketplace, content_hash('a'));
+        marketplace.set_referral_bonus(1_000);
+
+        env.set_caller(buyer);
+        let price = U512::from(PRICE);
+        marketplace.with_tokens(price).purchase_sample_with_referrer(sample_id, None, referrer);
+
+        // Half the 10% fee, plus 10% of the seller's 90%
+        let commission = price / 20 + price * 9 / 100;
+        assert_eq!(marketplace.get_earnings(referrer), commission);
+        assert_eq!(marketplace.get_earnings(seller), price * 81 / 100);
+        assert_eq!(marketplace.get_marketplace_stats().platform_fee_collected, price / 20);
+        let stats = marketplace.get_referrer_stats(referrer);
+        assert_eq!(stats.referral_count, 1);
+        assert_eq!(stats.total_earned, commission);
+    }
+
+    #[test]
+    fn buyers_and_sellers_cannot_refer_themselves() {
+        let (env, mut marketplace) = setup();
+        let seller = env.get_account(1);
+        let buyer = env.get_account(2);
+        env.set_caller(seller);
+        let sample_id = upload(&mut marketplace, content_hash('a'));
+
+        env.set_caller(buyer);
+        let price = U512::from(PRICE);
+        for referrer in [buyer, seller] {
+            assert_eq!(
+                marketplace.with_tokens(price).try_purchase_sample_with_referrer(sample_id, None, referrer),
+                Err(Error::InvalidReferrer.into())
+            );
+        }
+    }
+
+    // --- Moderation ---
+
+    #[test]
+    fn reports_stay_open_until_a_moderator_closes_them() {
+        let (env, mut marketplace) = setup();
+        let reporter = env.get_account(2);
+        env.set_caller(env.get_account(1));
+        let sample_id = upload(&mut marketplace, content_hash('a'));
+
+        env.set_caller(reporter);
+        assert_eq!(marketplace.try_report_sample(sample_id, 9), Err(Error::InvalidReportReason.into()));
+        marketplace.report_sample(sample_id, ReportReason::Copyright as u8);
+        assert_eq!(
+            marketplace.try_report_sample(sample_id, ReportReason::Spam as u8),
+            Err(Error::AlreadyReported.into())
+        );
+        let report_id = marketplace.get_open_reports()[0].report_id;
+        assert_eq!(marketplace.try_resolve_report(report_id, true), Err(Error::Unauthorized.into()));
+
+        env.set_caller(env.get_account(0));
+        marketplace.resolve_report(report_id, true);
+        assert!(marketplace.get_open_reports().is_empty());
+        let report = marketplace.get_report(report_id).unwrap();
+        assert!(matches!(report.status, ReportStatus::Resolved));
+        assert_eq!(report.resolved_by, Some(env.get_account(0)));
+        assert_eq!(
+            marketplace.try_resolve_report(report_id, false),
+            Err(Error::ReportAlreadyClosed.into())
+        );
+    }
+
+    #[test]
+    fn delisted_samples_and_blocked_addresses_cannot_trade() {
+        let (env, mut marketplace) = setup();
+        let moderator = env.get_account(0);
+        let seller = env.get_account(1);
+        let buyer = env.get_account(2);
+        env.set_caller(seller);
+        let sample_id = upload(&mut marketplace, content_hash('a'));
+        assert_eq!(marketplace.try_delist_sample(sample_id), Err(Error::Unauthorized.into()));
+
+        env.set_caller(moderator);
+        marketplace.delist_sample(sample_id);
+        env.set_caller(buyer);
+        let price = U512::from(PRICE);
+        assert_eq!(
+            marketplace.with_tokens(price).try_purchase_sample(sample_id, None),
+            Err(Error::SampleDelisted.into())
+        );
+
+        env.set_caller(moderator);
+        marketplace.restore_sample(sample_id);
+        marketplace.set_blocked(buyer, true);
+        marketplace.set_blocked(seller, true);
+        assert!(marketplace.is_blocked(buyer));
+        env.set_caller(buyer);
+        assert_eq!(
+            marketplace.with_tokens(price).try_purchase_sample(sample_id, None),
+            Err(Error::AddressBlocked.into())
+        );
+        env.set_caller(seller);
+        assert_eq!(
+            try_upload(&mut marketplace, content_hash('b')),
+            Err(Error::AddressBlocked.into())
+        );
+
+        env.set_caller(moderator);
+        marketplace.set_blocked(buyer, false);
+        env.set_caller(buyer);
+        marketplace.with_tokens(price).purchase_sample(sample_id, None);
+        assert!(marketplace.has_purchased(buyer, sample_id));
+    }
+
+    // --- Roles ---
+
+    #[test]
+    fn granted_roles_replace_the_admin_check() {
+        let (env, mut marketplace) = setup();
+        let moderator = env.get_account(3);
+        let treasurer = env.get_account(4);
+        let pauser = env.get_account(5);
+        env.set_caller(env.get_account(1));
+        let sample_id = upload(&mut marketplace, content_hash('a'));
+        assert!(marketplace.try_grant_role(MODERATOR_ROLE, moderator).is_err());
+
+        env.set_caller(env.get_account(0));
+        marketplace.grant_role(MODERATOR_ROLE, moderator);
+        marketplace.grant_role(TREASURER_ROLE, treasurer);
+        marketplace.grant_role(PAUSER_ROLE, pauser);
+        assert!(marketplace.is_moderator(moderator));
+
+        // Each role unlocks its own actions only
+        env.set_caller(moderator);
+        marketplace.delist_sample(sample_id);
+        assert_eq!(
+            marketplace.try_set_accepted_token(treasurer, true),
+            Err(Error::Unauthorized.into())
+        );
+        env.set_caller(treasurer);
+        marketplace.set_accepted_token(treasurer, true);
+        assert_eq!(marketplace.try_restore_sample(sample_id), Err(Error::Unauthorized.into()));
+
+        env.set_caller(pauser);
+        marketplace.pause();
+        env.set_caller(env.get_account(1));
+        assert!(try_upload(&mut marketplace, content_hash('b')).is_err());
+
+        env.set_caller(env.get_account(0));
+        marketplace.revoke_role(MODERATOR_ROLE, moderator);
+        env.set_caller(moderator);
+        assert_eq!(marketplace.try_restore_sample(sample_id), Err(Error::Unauthorized.into()));
+    }
+
+    // --- Promotional grants ---
+
+    #[test]
+    fn granted_licenses_are_free_and_not_sales() {
+        let (env, mut marketplace, licenses) = setup_with_licenses();
+        let seller = env.get_account(1);
+        let winners = vec![env.get_account(3), env.get_account(4)];
+        let personal = LicenseType::Personal.to_u8();
+        env.set_caller(seller);
+        let sample_id = upload(&mut marketplace, content_hash('a'));
+
+        env.set_caller(env.get_account(2));
+        assert_eq!(
+            marketplace.try_grant_licenses(sample_id, personal, winners.clone()),
+            Err(Error::NotSeller.into())
+        );
+        env.set_caller(seller);
+        assert_eq!(
+            marketplace.try_grant_licenses(sample_id, personal, vec![seller]),
+            Err(Error::InvalidRecipients.into())
+        );
+
+        marketplace.grant_licenses(sample_id, personal, winners.clone());
+        for winner in winners {
+            let license_id = licenses.get_user_license(winner, sample_id, personal).unwrap();
+            let license = licenses.get_license(license_id).unwrap();
+            assert!(license.is_promotional);
+            assert_eq!(license.purchase_price, U512::zero());
+        }
+        assert_eq!(marketplace.get_marketplace_stats().total_volume, U512::zero());
+        assert_eq!(marketplace.get_sample(sample_id).unwrap().total_sales, 0);
+    }
+
+    #[test]
+    fn grants_respect_supply_caps_and_exclusivity() {
+        let (env, mut marketplace, _licenses) = setup_with_licenses();
+        let commercial = LicenseType::Commercial.to_u8();
+        let exclusive = LicenseType::Exclusive.to_u8();
+        env.set_caller(env.get_account(1));
+        let sample_id = upload(&mut marketplace, content_hash('a'));
+        marketplace.set_license_supply(sample_id, commercial, 1);
+
+        assert_eq!(
+            marketplace.try_grant_licenses(sample_id, commercial, vec![env.get_account(3), env.get_account(4)]),
+            Err(Error::LicenseSoldOut.into())
+        );
+        marketplace.grant_licenses(sample_id, commercial, vec![env.get_account(3)]);
+        assert_eq!(
+            marketplace.try_grant_licenses(sample_id, commercial, vec![env.get_account(4)]),
+            Err(Error::LicenseSoldOut.into())
+        );
+
+        assert_eq!(
+            marketplace.try_grant_licenses(sample_id, exclusive, vec![env.get_account(5), env.get_account(6)]),
+            Err(Error::InvalidRecipients.into())
+        );
+        marketplace.grant_licenses(sample_id, exclusive, vec![env.get_account(5)]);
+        assert!(!marketplace.get_sample(sample_id).unwrap().is_active);
+        assert_eq!(
+            marketplace.try_grant_licenses(sample_id, LicenseType::Personal.to_u8(), vec![env.get_account(6)]),
+            Err(Error::SampleExclusivelyLicensed.into())
+        );
+    }
+
+    // --- Reactivation after revocation ---
+
+    #[test]
+    fn moderators_reactivate_a_sample_once_its_exclusive_is_revoked() {
+        let (env, mut marketplace, mut licenses) = setup_with_licenses();
+        let moderator = env.get_account(0);
+        let buyer = env.get_account(2);
+        let exclusive = LicenseType::Exclusive.to_u8();
+        env.set_caller(env.get_account(1));
+        let sample_id = upload(&mut marketplace, content_hash('a'));
+
+        env.set_caller(moderator);
+        assert_eq!(marketplace.try_reactivate_sample(sample_id), Err(Error::SampleAlreadyActive.into()));
+
+        env.set_caller(buyer);
+        let price = marketplace.get_license_price(sample_id, exclusive);
+        marketplace.with_tokens(price).purchase_sample_license(sample_id, exclusive, None, Vec::new(), None);
+        assert!(!marketplace.get_sample(sample_id).unwrap().is_active);
+        assert_eq!(marketplace.try_reactivate_sample(sample_id), Err(Error::Unauthorized.into()));
+
+        env.set_caller(moderator);
+        assert_eq!(
+            marketplace.try_reactivate_sample(sample_id),
+            Err(Error::SampleExclusivelyLicensed.into())
+        );
+        let license_id = licenses.get_user_license(buyer, sample_id, exclusive).unwrap();
+        licenses.revoke_license(license_id, 2);
+        marketplace.reactivate_sample(sample_id);
+        assert!(marketplace.get_sample(sample_id).unwrap().is_active);
+
+        env.set_caller(env.get_account(3));
+        marketplace.with_tokens(U512::from(PRICE)).purchase_sample(sample_id, None);
+        assert!(marketplace.has_purchased(env.get_account(3), sample_id));
+    }
+
+    // --- License terms ---
+
+    #[test]
+    fn buyers_must_accept_the_terms_in_force() {
+        let (env, mut marketplace, mut licenses) = setup_with_licenses();
+        let seller = env.get_account(1);
+        let buyer = env.get_account(2);
+        let personal = LicenseType::Personal.to_u8();
+        let commercial = LicenseType::Commercial.to_u8();
+        let terms_v1 = String::from("ipfs://commercial-terms-v1");
+        licenses.set_default_terms(personal, Some(String::from("ipfs://platform-personal")));
+
+        env.set_caller(seller);
+        let sample_id = upload(&mut marketplace, content_hash('a'));
+        marketplace.set_license_terms(sample_id, commercial, Some(terms_v1.clone()));
+        assert_eq!(marketplace.get_license_terms(sample_id, commercial), Some(terms_v1.clone()));
+        assert_eq!(
+            marketplace.get_license_terms(sample_id, personal),
+            Some(String::from("ipfs://platform-personal"))
+        );
+
+        env.set_caller(buyer);
+        let price = marketplace.get_license_price(sample_id, commercial);
+        for accepted in [None, Some(String::from("ipfs://other"))] {
+            assert_eq!(
+                marketplace.with_tokens(price).try_purchase_sample_license(sample_id, commercial, accepted, Vec::new(), None),
+                Err(Error::TermsMismatch.into())
+            );
+        }
+        marketplace.with_tokens(price).purchase_sample_license(sample_id, commercial, Some(terms_v1.clone()), Vec::new(), None);
+        let license_id = licenses.get_user_license(buyer, sample_id, commercial).unwrap();
+
+        // New terms apply to later buyers; minted licenses keep the snapshot
+        env.set_caller(seller);
+        marketplace.set_license_terms(sample_id, commercial, Some(String::from("ipfs://commercial-terms-v2")));
+        assert_eq!(licenses.get_license(license_id).unwrap().terms_hash, Some(terms_v1));
+    }
+
+    // --- Custom license tiers ---
+
+    #[test]
+    fn custom_tiers_are_priced_sold_and_verified() {
+        let (env, mut marketplace, mut licenses) = setup_with_licenses();
+        let seller = env.get_account(1);
+        let buyer = env.get_account(2);
+        let sync_only = licenses.register_tier(
+            String::from("Sync-only"),
+            UsageRights {
+                streaming: false,
+                sync: true,
+                broadcast: false,
+                commercial_distribution: false,
+                attribution_required: true,
+                max_units: None,
+                duration: None,
+            },
+            300,
+            false,
+            false,
+        );
+        assert!(licenses.get_tiers().iter().any(|tier| tier.tier_id == sync_only && tier.name == "Sync-only"));
+
+        env.set_caller(seller);
+        let sample_id = upload(&mut marketplace, content_hash('a'));
+        assert_eq!(marketplace.get_license_price(sample_id, sync_only), U512::from(3 * PRICE));
+        assert_eq!(
+            marketplace.try_set_tier_multiplier(sample_id, LicenseType::Commercial.to_u8(), 400),
+            Err(Error::InvalidLicenseType.into())
+        );
+        marketplace.set_tier_multiplier(sample_id, sync_only, 400);
+        let price = marketplace.get_license_price(sample_id, sync_only);
+        assert_eq!(price, U512::from(4 * PRICE));
+        assert!(marketplace.get_license_tier_prices(sample_id).iter().any(|tier| tier.tier_id == sync_only && tier.price == price));
+
+        env.set_caller(buyer);
+        marketplace.with_tokens(price).purchase_sample_license(sample_id, sync_only, None, Vec::new(), None);
+        assert!(licenses.verify_usage(buyer, sample_id, UsageType::Sync as u8, None).is_permitted);
+        assert!(!licenses.verify_usage(buyer, sample_id, UsageType::Streaming as u8, None).is_permitted);
+        let license_id = licenses.get_user_license(buyer, sample_id, sync_only).unwrap();
+        assert_eq!(
+            licenses.try_transfer_license(license_id, env.get_account(3), U512::zero()),
+            Err(Error::LicenseNotTransferable.into())
+        );
+
+        // Retired tiers are no longer sold
+        env.set_caller(env.get_account(0));
+        let tier = licenses.get_tier(sync_only).unwrap();
+        licenses.update_tier(sync_only, tier.name, tier.rights, tier.default_multiplier, false, false);
+        env.set_caller(env.get_account(3));
+        assert_eq!(
+            marketplace.with_tokens(price).try_purchase_sample_license(sample_id, sync_only, None, Vec::new(), None),
+            Err(Error::InvalidLicenseType.into())
+        );
+    }
+
+    // --- Territory pricing ---
+
+    #[test]
+    fn territory_licenses_are_priced_per_offered_territory() {
+        let (env, mut marketplace, licenses) = setup_with_licenses();
+        let seller = env.get_account(1);
+        let buyer = env.get_account(2);
+        let commercial = LicenseType::Commercial.to_u8();
+        let us = vec![String::from("US")];
+
+        env.set_caller(seller);
+        let sample_id = upload(&mut marketplace, content_hash('a'));
+        assert_eq!(
+            marketplace.try_set_territory_pricing(sample_id, String::from("us"), 50),
+            Err(Error::InvalidTerritory.into())
+        );
+        marketplace.set_territory_pricing(sample_id, String::from("US"), 50);
+
+        env.set_caller(buyer);
+        assert_eq!(
+            marketplace.try_set_territory_pricing(sample_id, String::from("DE"), 50),
+            Err(Error::NotSeller.into())
+        );
+        let worldwide = marketplace.get_license_price(sample_id, commercial);
+        let price = marketplace.get_territory_license_price(sample_id, commercial, us.clone());
+        assert_eq!(price, worldwide / 2);
+        assert!(marketplace
+            .get_territory_license_price(sample_id, commercial, vec![String::from("DE")])
+            .is_zero());
+        assert_eq!(
+            marketplace.with_tokens(worldwide).try_purchase_sample_license(
+                sample_id,
+                commercial,
+                None,
+                vec![String::from("DE")],
+                None
+            ),
+            Err(Error::TerritoryNotOffered.into())
+        );
+
+        marketplace.with_tokens(price).purchase_sample_license(sample_id, commercial, None, us.clone(), None);
+        let license_id = licenses.get_user_license(buyer, sample_id, commercial).unwrap();
+        let license = licenses.get_license(license_id).unwrap();
+        assert_eq!(license.purchase_price, price);
+        assert_eq!(license.territories, us);
+    }
+
+    // --- Maximum prices ---
+
+    #[test]
+    fn purchases_revert_when_the_price_rose_above_the_buyers_maximum() {
+        let (env, mut marketplace, licenses) = setup_with_licenses();
+        let seller = env.get_account(1);
+        let buyer = env.get_account(2);
+        let personal = LicenseType::Personal.to_u8();
+
+        env.set_caller(seller);
+        let sample_id = upload(&mut marketplace, content_hash('a'));
+        let quoted_sample_price = marketplace.get_sample_price(sample_id);
+        let quoted_license_price = marketplace.get_license_price(sample_id, personal);
+
+        // The seller raises prices while the buyer's purchases are in flight
+        marketplace.update_price(sample_id, U512::from(2 * PRICE));
+        marketplace.set_license_pricing(sample_id, 200, 250, 500, 2000);
+
+        env.set_caller(buyer);
+        let new_sample_price = marketplace.get_sample_price(sample_id);
+        assert_eq!(
+            marketplace.with_tokens(new_sample_price).try_purchase_sample(sample_id, Some(quoted_sample_price)),
+            Err(Error::PriceAboveMaximum.into())
+        );
+        let new_license_price = marketplace.get_license_price(sample_id, personal);
+        assert_eq!(
+            marketplace.with_tokens(new_license_price).try_purchase_sample_license(
+                sample_id,
+                personal,
+                None,
+                Vec::new(),
+                Some(quoted_license_price)
+            ),
+            Err(Error::PriceAboveMaximum.into())
+        );
+
+        // Buyers who accept the new prices can still purchase
+        marketplace.with_tokens(new_sample_price).purchase_sample(sample_id, Some(new_sample_price));
+        assert!(marketplace.has_purchased(buyer, sample_id));
+        marketplace.with_tokens(new_license_price).purchase_sample_license(
+            sample_id,
+            personal,
+            None,
+            Vec::new(),
+            Some(new_license_price),
+        );
+        assert!(licenses.get_user_license(buyer, sample_id, personal).is_some());
+    }
+
+    // --- Signed listings ---
+
+    #[test]
+    fn signed_listing_requires_domain_separated_signature() {
+        let (env, marketplace) = setup();
+        let seller = env.get_account(1);
+        let buyer = env.get_account(2);
+        let listing = listing(&env, content_hash('a'));
+
+        // A signature over the undomained payload is rejected
+        let raw = Bytes::from((marketplace.address(), listing.clone()).to_bytes().unwrap());
+        let raw_signature = env.sign_message(&raw, &seller);
+        env.set_caller(buyer);
+        assert_eq!(
+            marketplace.with_tokens(U512::from(PRICE)).try_purchase_signed_listing(
+                listing.clone(),
+                env.public_key(&seller),
+                raw_signature,
+                None,
+            ),
+            Err(Error::InvalidListingSignature.into())
+        );
+
+        let message = marketplace.get_signed_listing_message(listing.clone());
+        assert!(message.starts_with(SIGNED_LISTING_DOMAIN));
+        let signature = env.sign_message(&message, &seller);
+        marketplace.with_tokens(U512::from(PRICE)).purchase_signed_listing(
+            listing.clone(),
+            env.public_key(&seller),
+            signature.clone(),
+            None,
+        );
+
+        let sample_id = marketplace.get_signed_listing_sample(seller, listing.nonce).unwrap();
+        assert!(marketplace.has_purchased(buyer, sample_id));
+        assert_eq!(marketplace.get_sample(sample_id).unwrap().seller, seller);
+
+        // The same listing cannot be redeemed twice
+        assert_eq!(
+            marketplace.with_tokens(U512::from(PRICE)).try_purchase_signed_listing(
+                listing,
+                env.public_key(&seller),
+                signature,
+                None,
+            ),
+            Err(Error::ListingNonceUsed.into())
+        );
+    }
+
+    // --- Meta-transactions ---
+
+    #[test]
+    fn meta_transaction_runs_once_for_its_signer() {
+        let (env, mut marketplace) = setup();
+        let seller = env.get_account(1);
+        let relayer = env.get_account(2);
+        env.set_caller(seller);
+        let sample_id = upload(&mut marketplace, content_hash('a'));
+
+        let meta_tx = update_price_meta_tx(&env, sample_id, U512::from(2 * PRICE), 0);
+        let message = marketplace.get_meta_tx_message(meta_tx.clone());
+        assert!(message.starts_with(META_TX_DOMAIN));
+        let signature = env.sign_message(&message, &seller);
+
+        env.set_caller(relayer);
+        marketplace.execute_meta_transaction(meta_tx.clone(), env.public_key(&seller), signature.clone());
+        assert_eq!(marketplace.get_sample(sample_id).unwrap().price, U512::from(2 * PRICE));
+        assert_eq!(marketplace.get_meta_tx_nonce(seller), 1);
+
+        assert_eq!(
+            marketplace.try_execute_meta_transaction(meta_tx, env.public_key(&seller), signature),
+            Err(Error::InvalidMetaTxNonce.into())
+        );
+    }
+
+    #[test]
+    fn meta_transaction_rejects_undomained_or_cancelled_signatures() {
+        let (env, mut marketplace) = setup();
+        let seller = env.get_account(1);
+        let relayer = env.get_account(2);
+        env.set_caller(seller);
+        let sample_id = upload(&mut marketplace, content_hash('a'));
+
+        let meta_tx = update_price_meta_tx(&env, sample_id, U512::from(2 * PRICE), 0);
+        let raw = Bytes::from((marketplace.address(), meta_tx.clone()).to_bytes().unwrap());
+        let raw_signature = env.sign_message(&raw, &seller);
+        env.set_caller(relayer);
+        assert_eq!(
+            marketplace.try_execute_meta_transaction(meta_tx.clone(), env.public_key(&seller), raw_signature),
+            Err(Error::InvalidMetaTxSignature.into())
+        );
+
+        // Bumping the nonce cancels an action already signed with it
+        let signature = env.sign_message(&marketplace.get_meta_tx_message(meta_tx.clone()), &seller);
+        env.set_caller(seller);
+        marketplace.bump_meta_tx_nonce();
+        assert_eq!(marketplace.get_meta_tx_nonce(seller), 1);
+
+        env.set_caller(relayer);
+        assert_eq!(
+            marketplace.try_execute_meta_transaction(meta_tx, env.public_key(&seller), signature),
+            Err(Error::InvalidMetaTxNonce.into())
+        );
+        assert_eq!(marketplace.get_sample(sample_id).unwrap().price, U512::from(PRICE));
+    }
+
+    // --- Content registry ---
+
+    #[test]
+    fn upload_requires_canonical_unique_content_hash() {
+        let (env, mut marketplace) = setup();
+        env.set_caller(env.get_account(1));
+
+        for hash in [content_hash('A'), String::from("abc123"), content_hash('g')] {
+            assert_eq!(try_upload(&mut marketplace, hash), Err(Error::InvalidContentHash.into()));
+        }
+
+        let sample_id = upload(&mut marketplace, content_hash('a'));
+        assert_eq!(marketplace.get_sample_by_content_hash(content_hash('a')), Some(sample_id));
+
+        env.set_caller(env.get_account(2));
+        assert_eq!(
+            try_upload(&mut marketplace, content_hash('a')),
+            Err(Error::DuplicateContent.into())
+        );
+    }
+
+    #[test]
+    fn moderators_release_and_reassign_content_hashes() {
+        let (env, mut marketplace) = setup();
+        let admin = env.get_account(0);
+        let squatter = env.get_account(1);
+        let producer = env.get_account(2);
+        let hash = content_hash('b');
+
+        env.set_caller(squatter);
+        let squatted_id = upload(&mut marketplace, hash.clone());
+        assert_eq!(
+            marketplace.try_release_content_hash(hash.clone()),
+            Err(Error::Unauthorized.into())
+        );
+
+        // Delisting keeps the hash reserved until a moderator releases it
+        env.set_caller(admin);
+        marketplace.delist_sample(squatted_id);
+        assert_eq!(marketplace.get_sample_by_content_hash(hash.clone()), Some(squatted_id));
+        env.set_caller(producer);
+        assert_eq!(try_upload(&mut marketplace, hash.clone()), Err(Error::DuplicateContent.into()));
+
+        env.set_caller(admin);
+        marketplace.release_content_hash(hash.clone());
+        assert_eq!(marketplace.get_sample_by_content_hash(hash.clone()), None);
+        env.set_caller(producer);
+        let producer_id = upload(&mut marketplace, hash.clone());
+        assert_eq!(marketplace.get_sample_by_content_hash(hash.clone()), Some(producer_id));
+
+        // Restoring does not take the hash back
+        env.set_caller(admin);
+        marketplace.restore_sample(squatted_id);
+        assert_eq!(marketplace.get_sample_by_content_hash(hash.clone()), Some(producer_id));
+
+        // Reassignment only targets samples uploaded with the same hash
+        env.set_caller(producer);
+        let other_id = upload(&mut marketplace, content_hash('c'));
+        env.set_caller(admin);
+        assert_eq!(
+            marketplace.try_reassign_content_hash(hash.clone(), other_id),
+            Err(Error::ContentHashMismatch.into())
+        );
+        marketplace.reassign_content_hash(hash.clone(), squatted_id);
+        assert_eq!(marketplace.get_sample_by_content_hash(hash), Some(squatted_id));
+    }
+}
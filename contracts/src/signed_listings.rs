@@ -0,0 +1,41 @@
+//! Signed Listing Storage for Sampled Marketplace
+//!
+//! Sellers can sign listings off-chain instead of uploading them (lazy
+//! listing); the sample is created by the first buyer's purchase.
+//! Each listing carries a seller-chosen nonce that is:
+//! - Consumed when the listing is redeemed, so a signature works only once
+//! - Cancellable by the seller before anyone redeems it
+
+use odra::prelude::*;
+
+/// Used nonces and redeemed listings per seller
+#[odra::module]
+pub struct SignedListingStore {
+    /// Whether a seller's listing nonce is used or cancelled: (seller, nonce) -> bool
+    used_nonces: Mapping<(Address, u64), bool>,
+    /// Sample created from a redeemed listing: (seller, nonce) -> sample_id
+    listing_sample: Mapping<(Address, u64), u64>,
+}
+
+impl SignedListingStore {
+    /// Check if a seller's listing nonce has been redeemed or cancelled
+    pub fn is_used(&self, seller: &Address, nonce: u64) -> bool {
+        self.used_nonces.get_or_default(&(*seller, nonce))
+    }
+
+    /// Mark a listing as redeemed into a sample
+    pub fn redeem(&mut self, seller: Address, nonce: u64, sample_id: u64) {
+        self.used_nonces.set(&(seller, nonce), true);
+        self.listing_sample.set(&(seller, nonce), sample_id);
+    }
+
+    /// Invalidate a listing nonce without creating a sample
+    pub fn cancel(&mut self, seller: Address, nonce: u64) {
+        self.used_nonces.set(&(seller, nonce), true);
+    }
+
+    /// Get the sample created from a redeemed listing (if any)
+    pub fn sample(&self, seller: &Address, nonce: u64) -> Option<u64> {
+        self.listing_sample.get(&(*seller, nonce))
+    }
+}
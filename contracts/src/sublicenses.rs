@@ -0,0 +1,72 @@
+//! Sublicense Storage for the License NFT contract
+//!
+//! Child licenses issued by license holders to the agencies and editors
+//! working on a specific project:
+//! - The scope (usage rights) granted to each sublicense
+//! - Sublicenses issued under each parent license
+//! - Sublicenses held per (holder, sample), for usage verification
+
+use odra::prelude::*;
+
+use crate::license_types::UsageRights;
+
+/// Sublicense scopes and indexes
+#[odra::module]
+pub struct Sublicenses {
+    /// Rights granted to a sublicense: license_id -> scope
+    scopes: Mapping<u64, UsageRights>,
+
+    /// Count of sublicenses per parent: parent_license_id -> count
+    child_count: Mapping<u64, u64>,
+    /// Indexed sublicenses: (parent_license_id, index) -> license_id
+    child_at: Mapping<(u64, u64), u64>,
+
+    /// Count of sublicenses per holder and sample: (holder, sample_id) -> count
+    holder_count: Mapping<(Address, u64), u64>,
+    /// Indexed sublicenses: (holder, sample_id, index) -> license_id
+    holder_at: Mapping<(Address, u64, u64), u64>,
+}
+
+impl Sublicenses {
+    /// Record a sublicense under its parent and its holder
+    pub fn add(&mut self, parent_license_id: u64, license_id: u64, holder: Address, sample_id: u64, scope: UsageRights) {
+        self.scopes.set(&license_id, scope);
+
+        let count = self.child_count.get_or_default(&parent_license_id);
+        self.child_at.set(&(parent_license_id, count), license_id);
+        self.child_count.set(&parent_license_id, count + 1);
+
+        let count = self.holder_count.get_or_default(&(holder, sample_id));
+        self.holder_at.set(&(holder, sample_id, count), license_id);
+        self.holder_count.set(&(holder, sample_id), count + 1);
+    }
+
+    /// Get the rights granted to a sublicense
+    pub fn scope(&self, license_id: u64) -> Option<UsageRights> {
+        self.scopes.get(&license_id)
+    }
+
+    /// Get all sublicenses issued under a parent license
+    pub fn children(&self, parent_license_id: u64) -> Vec<u64> {
+        let count = self.child_count.get_or_default(&parent_license_id);
+        let mut result = Vec::new();
+        for i in 0..count {
+            if let Some(id) = self.child_at.get(&(parent_license_id, i)) {
+                result.push(id);
+            }
+        }
+        result
+    }
+
+    /// Get all sublicenses a holder has received for a sample
+    pub fn held(&self, holder: &Address, sample_id: u64) -> Vec<u64> {
+        let count = self.holder_count.get_or_default(&(*holder, sample_id));
+        let mut result = Vec::new();
+        for i in 0..count {
+            if let Some(id) = self.holder_at.get(&(*holder, sample_id, i)) {
+                result.push(id);
+            }
+        }
+        result
+    }
+}
@@ -0,0 +1,93 @@
+//! Producer Subscriptions for Sampled Marketplace
+//!
+//! Stores producer-defined subscription plans and each subscriber's
+//! access to a producer's catalog.
+//! Features:
+//! - Plans with a price per period and an included (Personal) license type
+//! - One subscription per (subscriber, producer) with a rolling expiry
+//! - Renewals extend from the current expiry, or from now once lapsed
+
+use odra::prelude::*;
+
+use crate::types::{SubscriptionPlan, Subscription};
+
+/// Subscription plan and subscriber storage
+#[odra::module]
+pub struct SubscriptionStore {
+    /// Mapping of plan ID to plan data
+    plans: Mapping<u64, SubscriptionPlan>,
+    /// Total number of plans (used for generating IDs)
+    plan_count: Var<u64>,
+    /// Count of plans created by each producer
+    producer_plan_count: Mapping<Address, u64>,
+    /// Indexed plan IDs: (producer, index) -> plan_id
+    producer_plan_at: Mapping<(Address, u64), u64>,
+    /// Subscriptions: (subscriber, producer) -> Subscription
+    subscriptions: Mapping<(Address, Address), Subscription>,
+}
+
+impl SubscriptionStore {
+    /// Allocate the next plan ID
+    pub fn next_plan_id(&mut self) -> u64 {
+        let plan_id = self.plan_count.get_or_default() + 1;
+        self.plan_count.set(plan_id);
+        plan_id
+    }
+
+    /// Store a new plan and index it under its producer
+    pub fn add_plan(&mut self, plan: SubscriptionPlan) {
+        let producer = plan.producer;
+        let plan_id = plan.plan_id;
+        let count = self.producer_plan_count.get_or_default(&producer);
+        self.producer_plan_at.set(&(producer, count), plan_id);
+        self.producer_plan_count.set(&producer, count + 1);
+        self.plans.set(&plan_id, plan);
+    }
+
+    /// Overwrite an existing plan
+    pub fn update_plan(&mut self, plan: SubscriptionPlan) {
+        let plan_id = plan.plan_id;
+        self.plans.set(&plan_id, plan);
+    }
+
+    /// Get a plan by ID
+    pub fn plan(&self, plan_id: u64) -> Option<SubscriptionPlan> {
+        self.plans.get(&plan_id)
+    }
+
+    /// Get all plans created by a producer
+    pub fn producer_plans(&self, producer: &Address) -> Vec<SubscriptionPlan> {
+        let count = self.producer_plan_count.get_or_default(producer);
+        let mut result = Vec::new();
+        for i in 0..count {
+            if let Some(plan_id) = self.producer_plan_at.get(&(*producer, i)) {
+                if let Some(plan) = self.plans.get(&plan_id) {
+                    result.push(plan);
+                }
+            }
+        }
+        result
+    }
+
+    /// Get a subscriber's subscription to a producer (active or lapsed)
+    pub fn subscription(&self, subscriber: &Address, producer: &Address) -> Option<Subscription> {
+        self.subscriptions.get(&(*subscriber, *producer))
+    }
+
+    /// Store a subscriber's subscription to a producer
+    pub fn set_subscription(&mut self, subscriber: Address, producer: Address, subscription: Subscription) {
+        self.subscriptions.set(&(subscriber, producer), subscription);
+    }
+
+    /// Check if a subscriber has an active subscription to a producer
+    pub fn is_subscribed(&self, subscriber: &Address, producer: &Address, now: u64) -> bool {
+        self.subscription(subscriber, producer)
+            .is_some_and(|subscription| subscription.is_active(now))
+    }
+
+    /// Check if a subscriber's active subscription to a producer grants a license type
+    pub fn grants(&self, subscriber: &Address, producer: &Address, license_type: u8, now: u64) -> bool {
+        self.subscription(subscriber, producer)
+            .is_some_and(|subscription| subscription.is_active(now) && subscription.license_type == license_type)
+    }
+}
@@ -0,0 +1,54 @@
+//! License Tier Registry for the License NFT contract
+//!
+//! Admin-registered license tiers. IDs 0-3 are the built-in `LicenseType`
+//! defaults (Personal, Commercial, Broadcast, Exclusive), which can be
+//! reconfigured but not removed; custom tiers ("Sync-only", "Non-profit", ...)
+//! get the following IDs.
+
+use odra::prelude::*;
+
+use crate::license_types::{LicenseType, LicenseTier};
+
+/// Registered license tiers
+#[odra::module]
+pub struct TierRegistry {
+    /// Tier data: tier_id -> LicenseTier (built-in tiers only once reconfigured)
+    tiers: Mapping<u8, LicenseTier>,
+    /// Number of custom tiers registered
+    custom_tier_count: Var<u8>,
+}
+
+impl TierRegistry {
+    /// ID the next custom tier will get (None once all IDs are taken)
+    pub fn next_tier_id(&self) -> Option<u8> {
+        (LicenseType::all().len() as u8).checked_add(self.custom_tier_count.get_or_default())
+    }
+
+    /// Register a custom tier under the next free ID
+    pub fn register(&mut self, tier: LicenseTier) {
+        let count = self.custom_tier_count.get_or_default();
+        self.custom_tier_count.set(count + 1);
+        let tier_id = tier.tier_id;
+        self.tiers.set(&tier_id, tier);
+    }
+
+    /// Store a tier's updated configuration
+    pub fn update(&mut self, tier: LicenseTier) {
+        let tier_id = tier.tier_id;
+        self.tiers.set(&tier_id, tier);
+    }
+
+    /// Get a tier by ID, falling back to the built-in defaults for IDs 0-3
+    pub fn tier(&self, tier_id: u8) -> Option<LicenseTier> {
+        self.tiers.get(&tier_id)
+            .or_else(|| LicenseType::from_u8(tier_id).map(|lt| lt.default_tier()))
+    }
+
+    /// Get every tier, built-in tiers first
+    pub fn all_tiers(&self) -> Vec<LicenseTier> {
+        let count = LicenseType::all().len() as u64 + self.custom_tier_count.get_or_default() as u64;
+        (0..count)
+            .filter_map(|tier_id| self.tier(tier_id as u8))
+            .collect()
+    }
+}
@@ -2,15 +2,19 @@
 
 use odra::prelude::*;
 use odra::casper_types::U512;
+use odra::casper_types::bytesrepr::Bytes;
+
+use crate::license_types::LicensePricing;
 
 /// Represents a music sample listed on the marketplace
+/// Prices are in motes for CSPR samples, or in token units for CEP-18 samples
 #[odra::odra_type]
 pub struct Sample {
     /// Unique identifier for this sample
     pub sample_id: u64,
     /// Address of the seller who uploaded this sample
     pub seller: Address,
-    /// Price in motes (1 CSPR = 10^9 motes)
+    /// Price in motes (1 CSPR = 10^9 motes) or token units
     pub price: U512,
     /// IPFS link to the audio file
     pub ipfs_link: String,
@@ -30,6 +34,45 @@ pub struct Sample {
     pub is_active: bool,
     /// Unix timestamp when the sample was created
     pub created_at: u64,
+    /// Optional timestamp when the sample goes on sale (None = immediately)
+    pub sale_starts_at: Option<u64>,
+    /// Optional timestamp when sales end automatically (None = never)
+    pub sale_ends_at: Option<u64>,
+    /// Payment currency (None = CSPR, Some = CEP-18 token contract)
+    pub currency: Option<Address>,
+    /// Optional USD price in cents; when set, `price` is ignored and the
+    /// motes price is derived from the price feed at purchase time
+    pub usd_price_cents: Option<u64>,
+    /// Pay-what-you-want mode: `price` is the minimum (may be zero) and
+    /// buyers attaching CSPR pay whatever they attach
+    pub pay_what_you_want: bool,
+    /// Whether a moderator has removed this sample from sale
+    /// (independent of the seller's own deactivation)
+    pub is_delisted: bool,
+    /// SHA-256 of the audio file as 64 lowercase hex characters (unique across the marketplace)
+    pub content_hash: String,
+    /// Optional perceptual fingerprint of the audio
+    pub fingerprint: Option<String>,
+}
+
+impl Sample {
+    /// Check whether the sale window has opened at the given block time
+    pub fn has_sale_started(&self, now: u64) -> bool {
+        self.sale_starts_at.is_none_or(|starts_at| now >= starts_at)
+    }
+
+    /// Check whether the sale window has closed at the given block time
+    pub fn has_sale_ended(&self, now: u64) -> bool {
+        self.sale_ends_at.is_some_and(|ends_at| now >= ends_at)
+    }
+
+    /// Check whether the sample can be purchased at the given block time
+    pub fn is_on_sale(&self, now: u64) -> bool {
+        self.is_active
+            && !self.is_delisted
+            && self.has_sale_started(now)
+            && !self.has_sale_ended(now)
+    }
 }
 
 /// Record of a purchase made by a user
@@ -45,6 +88,144 @@ pub struct PurchaseRecord {
     pub timestamp: u64,
     /// IPFS link to access the purchased content
     pub ipfs_link: String,
+    /// Currency the price was paid in (None = CSPR)
+    pub currency: Option<Address>,
+    /// Motes per USD used to convert a USD-priced sample (None = not converted)
+    pub conversion_rate: Option<U512>,
+}
+
+/// CSPR/USD price round reported by a price feed contract
+#[odra::odra_type]
+pub struct PriceRound {
+    /// Sequential round number (each price update starts a new round)
+    pub round_id: u64,
+    /// Number of motes per 1 USD
+    pub motes_per_usd: U512,
+    /// Timestamp when the price was last updated
+    pub updated_at: u64,
+}
+
+/// Price feed configuration set by the admin
+#[odra::odra_type]
+pub struct PriceFeedConfig {
+    /// Address of the price feed contract
+    pub feed: Address,
+    /// Maximum age of a price round before it is considered stale
+    pub max_staleness: u64,
+    /// Maximum change from the previous price round in basis points (0 = no limit)
+    pub max_deviation_bps: u64,
+}
+
+/// Producer-defined subscription plan
+#[odra::odra_type]
+pub struct SubscriptionPlan {
+    /// Unique identifier for the plan
+    pub plan_id: u64,
+    /// Producer offering the plan (receives the revenue)
+    pub producer: Address,
+    /// Price per period in motes
+    pub price: U512,
+    /// Length of one subscription period
+    pub period: u64,
+    /// License type granted on the producer's catalog (always `LicenseType::Personal`)
+    pub license_type: u8,
+    /// Whether new subscriptions and renewals are accepted
+    pub is_active: bool,
+    /// Timestamp when the plan was created
+    pub created_at: u64,
+}
+
+/// A subscriber's access to a producer's catalog
+#[odra::odra_type]
+pub struct Subscription {
+    /// Plan the subscriber last paid for
+    pub plan_id: u64,
+    /// License type granted on the producer's catalog
+    pub license_type: u8,
+    /// Timestamp when the subscription started
+    pub started_at: u64,
+    /// Timestamp when access expires
+    pub expires_at: u64,
+}
+
+impl Subscription {
+    /// Check if the subscription grants access at the given time
+    pub fn is_active(&self, now: u64) -> bool {
+        now < self.expires_at
+    }
+}
+
+/// Reason a sample was reported
+#[odra::odra_type]
+#[derive(Default, Copy)]
+pub enum ReportReason {
+    /// Stolen or uncleared audio
+    #[default]
+    Copyright = 0,
+    /// Offensive or illegal content
+    Inappropriate = 1,
+    /// Spam, duplicates or misleading metadata
+    Spam = 2,
+    /// Anything else
+    Other = 3,
+}
+
+impl ReportReason {
+    /// Convert from u8 to ReportReason
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(ReportReason::Copyright),
+            1 => Some(ReportReason::Inappropriate),
+            2 => Some(ReportReason::Spam),
+            3 => Some(ReportReason::Other),
+            _ => None,
+        }
+    }
+}
+
+/// Lifecycle of a report
+#[odra::odra_type]
+#[derive(Default, Copy)]
+pub enum ReportStatus {
+    /// Awaiting moderator review
+    #[default]
+    Open = 0,
+    /// Reviewed and upheld
+    Resolved = 1,
+    /// Reviewed and rejected
+    Dismissed = 2,
+}
+
+impl ReportStatus {
+    /// Convert ReportStatus to u8
+    pub fn to_u8(&self) -> u8 {
+        match self {
+            ReportStatus::Open => 0,
+            ReportStatus::Resolved => 1,
+            ReportStatus::Dismissed => 2,
+        }
+    }
+}
+
+/// A user report filed against a sample
+#[odra::odra_type]
+pub struct Report {
+    /// Unique identifier for the report
+    pub report_id: u64,
+    /// Sample being reported
+    pub sample_id: u64,
+    /// Address that filed the report
+    pub reporter: Address,
+    /// Reason code (see `ReportReason`)
+    pub reason_code: u8,
+    /// Current status
+    pub status: ReportStatus,
+    /// Timestamp when the report was filed
+    pub created_at: u64,
+    /// Moderator who closed the report
+    pub resolved_by: Option<Address>,
+    /// Timestamp when the report was closed
+    pub resolved_at: Option<u64>,
 }
 
 /// User statistics
@@ -61,6 +242,95 @@ pub struct UserStats {
     pub total_earned: U512,
     /// Total amount ever spent on purchases
     pub total_spent: U512,
+    /// Total tips received, after the platform fee
+    pub tips_received: U512,
+    /// Total tips given to producers
+    pub tips_given: U512,
+}
+
+/// Referral statistics for a referrer (CSPR commission)
+#[odra::odra_type]
+#[derive(Default)]
+pub struct ReferrerStats {
+    /// Number of referred sales
+    pub referral_count: u64,
+    /// Total commission ever earned in motes
+    pub total_earned: U512,
+}
+
+/// Listing a seller signs off-chain instead of uploading it (lazy listing)
+/// The sample is created on-chain by the first purchase that carries the signature
+#[odra::odra_type]
+pub struct SignedListing {
+    /// Price in motes (signed listings are always priced in CSPR)
+    pub price: U512,
+    /// IPFS link to the audio file
+    pub ipfs_link: String,
+    /// Title of the sample
+    pub title: String,
+    /// Beats per minute
+    pub bpm: u64,
+    /// Music genre
+    pub genre: String,
+    /// IPFS link to the cover image
+    pub cover_image: String,
+    /// Optional IPFS link to video preview
+    pub video_preview_link: String,
+    /// Custom license multipliers (None = the seller's default pricing)
+    pub license_pricing: Option<LicensePricing>,
+    /// Timestamp after which the signature can no longer be redeemed
+    pub expires_at: u64,
+    /// Seller-chosen nonce; each nonce can be redeemed or cancelled once
+    pub nonce: u64,
+    /// SHA-256 of the audio file as 64 lowercase hex characters (unique across the marketplace)
+    pub content_hash: String,
+    /// Optional perceptual fingerprint of the audio
+    pub fingerprint: Option<String>,
+}
+
+/// Seller actions a relayer can submit on a user's behalf
+#[odra::odra_type]
+#[derive(Default, Copy)]
+pub enum MetaTxAction {
+    /// `upload_sample`; args: ((price, pay_what_you_want), ipfs_link, title, bpm, genre, cover_image,
+    /// video_preview_link, (sale_starts_at, sale_ends_at), (content_hash, fingerprint), pricing_template)
+    #[default]
+    UploadSample = 0,
+    /// `update_price`; args: (sample_id, new_price)
+    UpdatePrice = 1,
+    /// `deactivate_sample`; args: sample_id
+    DeactivateSample = 2,
+    /// `set_license_pricing`; args: (sample_id, personal, commercial, broadcast, exclusive)
+    SetLicensePricing = 3,
+    /// `withdraw_earnings` / `withdraw_token_earnings`; args: token (None = CSPR)
+    WithdrawEarnings = 4,
+}
+
+impl MetaTxAction {
+    /// Convert from u8 to MetaTxAction
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(MetaTxAction::UploadSample),
+            1 => Some(MetaTxAction::UpdatePrice),
+            2 => Some(MetaTxAction::DeactivateSample),
+            3 => Some(MetaTxAction::SetLicensePricing),
+            4 => Some(MetaTxAction::WithdrawEarnings),
+            _ => None,
+        }
+    }
+}
+
+/// Action a user signs off-chain for a relayer to submit (meta-transaction)
+#[odra::odra_type]
+pub struct MetaTransaction {
+    /// Action to run (see `MetaTxAction`)
+    pub action: u8,
+    /// The action's arguments, serialized as a tuple of its parameters
+    pub args: Bytes,
+    /// Signer's next nonce (see `get_meta_tx_nonce`)
+    pub nonce: u64,
+    /// Timestamp after which the signature can no longer be submitted
+    pub deadline: u64,
 }
 
 /// Marketplace statistics
@@ -87,6 +357,24 @@ pub mod constants {
     pub const MAX_GENRE_LENGTH: usize = 30;
     /// Maximum length for video preview links
     pub const MAX_VIDEO_PREVIEW_LENGTH: usize = 256;
+    /// Length of audio content hashes (hex-encoded SHA-256)
+    pub const CONTENT_HASH_LENGTH: usize = 64;
+    /// Maximum length for perceptual audio fingerprints
+    pub const MAX_FINGERPRINT_LENGTH: usize = 256;
+    /// Maximum length for seller pricing template names
+    pub const MAX_TEMPLATE_NAME_LENGTH: usize = 32;
+    /// Basis points denominator (100% = 10,000 bps)
+    pub const BPS_DENOMINATOR: u64 = 10_000;
+    /// Maximum number of samples repriced by one `reprice_catalog` call
+    pub const MAX_REPRICE_BATCH: u64 = 50;
+    /// Domain prefix of signed listing messages
+    pub const SIGNED_LISTING_DOMAIN: &[u8] = b"SAMPLED_LISTING";
+    /// Domain prefix of meta-transaction messages
+    pub const META_TX_DOMAIN: &[u8] = b"SAMPLED_META_TX";
+    /// Maximum number of recipients in one promotional license grant
+    pub const MAX_GRANT_RECIPIENTS: usize = 50;
+    /// Cents per USD
+    pub const CENTS_PER_USD: u64 = 100;
     /// Platform fee: 10% (numerator)
     pub const PLATFORM_FEE_NUMERATOR: u64 = 10;
     /// Platform fee: 100 (denominator)
@@ -0,0 +1,100 @@
+//! User Activity Storage for Sampled Marketplace
+//!
+//! Indexed per-user lists kept by the marketplace:
+//! - Samples uploaded by each user
+//! - Samples purchased by each user, with their purchase records
+
+use odra::prelude::*;
+
+use crate::types::PurchaseRecord;
+
+/// Per-user upload and purchase indexes (using indexed mappings)
+#[odra::module]
+pub struct UserActivity {
+    /// Count of samples uploaded by each user
+    uploaded_count: Mapping<Address, u64>,
+    /// Indexed sample IDs: (user, index) -> sample_id
+    uploaded_at: Mapping<(Address, u64), u64>,
+
+    /// Count of samples purchased by each user
+    purchased_count: Mapping<Address, u64>,
+    /// Indexed purchased sample IDs: (user, index) -> sample_id
+    purchased_at: Mapping<(Address, u64), u64>,
+
+    /// Purchase records for each user (buyer, sample_id) -> PurchaseRecord
+    purchase_records: Mapping<(Address, u64), PurchaseRecord>,
+}
+
+impl UserActivity {
+    /// Add a sample to the seller's uploads
+    pub fn add_upload(&mut self, seller: Address, sample_id: u64) {
+        let count = self.uploaded_count.get_or_default(&seller);
+        self.uploaded_at.set(&(seller, count), sample_id);
+        self.uploaded_count.set(&seller, count + 1);
+    }
+
+    /// Add a sample to the buyer's purchases
+    pub fn add_purchase(&mut self, buyer: Address, sample_id: u64) {
+        let count = self.purchased_count.get_or_default(&buyer);
+        self.purchased_at.set(&(buyer, count), sample_id);
+        self.purchased_count.set(&buyer, count + 1);
+    }
+
+    /// Store the buyer's purchase record for a sample
+    pub fn set_purchase_record(&mut self, buyer: Address, record: PurchaseRecord) {
+        let sample_id = record.sample_id;
+        self.purchase_records.set(&(buyer, sample_id), record);
+    }
+
+    /// Get the buyer's purchase record for a sample
+    pub fn purchase_record(&self, buyer: &Address, sample_id: u64) -> Option<PurchaseRecord> {
+        self.purchase_records.get(&(*buyer, sample_id))
+    }
+
+    /// Number of samples uploaded by a user
+    pub fn uploaded_count(&self, user: &Address) -> u64 {
+        self.uploaded_count.get_or_default(user)
+    }
+
+    /// Number of purchases made by a user
+    pub fn purchased_count(&self, user: &Address) -> u64 {
+        self.purchased_count.get_or_default(user)
+    }
+
+    /// Get user's uploaded sample IDs
+    pub fn uploaded_samples(&self, user: &Address) -> Vec<u64> {
+        let count = self.uploaded_count.get_or_default(user);
+        let mut result = Vec::new();
+        for i in 0..count {
+            if let Some(id) = self.uploaded_at.get(&(*user, i)) {
+                result.push(id);
+            }
+        }
+        result
+    }
+
+    /// Get up to `limit` of a user's uploaded sample IDs, starting at `offset`
+    pub fn uploaded_page(&self, user: &Address, offset: u64, limit: u64) -> Vec<u64> {
+        let count = self.uploaded_count.get_or_default(user);
+        let end = offset.saturating_add(limit).min(count);
+        let mut result = Vec::new();
+        for i in offset..end {
+            if let Some(id) = self.uploaded_at.get(&(*user, i)) {
+                result.push(id);
+            }
+        }
+        result
+    }
+
+    /// Get user's purchased sample IDs
+    pub fn purchased_samples(&self, user: &Address) -> Vec<u64> {
+        let count = self.purchased_count.get_or_default(user);
+        let mut result = Vec::new();
+        for i in 0..count {
+            if let Some(id) = self.purchased_at.get(&(*user, i)) {
+                result.push(id);
+            }
+        }
+        result
+    }
+}
@@ -136,6 +136,10 @@ export interface ISampleLicenseInfo {
 export interface IPurchaseLicensePayload {
   sample_id: number
   license_type: LicenseType
+  /** Terms hash the buyer agreed to; must match the tier's terms in force */
+  accepted_terms?: string
+  /** Territories to license (ISO codes); empty or undefined = worldwide */
+  territories?: string[]
 }
 
 /**
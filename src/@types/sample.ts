@@ -1,12 +1,24 @@
 export interface IUploadSamplePayload {
   seller: string
   price: bigint
+  /** When true, `price` is the minimum buyers pay (zero allows free downloads) */
+  pay_what_you_want?: boolean
   ipfs_link: string
   title: string
   bpm: number
   genre: string
   cover_image: string
   video_preview_link: string
+  /** Block time (Unix milliseconds) the sample goes on sale; undefined = immediately */
+  sale_starts_at?: number
+  /** Block time (Unix milliseconds) the sale ends; undefined = never */
+  sale_ends_at?: number
+  /** SHA-256 of the audio file as 64 lowercase hex characters */
+  content_hash: string
+  /** Optional perceptual fingerprint for near-duplicate detection */
+  fingerprint?: string
+  /** Name of one of the seller's pricing templates; undefined = default pricing */
+  pricing_template?: string
 }
 
 export interface ISample {
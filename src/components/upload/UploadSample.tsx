@@ -27,6 +27,9 @@ interface SampleFormData {
   tags: string[];
   coverImage?: File | null;
   videoPreview?: File | null;
+  saleStartsAt: string;
+  saleEndsAt: string;
+  pricingTemplate: string;
 }
 
 interface UploadProgress {
@@ -97,6 +100,9 @@ const UploadUI: React.FC<UploadUIProps> = ({ platformFeePercentage = 10 }) => {
     genre: Genre.Trap,
     tags: [],
     coverImage: null,
+    saleStartsAt: "",
+    saleEndsAt: "",
+    pricingTemplate: "",
   });
 
   const [errors, setErrors] = useState<
@@ -300,6 +306,14 @@ const UploadUI: React.FC<UploadUIProps> = ({ platformFeePercentage = 10 }) => {
       newErrors.title = "Please select a file";
     }
 
+    if (
+      formData.saleStartsAt &&
+      formData.saleEndsAt &&
+      new Date(formData.saleEndsAt) <= new Date(formData.saleStartsAt)
+    ) {
+      newErrors.saleEndsAt = "Sale end must be after sale start";
+    }
+
     setErrors(newErrors);
     return Object.keys(newErrors).length === 0;
   };
@@ -322,6 +336,9 @@ const UploadUI: React.FC<UploadUIProps> = ({ platformFeePercentage = 10 }) => {
       tags: [],
       coverImage: null,
       videoPreview: null,
+      saleStartsAt: "",
+      saleEndsAt: "",
+      pricingTemplate: "",
     });
     setErrors({});
     setUploadProgress({ status: "idle", percentage: 0 });
@@ -343,6 +360,21 @@ const UploadUI: React.FC<UploadUIProps> = ({ platformFeePercentage = 10 }) => {
     return (bytes / (1024 * 1024)).toFixed(1) + " MB";
   };
 
+  // Block time (Unix milliseconds, as the contract compares it) from a datetime-local value; undefined when empty
+  const toBlockTime = (value: string): number | undefined =>
+    value ? new Date(value).getTime() : undefined;
+
+  // SHA-256 of the audio file as 64 lowercase hex characters (the contract's content hash)
+  const hashFile = async (audioFile: File): Promise<string> => {
+    const digest = await crypto.subtle.digest(
+      "SHA-256",
+      await audioFile.arrayBuffer(),
+    );
+    return Array.from(new Uint8Array(digest))
+      .map((byte) => byte.toString(16).padStart(2, "0"))
+      .join("");
+  };
+
   const calculateEarnings = (): string => {
     if (!formData.price) return "0";
     const price = parseFloat(formData.price);
@@ -384,6 +416,7 @@ const UploadUI: React.FC<UploadUIProps> = ({ platformFeePercentage = 10 }) => {
     if (!validateForm() || !file) return;
 
     try {
+      const contentHash = await hashFile(file);
       const audioLink = await uploadFile(file);
 
       // Upload cover image if provided
@@ -407,6 +440,10 @@ const UploadUI: React.FC<UploadUIProps> = ({ platformFeePercentage = 10 }) => {
         seller: account?.address ?? "",
         cover_image: coverImageLink,
         video_preview_link: videoPreviewLink,
+        sale_starts_at: toBlockTime(formData.saleStartsAt),
+        sale_ends_at: toBlockTime(formData.saleEndsAt),
+        content_hash: contentHash,
+        pricing_template: formData.pricingTemplate.trim() || undefined,
       });
 
       toast.success("Success", {
@@ -625,6 +662,48 @@ const UploadUI: React.FC<UploadUIProps> = ({ platformFeePercentage = 10 }) => {
                   </select>
                 </div>
 
+                <div className="input-grid">
+                  <div className="form-group">
+                    <label className="form-label">Sale Starts (Optional)</label>
+                    <input
+                      type="datetime-local"
+                      className="form-input"
+                      value={formData.saleStartsAt}
+                      onChange={(e) =>
+                        handleInputChange("saleStartsAt", e.target.value)
+                      }
+                    />
+                  </div>
+
+                  <div className="form-group">
+                    <label className="form-label">Sale Ends (Optional)</label>
+                    <input
+                      type="datetime-local"
+                      className={`form-input ${errors.saleEndsAt ? "error" : ""}`}
+                      value={formData.saleEndsAt}
+                      onChange={(e) =>
+                        handleInputChange("saleEndsAt", e.target.value)
+                      }
+                    />
+                    {errors.saleEndsAt && (
+                      <div className="error-text">{errors.saleEndsAt}</div>
+                    )}
+                  </div>
+                </div>
+
+                <div className="form-group">
+                  <label className="form-label">Pricing Template (Optional)</label>
+                  <input
+                    type="text"
+                    className="form-input"
+                    placeholder="Default pricing"
+                    value={formData.pricingTemplate}
+                    onChange={(e) =>
+                      handleInputChange("pricingTemplate", e.target.value)
+                    }
+                  />
+                </div>
+
                 {/* Stats preview */}
                 <div className="stats-preview">
                   <div className="stat-card">
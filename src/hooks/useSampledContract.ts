@@ -17,7 +17,12 @@ import {
   RuntimeArgs,
   CLValueBuilder,
   CLPublicKey,
+  CLList,
+  CLStringType,
+  CLU64Type,
+  CLU512Type,
 } from "casper-js-sdk"
+import { Some, None } from "ts-results"
 import axios from "axios"
 
 
@@ -45,27 +50,35 @@ const GAS_UPDATE_PRICE = "3000000000" // 3 CSPR
 const GAS_DEACTIVATE_SAMPLE = "3000000000" // 3 CSPR
 const GAS_PURCHASE_LICENSE = "30000000000" // 30 CSPR (higher for license minting)
 const GAS_SET_LICENSE_PRICING = "3000000000" // 3 CSPR
+const GAS_QUOTE_PRICE = "3000000000" // 3 CSPR (only simulated, never charged)
 
 // Default TTL for deploys (30 minutes)
 const DEFAULT_TTL = 1800000
 
-// Cache for proxy caller WASM
-let proxyCallerWasmCache: Uint8Array | null = null
+// Cache for proxy caller WASM (keyed by file name)
+const proxyCallerWasmCache = new Map<string, Uint8Array>()
 
-/** Load the proxy caller WASM for payable function calls */
-const loadProxyCallerWasm = async (): Promise<Uint8Array> => {
-  if (proxyCallerWasmCache) {
-    return proxyCallerWasmCache
+/**
+ * Load a proxy caller WASM: proxy_caller.wasm for payable function calls,
+ * proxy_caller_with_return.wasm to read a view's return value from its effects
+ */
+const loadProxyCallerWasm = async (
+  fileName = "proxy_caller.wasm"
+): Promise<Uint8Array> => {
+  const cached = proxyCallerWasmCache.get(fileName)
+  if (cached) {
+    return cached
   }
 
-  const response = await fetch("/wasm/proxy_caller.wasm")
+  const response = await fetch(`/wasm/${fileName}`)
   if (!response.ok) {
-    throw new Error("Failed to load proxy_caller.wasm")
+    throw new Error(`Failed to load ${fileName}`)
   }
 
   const arrayBuffer = await response.arrayBuffer()
-  proxyCallerWasmCache = new Uint8Array(arrayBuffer)
-  return proxyCallerWasmCache
+  const wasm = new Uint8Array(arrayBuffer)
+  proxyCallerWasmCache.set(fileName, wasm)
+  return wasm
 }
 
 /** Build a payable contract call deploy using proxy_caller */
@@ -74,10 +87,11 @@ const buildPayableContractCallDeploy = async (
   entryPoint: string,
   functionArgs: RuntimeArgs,
   attachedValue: string,
-  paymentAmount: string
+  paymentAmount: string,
+  proxyCallerFile?: string
 ): Promise<DeployUtil.Deploy> => {
   // Load proxy caller WASM
-  const proxyCallerWasm = await loadProxyCallerWasm()
+  const proxyCallerWasm = await loadProxyCallerWasm(proxyCallerFile)
 
   // Convert contract package hash hex to Uint8Array (32 bytes)
   const packageHashBytes = hexToUint8Array(CONTRACT_PACKAGE_HASH)
@@ -291,6 +305,73 @@ const sendDeploy = async (signedDeployJson: string): Promise<string> => {
   return result.deploy_hash
 }
 
+/** Find the bytes the proxy caller stored under `__result` (supports both v1 and v2 effect formats) */
+const findProxyCallerResult = (effects: any[]): string | null => {
+  // The result is written to a URef stored under the `__result` named key
+  const uref = effects
+    .map(effect => effect.kind ?? effect.transform)
+    .flatMap((kind: any): string[] => {
+      if (kind?.AddKeys) {
+        return kind.AddKeys
+          .filter((namedKey: any) => namedKey.name === "__result")
+          .map((namedKey: any) => namedKey.key)
+      }
+      const namedKey = kind?.Write?.NamedKey
+      return namedKey?.name?.parsed === "__result" ? [namedKey.named_key.parsed] : []
+    })[0]
+  if (!uref) {
+    return null
+  }
+
+  // Compare URefs without their access rights suffix
+  const urefAddress = uref.substring(0, uref.lastIndexOf("-"))
+  const write = effects.find(effect =>
+    effect.key.startsWith(urefAddress) && (effect.kind?.Write?.CLValue ?? effect.transform?.WriteCLValue)
+  )
+  return (write?.kind?.Write?.CLValue ?? write?.transform?.WriteCLValue)?.bytes ?? null
+}
+
+/**
+ * Quote a price (U512 in motes) from a contract view entry point.
+ * The wallet signs the quote deploy, but it is only simulated with speculative execution, never sent.
+ */
+const quoteContractPrice = async (
+  publicKey: CLPublicKey,
+  signDeploy: (deployJson: string) => Promise<string>,
+  entryPoint: string,
+  args: RuntimeArgs
+): Promise<string> => {
+  const deploy = await buildPayableContractCallDeploy(
+    publicKey,
+    entryPoint,
+    args,
+    "0",
+    GAS_QUOTE_PRICE,
+    "proxy_caller_with_return.wasm"
+  )
+  const signedDeployJson = await signDeploy(JSON.stringify(DeployUtil.deployToJson(deploy)))
+  const result = await rpcCall<any>("speculative_exec", [JSON.parse(signedDeployJson)])
+
+  const execResult = result.execution_result
+  const errorMessage = execResult?.error_message ?? execResult?.Failure?.error_message
+  if (errorMessage) {
+    throw new Error(`Price quote failed: ${errorMessage}`)
+  }
+  const effects = execResult?.effects ?? execResult?.Success?.effect?.transforms ?? []
+  const resultHex = findProxyCallerResult(effects)
+  if (!resultHex) {
+    throw new Error("Price quote failed: no result returned")
+  }
+
+  // Bytes (u32 length prefix) wrapping a U512 (length byte followed by little-endian bytes)
+  const bytes = hexToUint8Array(resultHex).slice(4)
+  let price = 0n
+  for (let i = bytes[0]; i > 0; i--) {
+    price = (price << 8n) + BigInt(bytes[i])
+  }
+  return price.toString()
+}
+
 /** Helper to query contract state */
 // @ts-expect-error - Reserved for future use
 const queryContractState = async <T>(
@@ -666,6 +747,28 @@ export const getAccountHashFromPublicKey = (publicKeyHex: string): string => {
   }
 }
 
+// Option<T> argument builders (contract entry points take Option<u64>, Option<String>, ...)
+const optionU64 = (value?: number) =>
+  value === undefined
+    ? CLValueBuilder.option(None, new CLU64Type())
+    : CLValueBuilder.option(Some(CLValueBuilder.u64(value)))
+
+const optionString = (value?: string) =>
+  value === undefined || value === ""
+    ? CLValueBuilder.option(None, new CLStringType())
+    : CLValueBuilder.option(Some(CLValueBuilder.string(value)))
+
+const optionU512 = (value?: string) =>
+  value === undefined
+    ? CLValueBuilder.option(None, new CLU512Type())
+    : CLValueBuilder.option(Some(CLValueBuilder.u512(value)))
+
+// List<String> argument builder (an empty list still needs its element type)
+const stringList = (values: string[] = []) =>
+  values.length === 0
+    ? new CLList(new CLStringType())
+    : CLValueBuilder.list(values.map(value => CLValueBuilder.string(value)))
+
 export const useUploadSample = () => {
   const { account, signDeploy } = useCasperWallet()
   const queryClient = useQueryClient()
@@ -683,12 +786,18 @@ export const useUploadSample = () => {
       // Build arguments for upload_sample entry point
       const args = RuntimeArgs.fromMap({
         price: CLValueBuilder.u512(request.price.toString()),
+        pay_what_you_want: CLValueBuilder.bool(request.pay_what_you_want ?? false),
         ipfs_link: CLValueBuilder.string(request.ipfs_link),
         title: CLValueBuilder.string(request.title),
         bpm: CLValueBuilder.u64(request.bpm),
         genre: CLValueBuilder.string(request.genre),
         cover_image: CLValueBuilder.string(request.cover_image),
         video_preview_link: CLValueBuilder.string(request.video_preview_link || ""),
+        sale_starts_at: optionU64(request.sale_starts_at),
+        sale_ends_at: optionU64(request.sale_ends_at),
+        content_hash: CLValueBuilder.string(request.content_hash),
+        fingerprint: optionString(request.fingerprint),
+        pricing_template: optionString(request.pricing_template),
       })
 
       // Build the deploy
@@ -838,7 +947,7 @@ export const usePurchaseSample = () => {
         throw new Error("Contract hash not configured")
       }
 
-      // First, get the sample to know the price
+      // First, make sure the sample exists
       const allSamples = await fetchAllSamples()
       const sample = allSamples.find(s => s.sample_id === sampleId)
       if (!sample) {
@@ -846,13 +955,22 @@ export const usePurchaseSample = () => {
       }
 
       const sampleIdNum = parseInt(sampleId, 10)
-      const price = sample.price // Price in motes
+
+      // Quote the current price (in motes) from the contract; it is also the
+      // maximum we accept, so the purchase reverts if the seller raises it meanwhile
+      const price = await quoteContractPrice(
+        account.publicKey,
+        signDeploy,
+        "get_sample_price",
+        RuntimeArgs.fromMap({ sample_id: CLValueBuilder.u64(sampleIdNum) })
+      )
 
       // Build arguments for purchase_sample entry point
-      // Only pass the function-specific args (sample_id)
+      // Only pass the function-specific args (sample_id, max_price)
       // The proxy_caller handles the cargo_purse for payable functions
       const args = RuntimeArgs.fromMap({
         sample_id: CLValueBuilder.u64(sampleIdNum),
+        max_price: optionU512(price),
       })
 
       // Build the deploy using proxy_caller for payable function
@@ -1350,22 +1468,48 @@ export const usePurchaseSampleLicense = () => {
         throw new Error("Contract hash not configured")
       }
 
-      const { sample_id, license_type } = payload
+      const { sample_id, license_type, accepted_terms, territories } = payload
 
-      // First, get the sample to calculate the license price
+      // First, make sure the sample exists
       const allSamples = await fetchAllSamples()
       const sample = allSamples.find(s => s.sample_id === String(sample_id))
       if (!sample) {
         throw new Error("Sample not found")
       }
 
-      // Calculate license price (using default pricing for now)
-      const licensePrice = calculateLicensePrice(sample.price, license_type)
+      // Quote the license price (scaled to the territories, if restricted) from the contract;
+      // it is also the maximum we accept, so the purchase reverts if the seller raises it meanwhile
+      const licensePrice = territories && territories.length > 0
+        ? await quoteContractPrice(
+            account.publicKey,
+            signDeploy,
+            "get_territory_license_price",
+            RuntimeArgs.fromMap({
+              sample_id: CLValueBuilder.u64(sample_id),
+              license_type: CLValueBuilder.u8(license_type),
+              territories: stringList(territories),
+            })
+          )
+        : await quoteContractPrice(
+            account.publicKey,
+            signDeploy,
+            "get_license_price",
+            RuntimeArgs.fromMap({
+              sample_id: CLValueBuilder.u64(sample_id),
+              license_type: CLValueBuilder.u8(license_type),
+            })
+          )
+      if (licensePrice === "0") {
+        throw new Error("This license is not available for the selected territories")
+      }
 
       // Build arguments for purchase_sample_license entry point
       const args = RuntimeArgs.fromMap({
         sample_id: CLValueBuilder.u64(sample_id),
         license_type: CLValueBuilder.u8(license_type),
+        accepted_terms: optionString(accepted_terms),
+        territories: stringList(territories),
+        max_price: optionU512(licensePrice),
       })
 
       // Build the deploy using proxy_caller for payable function